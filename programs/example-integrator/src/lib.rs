@@ -0,0 +1,159 @@
+use anchor_lang::prelude::*;
+use oraculo::cpi::accounts::{GetMarketPrice, PlaceBet};
+use oraculo::program::Oraculo;
+
+declare_id!("AWrMzs3XQzxgN6wh6Z9tCZCV19C9H7Z44eKY37ZcJMuU");
+
+/// Minimal Oráculo integration: reads a market's TWAP through
+/// `get_market_price` and only places the bet if the outcome is still cheap.
+#[program]
+pub mod example_integrator {
+    use super::*;
+
+    /// Bet on an outcome via CPI if its TWAP is at or below `max_price` (bps).
+    /// The bet itself may not cost more than `max_price` per share, fees included.
+    pub fn bet_below_price(
+        ctx: Context<BetBelowPrice>,
+        amount: u64,
+        bet_on_yes: bool,
+        max_price: u16,
+        twap_window: i64,
+    ) -> Result<()> {
+        let oraculo_program = ctx.accounts.oraculo_program.to_account_info();
+
+        let price = oraculo::cpi::get_market_price(
+            CpiContext::new(
+                oraculo_program.clone(),
+                GetMarketPrice {
+                    market: ctx.accounts.market.to_account_info(),
+                    price_history: ctx.accounts.price_history.to_account_info(),
+                },
+            ),
+            Some(twap_window),
+        )?
+        .get();
+
+        let yes_twap = price.twap.ok_or(ErrorCode::PriceUnavailable)?;
+        let outcome_price = if bet_on_yes {
+            yes_twap
+        } else {
+            oraculo::state::PRICE_SCALE_BPS - yes_twap
+        };
+        require!(outcome_price <= max_price, ErrorCode::PriceAboveLimit);
+
+        // The TWAP can lag the spot price, so bound what the bet pays as well
+        let max_cost = (amount as u128 * max_price as u128
+            / oraculo::state::PRICE_SCALE_BPS as u128) as u64;
+
+        oraculo::cpi::place_bet(
+            CpiContext::new(
+                oraculo_program,
+                PlaceBet {
+                    config: ctx.accounts.config.to_account_info(),
+                    market: ctx.accounts.market.to_account_info(),
+                    price_history: ctx.accounts.price_history.to_account_info(),
+                    user: ctx.accounts.user.to_account_info(),
+                    user_collateral: ctx.accounts.user_collateral.to_account_info(),
+                    liquidity_vault: ctx.accounts.liquidity_vault.to_account_info(),
+                    treasury_vault: ctx.accounts.treasury_vault.to_account_info(),
+                    collateral: ctx.accounts.collateral.to_account_info(),
+                    collateral_mint: ctx.accounts.collateral_mint.to_account_info(),
+                    outcome_token_mint: ctx.accounts.outcome_token_mint.to_account_info(),
+                    user_outcome_token: ctx.accounts.user_outcome_token.to_account_info(),
+                    gauge: None,
+                    reward_position: None,
+                    token_program: ctx.accounts.token_program.to_account_info(),
+                    associated_token_program: ctx.accounts.associated_token_program.to_account_info(),
+                    system_program: ctx.accounts.system_program.to_account_info(),
+                    rent: ctx.accounts.rent.to_account_info(),
+                },
+            ),
+            amount,
+            bet_on_yes,
+            max_cost,
+        )?;
+
+        emit!(BetBelowPricePlaced {
+            market: ctx.accounts.market.key(),
+            user: ctx.accounts.user.key(),
+            bet_on_yes,
+            amount,
+            twap: yes_twap,
+        });
+
+        Ok(())
+    }
+}
+
+/// Oráculo validates every account it is passed, so they are forwarded unchecked
+#[derive(Accounts)]
+pub struct BetBelowPrice<'info> {
+    /// CHECK: validated by Oráculo
+    #[account(mut)]
+    pub config: UncheckedAccount<'info>,
+
+    /// CHECK: validated by Oráculo
+    #[account(mut)]
+    pub market: UncheckedAccount<'info>,
+
+    /// CHECK: validated by Oráculo
+    #[account(mut)]
+    pub price_history: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    /// CHECK: validated by Oráculo
+    #[account(mut)]
+    pub user_collateral: UncheckedAccount<'info>,
+
+    /// CHECK: validated by Oráculo
+    #[account(mut)]
+    pub liquidity_vault: UncheckedAccount<'info>,
+
+    /// CHECK: validated by Oráculo
+    #[account(mut)]
+    pub treasury_vault: UncheckedAccount<'info>,
+
+    /// CHECK: validated by Oráculo
+    pub collateral: UncheckedAccount<'info>,
+
+    /// CHECK: validated by Oráculo
+    pub collateral_mint: UncheckedAccount<'info>,
+
+    /// CHECK: validated by Oráculo
+    #[account(mut)]
+    pub outcome_token_mint: UncheckedAccount<'info>,
+
+    /// CHECK: validated by Oráculo
+    #[account(mut)]
+    pub user_outcome_token: UncheckedAccount<'info>,
+
+    pub oraculo_program: Program<'info, Oraculo>,
+
+    /// CHECK: validated by Oráculo
+    pub token_program: UncheckedAccount<'info>,
+
+    /// CHECK: validated by Oráculo
+    pub associated_token_program: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+#[event]
+pub struct BetBelowPricePlaced {
+    pub market: Pubkey,
+    pub user: Pubkey,
+    pub bet_on_yes: bool,
+    pub amount: u64,
+    pub twap: u16,
+}
+
+#[error_code]
+pub enum ErrorCode {
+    #[msg("Market price is above the limit")]
+    PriceAboveLimit,
+    #[msg("Market TWAP unavailable")]
+    PriceUnavailable,
+}
@@ -3,10 +3,24 @@ use anchor_spl::token::{self, Token, TokenAccount, Mint, MintTo};
 
 declare_id!("BJBEwqCTA8kPehiqV7jzSRKonbhGVDfWSuPFSvw7kRoN");
 
-pub mod instructions;
-pub mod state;
-
-use instructions::*;
+// Instruction accounts and handlers live in the inline modules below
+use instructions::initialize::*;
+use instructions::create_emission_schedule::*;
+use instructions::mint_tokens::*;
+use instructions::create_token_metadata::*;
+use instructions::update_token_metadata::*;
+use instructions::create_vesting::*;
+use instructions::claim_vested::*;
+use instructions::revoke_vesting::*;
+use instructions::create_distributor::*;
+use instructions::claim_airdrop::*;
+use instructions::clawback_airdrop::*;
+use instructions::initialize_stake_pool::*;
+use instructions::stake::*;
+use instructions::unstake::*;
+use instructions::withdraw_unbonded::*;
+use instructions::claim_staking_rewards::*;
+use state::*;
 
 #[program]
 pub mod governance_token {
@@ -15,15 +29,129 @@ pub mod governance_token {
     pub fn initialize(
         ctx: Context<Initialize>,
         decimals: u8,
+        max_supply: u64,
+    ) -> Result<()> {
+        instructions::initialize::handler(ctx, decimals, max_supply)
+    }
+
+    pub fn create_emission_schedule(
+        ctx: Context<CreateEmissionSchedule>,
+        start_time: i64,
+        epoch_duration: i64,
+        buckets: Vec<EmissionBucketParams>,
     ) -> Result<()> {
-        instructions::initialize::handler(ctx, decimals)
+        instructions::create_emission_schedule::handler(ctx, start_time, epoch_duration, buckets)
     }
 
     pub fn mint_tokens(
         ctx: Context<MintTokens>,
+        bucket: u8,
+        amount: u64,
+    ) -> Result<()> {
+        instructions::mint_tokens::handler(ctx, bucket, amount)
+    }
+
+    pub fn create_token_metadata(
+        ctx: Context<CreateTokenMetadata>,
+        name: String,
+        symbol: String,
+        uri: String,
+    ) -> Result<()> {
+        instructions::create_token_metadata::handler(ctx, name, symbol, uri)
+    }
+
+    pub fn update_token_metadata(
+        ctx: Context<UpdateTokenMetadata>,
+        name: String,
+        symbol: String,
+        uri: String,
+    ) -> Result<()> {
+        instructions::update_token_metadata::handler(ctx, name, symbol, uri)
+    }
+
+    pub fn create_vesting(
+        ctx: Context<CreateVesting>,
+        params: CreateVestingParams,
+    ) -> Result<()> {
+        instructions::create_vesting::handler(ctx, params)
+    }
+
+    pub fn claim_vested(
+        ctx: Context<ClaimVested>,
+    ) -> Result<()> {
+        instructions::claim_vested::handler(ctx)
+    }
+
+    pub fn revoke_vesting(
+        ctx: Context<RevokeVesting>,
+    ) -> Result<()> {
+        instructions::revoke_vesting::handler(ctx)
+    }
+
+    pub fn create_distributor(
+        ctx: Context<CreateDistributor>,
+        id: u64,
+        root: [u8; 32],
+        max_total_claim: u64,
+        max_num_nodes: u64,
+        clawback_start_ts: i64,
+    ) -> Result<()> {
+        instructions::create_distributor::handler(
+            ctx,
+            id,
+            root,
+            max_total_claim,
+            max_num_nodes,
+            clawback_start_ts,
+        )
+    }
+
+    pub fn claim_airdrop(
+        ctx: Context<ClaimAirdrop>,
+        index: u64,
+        amount: u64,
+        proof: Vec<[u8; 32]>,
+    ) -> Result<()> {
+        instructions::claim_airdrop::handler(ctx, index, amount, proof)
+    }
+
+    pub fn clawback_airdrop(
+        ctx: Context<ClawbackAirdrop>,
+    ) -> Result<()> {
+        instructions::clawback_airdrop::handler(ctx)
+    }
+
+    pub fn initialize_stake_pool(
+        ctx: Context<InitializeStakePool>,
+        unbonding_period: i64,
+    ) -> Result<()> {
+        instructions::initialize_stake_pool::handler(ctx, unbonding_period)
+    }
+
+    pub fn stake(
+        ctx: Context<Stake>,
+        amount: u64,
+    ) -> Result<()> {
+        instructions::stake::handler(ctx, amount)
+    }
+
+    pub fn unstake(
+        ctx: Context<Unstake>,
         amount: u64,
     ) -> Result<()> {
-        instructions::mint_tokens::handler(ctx, amount)
+        instructions::unstake::handler(ctx, amount)
+    }
+
+    pub fn withdraw_unbonded(
+        ctx: Context<WithdrawUnbonded>,
+    ) -> Result<()> {
+        instructions::withdraw_unbonded::handler(ctx)
+    }
+
+    pub fn claim_staking_rewards(
+        ctx: Context<ClaimStakingRewards>,
+    ) -> Result<()> {
+        instructions::claim_staking_rewards::handler(ctx)
     }
 }
 
@@ -32,26 +160,35 @@ pub mod instructions {
 
     pub mod initialize {
         use super::*;
+        use crate::errors::ErrorCode;
 
         pub fn handler(
             ctx: Context<Initialize>,
-            _decimals: u8,
+            decimals: u8,
+            max_supply: u64,
         ) -> Result<()> {
+            require!(max_supply > 0, ErrorCode::InvalidMaxSupply);
+
             let config = &mut ctx.accounts.config;
             config.authority = ctx.accounts.authority.key();
             config.mint = ctx.accounts.mint.key();
+            config.decimals = decimals;
+            config.max_supply = max_supply;
             config.total_supply = 0;
             config.bump = ctx.bumps.config;
 
             emit!(TokenInitialized {
                 authority: config.authority,
                 mint: config.mint,
+                decimals,
+                max_supply,
             });
 
             Ok(())
         }
 
         #[derive(Accounts)]
+        #[instruction(decimals: u8)]
         pub struct Initialize<'info> {
             #[account(
                 init,
@@ -65,7 +202,7 @@ pub mod instructions {
             #[account(
                 init,
                 payer = authority,
-                mint::decimals = 9,
+                mint::decimals = decimals,
                 mint::authority = config,
                 seeds = [b"mint"],
                 bump
@@ -84,6 +221,116 @@ pub mod instructions {
         pub struct TokenInitialized {
             pub authority: Pubkey,
             pub mint: Pubkey,
+            pub decimals: u8,
+            pub max_supply: u64,
+        }
+    }
+
+    pub mod create_emission_schedule {
+        use super::*;
+        use crate::errors::ErrorCode;
+
+        #[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+        pub struct EmissionBucketParams {
+            pub name: String,
+            pub minter: Pubkey,
+            pub epoch_allowances: Vec<u64>,
+        }
+
+        pub fn handler(
+            ctx: Context<CreateEmissionSchedule>,
+            start_time: i64,
+            epoch_duration: i64,
+            buckets: Vec<EmissionBucketParams>,
+        ) -> Result<()> {
+            let config = &ctx.accounts.config;
+
+            require!(epoch_duration > 0, ErrorCode::InvalidEpochDuration);
+            require!(
+                !buckets.is_empty() && buckets.len() <= EmissionSchedule::MAX_BUCKETS,
+                ErrorCode::InvalidBucketCount
+            );
+
+            let mut scheduled: u64 = 0;
+            let mut emission_buckets = Vec::with_capacity(buckets.len());
+            for params in buckets {
+                require!(
+                    params.name.len() <= EmissionBucket::MAX_NAME_LEN,
+                    ErrorCode::BucketNameTooLong
+                );
+                require!(
+                    !params.epoch_allowances.is_empty()
+                        && params.epoch_allowances.len() <= EmissionBucket::MAX_EPOCHS,
+                    ErrorCode::InvalidEpochCount
+                );
+
+                let bucket = EmissionBucket {
+                    name: params.name,
+                    minter: params.minter,
+                    epoch_allowances: params.epoch_allowances,
+                    minted: 0,
+                };
+                scheduled = scheduled
+                    .checked_add(bucket.total_allowance().ok_or(ErrorCode::MathOverflow)?)
+                    .ok_or(ErrorCode::MathOverflow)?;
+                emission_buckets.push(bucket);
+            }
+
+            // The whole schedule must fit under the remaining supply cap
+            let remaining = config.max_supply
+                .checked_sub(config.total_supply)
+                .ok_or(ErrorCode::MathOverflow)?;
+            require!(scheduled <= remaining, ErrorCode::ScheduleExceedsMaxSupply);
+
+            let schedule = &mut ctx.accounts.emission_schedule;
+            schedule.config = config.key();
+            schedule.start_time = start_time;
+            schedule.epoch_duration = epoch_duration;
+            schedule.buckets = emission_buckets;
+            schedule.bump = ctx.bumps.emission_schedule;
+
+            emit!(EmissionScheduleCreated {
+                schedule: schedule.key(),
+                start_time,
+                epoch_duration,
+                bucket_count: schedule.buckets.len() as u8,
+                total_scheduled: scheduled,
+            });
+
+            Ok(())
+        }
+
+        #[derive(Accounts)]
+        pub struct CreateEmissionSchedule<'info> {
+            #[account(
+                seeds = [b"config"],
+                bump = config.bump,
+                has_one = authority
+            )]
+            pub config: Account<'info, TokenConfig>,
+
+            #[account(
+                init,
+                payer = authority,
+                space = 8 + EmissionSchedule::INIT_SPACE,
+                seeds = [b"emission_schedule"],
+                bump
+            )]
+            pub emission_schedule: Account<'info, EmissionSchedule>,
+
+            #[account(mut)]
+            pub authority: Signer<'info>,
+
+            pub system_program: Program<'info, System>,
+        }
+
+        #[event]
+        pub struct EmissionScheduleCreated {
+            pub schedule: Pubkey,
+            pub start_time: i64,
+            pub epoch_duration: i64,
+            pub bucket_count: u8,
+            pub total_scheduled: u64,
         }
     }
 
@@ -92,11 +339,22 @@ pub mod instructions {
 
         pub fn handler(
             ctx: Context<MintTokens>,
+            bucket: u8,
             amount: u64,
         ) -> Result<()> {
             let config = &mut ctx.accounts.config;
+            let schedule = &mut ctx.accounts.emission_schedule;
 
-            let seeds = &[b"config", &[config.bump]];
+            let clock = Clock::get()?;
+            let epoch = schedule.reserve(
+                bucket,
+                &ctx.accounts.minter.key(),
+                amount,
+                clock.unix_timestamp,
+            )?;
+            config.record_mint(amount)?;
+
+            let seeds = &[b"config".as_ref(), &[config.bump]];
             let signer = &[&seeds[..]];
 
             token::mint_to(
@@ -112,10 +370,10 @@ pub mod instructions {
                 amount,
             )?;
 
-            config.total_supply = config.total_supply.checked_add(amount).unwrap();
-
             emit!(TokensMinted {
                 recipient: ctx.accounts.destination.owner,
+                bucket,
+                epoch,
                 amount,
                 total_supply: config.total_supply,
             });
@@ -128,13 +386,18 @@ pub mod instructions {
             #[account(
                 mut,
                 seeds = [b"config"],
-                bump = config.bump,
-                has_one = authority
+                bump = config.bump
             )]
             pub config: Account<'info, TokenConfig>,
 
-            #[account(mut)]
-            pub authority: Signer<'info>,
+            #[account(
+                mut,
+                seeds = [b"emission_schedule"],
+                bump = emission_schedule.bump
+            )]
+            pub emission_schedule: Account<'info, EmissionSchedule>,
+
+            pub minter: Signer<'info>,
 
             #[account(
                 mut,
@@ -152,21 +415,1854 @@ pub mod instructions {
         #[event]
         pub struct TokensMinted {
             pub recipient: Pubkey,
+            pub bucket: u8,
+            pub epoch: u64,
             pub amount: u64,
             pub total_supply: u64,
         }
     }
-}
+    pub mod create_token_metadata {
+        use super::*;
+        use anchor_spl::metadata::{
+            create_metadata_accounts_v3, mpl_token_metadata::types::DataV2,
+            CreateMetadataAccountsV3, Metadata,
+        };
 
-pub mod state {
-    use super::*;
+        pub fn handler(
+            ctx: Context<CreateTokenMetadata>,
+            name: String,
+            symbol: String,
+            uri: String,
+        ) -> Result<()> {
+            let config = &ctx.accounts.config;
 
-    #[account]
-    #[derive(InitSpace)]
-    pub struct TokenConfig {
-        pub authority: Pubkey,
-        pub mint: Pubkey,
-        pub total_supply: u64,
-        pub bump: u8,
+            let seeds = &[b"config".as_ref(), &[config.bump]];
+            let signer = &[&seeds[..]];
+
+            // The config PDA is both mint authority and update authority
+            create_metadata_accounts_v3(
+                CpiContext::new_with_signer(
+                    ctx.accounts.token_metadata_program.to_account_info(),
+                    CreateMetadataAccountsV3 {
+                        metadata: ctx.accounts.metadata.to_account_info(),
+                        mint: ctx.accounts.mint.to_account_info(),
+                        mint_authority: config.to_account_info(),
+                        payer: ctx.accounts.authority.to_account_info(),
+                        update_authority: config.to_account_info(),
+                        system_program: ctx.accounts.system_program.to_account_info(),
+                        rent: ctx.accounts.rent.to_account_info(),
+                    },
+                    signer,
+                ),
+                DataV2 {
+                    name: name.clone(),
+                    symbol: symbol.clone(),
+                    uri: uri.clone(),
+                    seller_fee_basis_points: 0,
+                    creators: None,
+                    collection: None,
+                    uses: None,
+                },
+                true,
+                true,
+                None,
+            )?;
+
+            emit!(TokenMetadataUpdated {
+                mint: ctx.accounts.mint.key(),
+                name,
+                symbol,
+                uri,
+            });
+
+            Ok(())
+        }
+
+        #[derive(Accounts)]
+        pub struct CreateTokenMetadata<'info> {
+            #[account(
+                seeds = [b"config"],
+                bump = config.bump,
+                has_one = authority
+            )]
+            pub config: Account<'info, TokenConfig>,
+
+            #[account(seeds = [b"mint"], bump)]
+            pub mint: Account<'info, Mint>,
+
+            /// CHECK: Created and validated by the token metadata program
+            #[account(
+                mut,
+                seeds = [b"metadata", token_metadata_program.key().as_ref(), mint.key().as_ref()],
+                bump,
+                seeds::program = token_metadata_program.key()
+            )]
+            pub metadata: UncheckedAccount<'info>,
+
+            #[account(mut)]
+            pub authority: Signer<'info>,
+
+            pub token_metadata_program: Program<'info, Metadata>,
+            pub system_program: Program<'info, System>,
+            pub rent: Sysvar<'info, Rent>,
+        }
+
+        #[event]
+        pub struct TokenMetadataUpdated {
+            pub mint: Pubkey,
+            pub name: String,
+            pub symbol: String,
+            pub uri: String,
+        }
+    }
+
+    pub mod update_token_metadata {
+        use super::*;
+        use anchor_spl::metadata::{
+            mpl_token_metadata::types::DataV2, update_metadata_accounts_v2, Metadata,
+            UpdateMetadataAccountsV2,
+        };
+        use super::create_token_metadata::TokenMetadataUpdated;
+
+        pub fn handler(
+            ctx: Context<UpdateTokenMetadata>,
+            name: String,
+            symbol: String,
+            uri: String,
+        ) -> Result<()> {
+            let config = &ctx.accounts.config;
+
+            let seeds = &[b"config".as_ref(), &[config.bump]];
+            let signer = &[&seeds[..]];
+
+            update_metadata_accounts_v2(
+                CpiContext::new_with_signer(
+                    ctx.accounts.token_metadata_program.to_account_info(),
+                    UpdateMetadataAccountsV2 {
+                        metadata: ctx.accounts.metadata.to_account_info(),
+                        update_authority: config.to_account_info(),
+                    },
+                    signer,
+                ),
+                None,
+                Some(DataV2 {
+                    name: name.clone(),
+                    symbol: symbol.clone(),
+                    uri: uri.clone(),
+                    seller_fee_basis_points: 0,
+                    creators: None,
+                    collection: None,
+                    uses: None,
+                }),
+                None,
+                None,
+            )?;
+
+            emit!(TokenMetadataUpdated {
+                mint: ctx.accounts.mint.key(),
+                name,
+                symbol,
+                uri,
+            });
+
+            Ok(())
+        }
+
+        #[derive(Accounts)]
+        pub struct UpdateTokenMetadata<'info> {
+            #[account(
+                seeds = [b"config"],
+                bump = config.bump,
+                has_one = authority
+            )]
+            pub config: Account<'info, TokenConfig>,
+
+            #[account(seeds = [b"mint"], bump)]
+            pub mint: Account<'info, Mint>,
+
+            /// CHECK: Validated by the token metadata program
+            #[account(
+                mut,
+                seeds = [b"metadata", token_metadata_program.key().as_ref(), mint.key().as_ref()],
+                bump,
+                seeds::program = token_metadata_program.key()
+            )]
+            pub metadata: UncheckedAccount<'info>,
+
+            pub authority: Signer<'info>,
+
+            pub token_metadata_program: Program<'info, Metadata>,
+        }
+    }
+
+    pub mod create_vesting {
+        use super::*;
+        use anchor_spl::token::Transfer;
+        use crate::errors::ErrorCode;
+
+        #[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq)]
+        pub enum VestingFunding {
+            /// Transfer `total_amount` from the funder's token account
+            Transfer,
+            /// Mint `total_amount` from an emission bucket the funder controls
+            Emission { bucket: u8 },
+        }
+
+        #[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+        pub struct CreateVestingParams {
+            pub id: u64,
+            pub start_time: i64,
+            pub cliff_seconds: i64,
+            pub duration: i64,
+            pub total_amount: u64,
+            pub revocable: bool,
+            pub funding: VestingFunding,
+        }
+
+        pub fn handler(
+            ctx: Context<CreateVesting>,
+            params: CreateVestingParams,
+        ) -> Result<()> {
+            let CreateVestingParams {
+                id,
+                start_time,
+                cliff_seconds,
+                duration,
+                total_amount,
+                revocable,
+                funding,
+            } = params;
+
+            require!(total_amount > 0, ErrorCode::ZeroAmount);
+            require!(
+                duration > 0 && cliff_seconds >= 0 && cliff_seconds <= duration,
+                ErrorCode::InvalidVestingSchedule
+            );
+
+            match funding {
+                VestingFunding::Transfer => {
+                    let funder_token = ctx.accounts.funder_token
+                        .as_ref()
+                        .ok_or(ErrorCode::MissingFundingAccount)?;
+
+                    token::transfer(
+                        CpiContext::new(
+                            ctx.accounts.token_program.to_account_info(),
+                            Transfer {
+                                from: funder_token.to_account_info(),
+                                to: ctx.accounts.vesting_vault.to_account_info(),
+                                authority: ctx.accounts.funder.to_account_info(),
+                            },
+                        ),
+                        total_amount,
+                    )?;
+                }
+                VestingFunding::Emission { bucket } => {
+                    let schedule = ctx.accounts.emission_schedule
+                        .as_mut()
+                        .ok_or(ErrorCode::MissingFundingAccount)?;
+                    let config = &mut ctx.accounts.config;
+
+                    schedule.reserve(
+                        bucket,
+                        &ctx.accounts.funder.key(),
+                        total_amount,
+                        Clock::get()?.unix_timestamp,
+                    )?;
+                    config.record_mint(total_amount)?;
+
+                    let seeds = &[b"config".as_ref(), &[config.bump]];
+                    let signer = &[&seeds[..]];
+
+                    token::mint_to(
+                        CpiContext::new_with_signer(
+                            ctx.accounts.token_program.to_account_info(),
+                            MintTo {
+                                mint: ctx.accounts.mint.to_account_info(),
+                                to: ctx.accounts.vesting_vault.to_account_info(),
+                                authority: config.to_account_info(),
+                            },
+                            signer,
+                        ),
+                        total_amount,
+                    )?;
+                }
+            }
+
+            let vesting = &mut ctx.accounts.vesting;
+            vesting.id = id;
+            vesting.beneficiary = ctx.accounts.beneficiary.key();
+            vesting.funder = ctx.accounts.funder.key();
+            vesting.start_time = start_time;
+            vesting.cliff_time = start_time
+                .checked_add(cliff_seconds)
+                .ok_or(ErrorCode::MathOverflow)?;
+            vesting.end_time = start_time
+                .checked_add(duration)
+                .ok_or(ErrorCode::MathOverflow)?;
+            vesting.total_amount = total_amount;
+            vesting.released = 0;
+            vesting.revocable = revocable;
+            vesting.revoked = false;
+            vesting.bump = ctx.bumps.vesting;
+
+            emit!(VestingCreated {
+                vesting: vesting.key(),
+                beneficiary: vesting.beneficiary,
+                funder: vesting.funder,
+                start_time,
+                cliff_time: vesting.cliff_time,
+                end_time: vesting.end_time,
+                total_amount,
+                revocable,
+            });
+
+            Ok(())
+        }
+
+        #[derive(Accounts)]
+        #[instruction(params: CreateVestingParams)]
+        pub struct CreateVesting<'info> {
+            #[account(
+                mut,
+                seeds = [b"config"],
+                bump = config.bump
+            )]
+            pub config: Account<'info, TokenConfig>,
+
+            #[account(
+                init,
+                payer = funder,
+                space = 8 + VestingAccount::INIT_SPACE,
+                seeds = [
+                    b"vesting".as_ref(),
+                    beneficiary.key().as_ref(),
+                    &params.id.to_le_bytes()
+                ],
+                bump
+            )]
+            pub vesting: Account<'info, VestingAccount>,
+
+            #[account(
+                init,
+                payer = funder,
+                token::mint = mint,
+                token::authority = vesting,
+                seeds = [b"vesting_vault", vesting.key().as_ref()],
+                bump
+            )]
+            pub vesting_vault: Account<'info, TokenAccount>,
+
+            /// CHECK: Only recorded as the beneficiary of the schedule
+            pub beneficiary: UncheckedAccount<'info>,
+
+            #[account(mut)]
+            pub funder: Signer<'info>,
+
+            #[account(
+                mut,
+                constraint = funder_token.owner == funder.key(),
+                constraint = funder_token.mint == mint.key()
+            )]
+            pub funder_token: Option<Account<'info, TokenAccount>>,
+
+            #[account(
+                mut,
+                seeds = [b"emission_schedule"],
+                bump = emission_schedule.bump
+            )]
+            pub emission_schedule: Option<Account<'info, EmissionSchedule>>,
+
+            #[account(
+                mut,
+                seeds = [b"mint"],
+                bump
+            )]
+            pub mint: Account<'info, Mint>,
+
+            pub token_program: Program<'info, Token>,
+            pub system_program: Program<'info, System>,
+            pub rent: Sysvar<'info, Rent>,
+        }
+
+        #[event]
+        pub struct VestingCreated {
+            pub vesting: Pubkey,
+            pub beneficiary: Pubkey,
+            pub funder: Pubkey,
+            pub start_time: i64,
+            pub cliff_time: i64,
+            pub end_time: i64,
+            pub total_amount: u64,
+            pub revocable: bool,
+        }
+    }
+
+    pub mod claim_vested {
+        use super::*;
+        use anchor_spl::token::Transfer;
+        use crate::errors::ErrorCode;
+
+        pub fn handler(ctx: Context<ClaimVested>) -> Result<()> {
+            let vesting = &mut ctx.accounts.vesting;
+
+            let clock = Clock::get()?;
+            let vested = vesting.vested_amount(clock.unix_timestamp)?;
+            let claimable = vested
+                .checked_sub(vesting.released)
+                .ok_or(ErrorCode::MathOverflow)?;
+            require!(claimable > 0, ErrorCode::NothingToClaim);
+
+            let beneficiary = vesting.beneficiary;
+            let id_bytes = vesting.id.to_le_bytes();
+            let seeds = &[
+                b"vesting".as_ref(),
+                beneficiary.as_ref(),
+                &id_bytes,
+                &[vesting.bump],
+            ];
+            let signer = &[&seeds[..]];
+
+            token::transfer(
+                CpiContext::new_with_signer(
+                    ctx.accounts.token_program.to_account_info(),
+                    Transfer {
+                        from: ctx.accounts.vesting_vault.to_account_info(),
+                        to: ctx.accounts.beneficiary_token.to_account_info(),
+                        authority: vesting.to_account_info(),
+                    },
+                    signer,
+                ),
+                claimable,
+            )?;
+
+            vesting.released = vested;
+
+            emit!(VestedClaimed {
+                vesting: vesting.key(),
+                beneficiary,
+                amount: claimable,
+                released: vesting.released,
+                total_amount: vesting.total_amount,
+            });
+
+            Ok(())
+        }
+
+        #[derive(Accounts)]
+        pub struct ClaimVested<'info> {
+            #[account(
+                mut,
+                has_one = beneficiary
+            )]
+            pub vesting: Account<'info, VestingAccount>,
+
+            #[account(
+                mut,
+                seeds = [b"vesting_vault", vesting.key().as_ref()],
+                bump
+            )]
+            pub vesting_vault: Account<'info, TokenAccount>,
+
+            pub beneficiary: Signer<'info>,
+
+            #[account(
+                mut,
+                constraint = beneficiary_token.owner == beneficiary.key(),
+                constraint = beneficiary_token.mint == vesting_vault.mint
+            )]
+            pub beneficiary_token: Account<'info, TokenAccount>,
+
+            pub token_program: Program<'info, Token>,
+        }
+
+        #[event]
+        pub struct VestedClaimed {
+            pub vesting: Pubkey,
+            pub beneficiary: Pubkey,
+            pub amount: u64,
+            pub released: u64,
+            pub total_amount: u64,
+        }
+    }
+
+    pub mod revoke_vesting {
+        use super::*;
+        use anchor_spl::token::Transfer;
+        use crate::errors::ErrorCode;
+
+        pub fn handler(ctx: Context<RevokeVesting>) -> Result<()> {
+            let vesting = &mut ctx.accounts.vesting;
+
+            require!(vesting.revocable, ErrorCode::NotRevocable);
+            require!(!vesting.revoked, ErrorCode::AlreadyRevoked);
+
+            let clock = Clock::get()?;
+            let vested = vesting.vested_amount(clock.unix_timestamp)?;
+            let unvested = vesting.total_amount
+                .checked_sub(vested)
+                .ok_or(ErrorCode::MathOverflow)?;
+
+            let beneficiary = vesting.beneficiary;
+            let id_bytes = vesting.id.to_le_bytes();
+            let seeds = &[
+                b"vesting".as_ref(),
+                beneficiary.as_ref(),
+                &id_bytes,
+                &[vesting.bump],
+            ];
+            let signer = &[&seeds[..]];
+
+            if unvested > 0 {
+                token::transfer(
+                    CpiContext::new_with_signer(
+                        ctx.accounts.token_program.to_account_info(),
+                        Transfer {
+                            from: ctx.accounts.vesting_vault.to_account_info(),
+                            to: ctx.accounts.funder_token.to_account_info(),
+                            authority: vesting.to_account_info(),
+                        },
+                        signer,
+                    ),
+                    unvested,
+                )?;
+            }
+
+            // Freeze the schedule at what has vested so far
+            vesting.total_amount = vested;
+            vesting.end_time = vesting.end_time.min(clock.unix_timestamp);
+            vesting.revoked = true;
+
+            emit!(VestingRevoked {
+                vesting: vesting.key(),
+                funder: vesting.funder,
+                returned: unvested,
+                vested,
+            });
+
+            Ok(())
+        }
+
+        #[derive(Accounts)]
+        pub struct RevokeVesting<'info> {
+            #[account(
+                mut,
+                has_one = funder
+            )]
+            pub vesting: Account<'info, VestingAccount>,
+
+            #[account(
+                mut,
+                seeds = [b"vesting_vault", vesting.key().as_ref()],
+                bump
+            )]
+            pub vesting_vault: Account<'info, TokenAccount>,
+
+            pub funder: Signer<'info>,
+
+            #[account(
+                mut,
+                constraint = funder_token.owner == funder.key(),
+                constraint = funder_token.mint == vesting_vault.mint
+            )]
+            pub funder_token: Account<'info, TokenAccount>,
+
+            pub token_program: Program<'info, Token>,
+        }
+
+        #[event]
+        pub struct VestingRevoked {
+            pub vesting: Pubkey,
+            pub funder: Pubkey,
+            pub returned: u64,
+            pub vested: u64,
+        }
+    }
+    pub mod create_distributor {
+        use super::*;
+        use anchor_spl::token::Transfer;
+        use crate::errors::ErrorCode;
+
+        pub fn handler(
+            ctx: Context<CreateDistributor>,
+            id: u64,
+            root: [u8; 32],
+            max_total_claim: u64,
+            max_num_nodes: u64,
+            clawback_start_ts: i64,
+        ) -> Result<()> {
+            require!(max_total_claim > 0, ErrorCode::ZeroAmount);
+            require!(
+                clawback_start_ts > Clock::get()?.unix_timestamp,
+                ErrorCode::InvalidClawbackTime
+            );
+
+            // Fund the whole airdrop up front
+            token::transfer(
+                CpiContext::new(
+                    ctx.accounts.token_program.to_account_info(),
+                    Transfer {
+                        from: ctx.accounts.admin_token.to_account_info(),
+                        to: ctx.accounts.distributor_vault.to_account_info(),
+                        authority: ctx.accounts.admin.to_account_info(),
+                    },
+                ),
+                max_total_claim,
+            )?;
+
+            let distributor = &mut ctx.accounts.distributor;
+            distributor.id = id;
+            distributor.admin = ctx.accounts.admin.key();
+            distributor.mint = ctx.accounts.mint.key();
+            distributor.root = root;
+            distributor.max_total_claim = max_total_claim;
+            distributor.max_num_nodes = max_num_nodes;
+            distributor.total_amount_claimed = 0;
+            distributor.num_nodes_claimed = 0;
+            distributor.clawback_start_ts = clawback_start_ts;
+            distributor.clawback_receiver = ctx.accounts.admin_token.key();
+            distributor.clawed_back = false;
+            distributor.bump = ctx.bumps.distributor;
+
+            emit!(DistributorCreated {
+                distributor: distributor.key(),
+                admin: distributor.admin,
+                root,
+                max_total_claim,
+                max_num_nodes,
+                clawback_start_ts,
+            });
+
+            Ok(())
+        }
+
+        #[derive(Accounts)]
+        #[instruction(id: u64)]
+        pub struct CreateDistributor<'info> {
+            #[account(
+                init,
+                payer = admin,
+                space = 8 + MerkleDistributor::INIT_SPACE,
+                seeds = [b"distributor".as_ref(), &id.to_le_bytes()],
+                bump
+            )]
+            pub distributor: Account<'info, MerkleDistributor>,
+
+            #[account(
+                init,
+                payer = admin,
+                token::mint = mint,
+                token::authority = distributor,
+                seeds = [b"distributor_vault", distributor.key().as_ref()],
+                bump
+            )]
+            pub distributor_vault: Account<'info, TokenAccount>,
+
+            #[account(mut)]
+            pub admin: Signer<'info>,
+
+            #[account(
+                mut,
+                constraint = admin_token.owner == admin.key(),
+                constraint = admin_token.mint == mint.key()
+            )]
+            pub admin_token: Account<'info, TokenAccount>,
+
+            #[account(seeds = [b"mint"], bump)]
+            pub mint: Account<'info, Mint>,
+
+            pub token_program: Program<'info, Token>,
+            pub system_program: Program<'info, System>,
+            pub rent: Sysvar<'info, Rent>,
+        }
+
+        #[event]
+        pub struct DistributorCreated {
+            pub distributor: Pubkey,
+            pub admin: Pubkey,
+            pub root: [u8; 32],
+            pub max_total_claim: u64,
+            pub max_num_nodes: u64,
+            pub clawback_start_ts: i64,
+        }
+    }
+
+    pub mod claim_airdrop {
+        use super::*;
+        use anchor_spl::token::Transfer;
+        use crate::errors::ErrorCode;
+        use crate::merkle;
+
+        pub fn handler(
+            ctx: Context<ClaimAirdrop>,
+            index: u64,
+            amount: u64,
+            proof: Vec<[u8; 32]>,
+        ) -> Result<()> {
+            let distributor = &mut ctx.accounts.distributor;
+            let claimant = ctx.accounts.claimant.key();
+
+            let clock = Clock::get()?;
+            require!(
+                clock.unix_timestamp < distributor.clawback_start_ts && !distributor.clawed_back,
+                ErrorCode::AirdropExpired
+            );
+
+            let leaf = merkle::leaf(index, &claimant, amount);
+            require!(
+                merkle::verify(&proof, distributor.root, leaf),
+                ErrorCode::InvalidProof
+            );
+
+            let total_amount_claimed = distributor.total_amount_claimed
+                .checked_add(amount)
+                .ok_or(ErrorCode::MathOverflow)?;
+            require!(
+                total_amount_claimed <= distributor.max_total_claim,
+                ErrorCode::ExceededMaxClaim
+            );
+            let num_nodes_claimed = distributor.num_nodes_claimed
+                .checked_add(1)
+                .ok_or(ErrorCode::MathOverflow)?;
+            require!(
+                num_nodes_claimed <= distributor.max_num_nodes,
+                ErrorCode::ExceededMaxNumNodes
+            );
+
+            let id_bytes = distributor.id.to_le_bytes();
+            let seeds = &[b"distributor".as_ref(), &id_bytes, &[distributor.bump]];
+            let signer = &[&seeds[..]];
+
+            token::transfer(
+                CpiContext::new_with_signer(
+                    ctx.accounts.token_program.to_account_info(),
+                    Transfer {
+                        from: ctx.accounts.distributor_vault.to_account_info(),
+                        to: ctx.accounts.claimant_token.to_account_info(),
+                        authority: distributor.to_account_info(),
+                    },
+                    signer,
+                ),
+                amount,
+            )?;
+
+            distributor.total_amount_claimed = total_amount_claimed;
+            distributor.num_nodes_claimed = num_nodes_claimed;
+
+            let claim_status = &mut ctx.accounts.claim_status;
+            claim_status.claimant = claimant;
+            claim_status.amount = amount;
+            claim_status.claimed_at = clock.unix_timestamp;
+            claim_status.bump = ctx.bumps.claim_status;
+
+            emit!(AirdropClaimed {
+                distributor: distributor.key(),
+                index,
+                claimant,
+                amount,
+            });
+
+            Ok(())
+        }
+
+        #[derive(Accounts)]
+        #[instruction(index: u64)]
+        pub struct ClaimAirdrop<'info> {
+            #[account(mut)]
+            pub distributor: Account<'info, MerkleDistributor>,
+
+            #[account(
+                mut,
+                seeds = [b"distributor_vault", distributor.key().as_ref()],
+                bump
+            )]
+            pub distributor_vault: Account<'info, TokenAccount>,
+
+            // Initialising this PDA is what prevents a leaf being claimed twice
+            #[account(
+                init,
+                payer = claimant,
+                space = 8 + ClaimStatus::INIT_SPACE,
+                seeds = [
+                    b"claim_status".as_ref(),
+                    distributor.key().as_ref(),
+                    &index.to_le_bytes()
+                ],
+                bump
+            )]
+            pub claim_status: Account<'info, ClaimStatus>,
+
+            #[account(mut)]
+            pub claimant: Signer<'info>,
+
+            #[account(
+                mut,
+                constraint = claimant_token.owner == claimant.key(),
+                constraint = claimant_token.mint == distributor.mint
+            )]
+            pub claimant_token: Account<'info, TokenAccount>,
+
+            pub token_program: Program<'info, Token>,
+            pub system_program: Program<'info, System>,
+        }
+
+        #[event]
+        pub struct AirdropClaimed {
+            pub distributor: Pubkey,
+            pub index: u64,
+            pub claimant: Pubkey,
+            pub amount: u64,
+        }
+    }
+
+    pub mod clawback_airdrop {
+        use super::*;
+        use anchor_spl::token::Transfer;
+        use crate::errors::ErrorCode;
+
+        pub fn handler(ctx: Context<ClawbackAirdrop>) -> Result<()> {
+            let distributor = &mut ctx.accounts.distributor;
+
+            let clock = Clock::get()?;
+            require!(
+                clock.unix_timestamp >= distributor.clawback_start_ts,
+                ErrorCode::ClawbackNotStarted
+            );
+            require!(!distributor.clawed_back, ErrorCode::AlreadyClawedBack);
+
+            let amount = ctx.accounts.distributor_vault.amount;
+            let id_bytes = distributor.id.to_le_bytes();
+            let seeds = &[b"distributor".as_ref(), &id_bytes, &[distributor.bump]];
+            let signer = &[&seeds[..]];
+
+            if amount > 0 {
+                token::transfer(
+                    CpiContext::new_with_signer(
+                        ctx.accounts.token_program.to_account_info(),
+                        Transfer {
+                            from: ctx.accounts.distributor_vault.to_account_info(),
+                            to: ctx.accounts.clawback_receiver.to_account_info(),
+                            authority: distributor.to_account_info(),
+                        },
+                        signer,
+                    ),
+                    amount,
+                )?;
+            }
+
+            distributor.clawed_back = true;
+
+            emit!(AirdropClawedBack {
+                distributor: distributor.key(),
+                receiver: distributor.clawback_receiver,
+                amount,
+            });
+
+            Ok(())
+        }
+
+        #[derive(Accounts)]
+        pub struct ClawbackAirdrop<'info> {
+            #[account(
+                mut,
+                has_one = admin,
+                has_one = clawback_receiver
+            )]
+            pub distributor: Account<'info, MerkleDistributor>,
+
+            #[account(
+                mut,
+                seeds = [b"distributor_vault", distributor.key().as_ref()],
+                bump
+            )]
+            pub distributor_vault: Account<'info, TokenAccount>,
+
+            #[account(mut)]
+            pub clawback_receiver: Account<'info, TokenAccount>,
+
+            pub admin: Signer<'info>,
+
+            pub token_program: Program<'info, Token>,
+        }
+
+        #[event]
+        pub struct AirdropClawedBack {
+            pub distributor: Pubkey,
+            pub receiver: Pubkey,
+            pub amount: u64,
+        }
+    }
+    pub mod initialize_stake_pool {
+        use super::*;
+        use crate::errors::ErrorCode;
+
+        pub fn handler(
+            ctx: Context<InitializeStakePool>,
+            unbonding_period: i64,
+        ) -> Result<()> {
+            require!(unbonding_period >= 0, ErrorCode::InvalidUnbondingPeriod);
+
+            let pool = &mut ctx.accounts.stake_pool;
+            pool.config = ctx.accounts.config.key();
+            pool.reward_mint = ctx.accounts.reward_mint.key();
+            pool.total_staked = 0;
+            pool.reward_index = 0;
+            pool.reward_reserve = 0;
+            pool.unbonding_period = unbonding_period;
+            pool.bump = ctx.bumps.stake_pool;
+
+            emit!(StakePoolInitialized {
+                stake_pool: pool.key(),
+                reward_mint: pool.reward_mint,
+                unbonding_period,
+            });
+
+            Ok(())
+        }
+
+        #[derive(Accounts)]
+        pub struct InitializeStakePool<'info> {
+            #[account(
+                seeds = [b"config"],
+                bump = config.bump,
+                has_one = authority
+            )]
+            pub config: Account<'info, TokenConfig>,
+
+            #[account(
+                init,
+                payer = authority,
+                space = 8 + StakePool::INIT_SPACE,
+                seeds = [b"stake_pool"],
+                bump
+            )]
+            pub stake_pool: Account<'info, StakePool>,
+
+            #[account(
+                init,
+                payer = authority,
+                token::mint = mint,
+                token::authority = stake_pool,
+                seeds = [b"stake_vault"],
+                bump
+            )]
+            pub stake_vault: Account<'info, TokenAccount>,
+
+            /// Receives protocol fees, e.g. via an `oraculo` treasury spend
+            #[account(
+                init,
+                payer = authority,
+                token::mint = reward_mint,
+                token::authority = stake_pool,
+                seeds = [b"reward_vault"],
+                bump
+            )]
+            pub reward_vault: Account<'info, TokenAccount>,
+
+            #[account(seeds = [b"mint"], bump)]
+            pub mint: Account<'info, Mint>,
+
+            pub reward_mint: Account<'info, Mint>,
+
+            #[account(mut)]
+            pub authority: Signer<'info>,
+
+            pub token_program: Program<'info, Token>,
+            pub system_program: Program<'info, System>,
+            pub rent: Sysvar<'info, Rent>,
+        }
+
+        #[event]
+        pub struct StakePoolInitialized {
+            pub stake_pool: Pubkey,
+            pub reward_mint: Pubkey,
+            pub unbonding_period: i64,
+        }
+    }
+
+    pub mod stake {
+        use super::*;
+        use anchor_spl::token::Transfer;
+        use crate::errors::ErrorCode;
+
+        pub fn handler(ctx: Context<Stake>, amount: u64) -> Result<()> {
+            require!(amount > 0, ErrorCode::ZeroAmount);
+
+            let pool = &mut ctx.accounts.stake_pool;
+            let stake_account = &mut ctx.accounts.stake_account;
+
+            pool.accrue(ctx.accounts.reward_vault.amount)?;
+            if stake_account.owner == Pubkey::default() {
+                stake_account.owner = ctx.accounts.owner.key();
+                stake_account.reward_index = pool.reward_index;
+                stake_account.bump = ctx.bumps.stake_account;
+            }
+            stake_account.settle(pool.reward_index)?;
+
+            token::transfer(
+                CpiContext::new(
+                    ctx.accounts.token_program.to_account_info(),
+                    Transfer {
+                        from: ctx.accounts.owner_token.to_account_info(),
+                        to: ctx.accounts.stake_vault.to_account_info(),
+                        authority: ctx.accounts.owner.to_account_info(),
+                    },
+                ),
+                amount,
+            )?;
+
+            stake_account.amount = stake_account.amount
+                .checked_add(amount)
+                .ok_or(ErrorCode::MathOverflow)?;
+            pool.total_staked = pool.total_staked
+                .checked_add(amount)
+                .ok_or(ErrorCode::MathOverflow)?;
+
+            emit!(Staked {
+                owner: stake_account.owner,
+                amount,
+                staked: stake_account.amount,
+                total_staked: pool.total_staked,
+            });
+
+            Ok(())
+        }
+
+        #[derive(Accounts)]
+        pub struct Stake<'info> {
+            #[account(
+                mut,
+                seeds = [b"stake_pool"],
+                bump = stake_pool.bump
+            )]
+            pub stake_pool: Account<'info, StakePool>,
+
+            #[account(
+                init_if_needed,
+                payer = owner,
+                space = 8 + StakeAccount::INIT_SPACE,
+                seeds = [b"stake", owner.key().as_ref()],
+                bump
+            )]
+            pub stake_account: Account<'info, StakeAccount>,
+
+            #[account(mut, seeds = [b"stake_vault"], bump)]
+            pub stake_vault: Account<'info, TokenAccount>,
+
+            #[account(seeds = [b"reward_vault"], bump)]
+            pub reward_vault: Account<'info, TokenAccount>,
+
+            #[account(mut)]
+            pub owner: Signer<'info>,
+
+            #[account(
+                mut,
+                constraint = owner_token.owner == owner.key(),
+                constraint = owner_token.mint == stake_vault.mint
+            )]
+            pub owner_token: Account<'info, TokenAccount>,
+
+            pub token_program: Program<'info, Token>,
+            pub system_program: Program<'info, System>,
+        }
+
+        #[event]
+        pub struct Staked {
+            pub owner: Pubkey,
+            pub amount: u64,
+            pub staked: u64,
+            pub total_staked: u64,
+        }
+    }
+
+    pub mod unstake {
+        use super::*;
+        use crate::errors::ErrorCode;
+
+        pub fn handler(ctx: Context<Unstake>, amount: u64) -> Result<()> {
+            require!(amount > 0, ErrorCode::ZeroAmount);
+
+            let pool = &mut ctx.accounts.stake_pool;
+            let stake_account = &mut ctx.accounts.stake_account;
+
+            require!(amount <= stake_account.amount, ErrorCode::InsufficientStake);
+
+            pool.accrue(ctx.accounts.reward_vault.amount)?;
+            stake_account.settle(pool.reward_index)?;
+
+            // Unbonding tokens stop earning immediately; a new unstake
+            // restarts the unbonding clock for the whole unbonding balance
+            let clock = Clock::get()?;
+            stake_account.amount -= amount;
+            stake_account.unbonding_amount = stake_account.unbonding_amount
+                .checked_add(amount)
+                .ok_or(ErrorCode::MathOverflow)?;
+            stake_account.unbonding_ends_at = clock.unix_timestamp
+                .checked_add(pool.unbonding_period)
+                .ok_or(ErrorCode::MathOverflow)?;
+            pool.total_staked -= amount;
+
+            emit!(UnstakeRequested {
+                owner: stake_account.owner,
+                amount,
+                unbonding_amount: stake_account.unbonding_amount,
+                unbonding_ends_at: stake_account.unbonding_ends_at,
+            });
+
+            Ok(())
+        }
+
+        #[derive(Accounts)]
+        pub struct Unstake<'info> {
+            #[account(
+                mut,
+                seeds = [b"stake_pool"],
+                bump = stake_pool.bump
+            )]
+            pub stake_pool: Account<'info, StakePool>,
+
+            #[account(
+                mut,
+                seeds = [b"stake", owner.key().as_ref()],
+                bump = stake_account.bump,
+                has_one = owner
+            )]
+            pub stake_account: Account<'info, StakeAccount>,
+
+            #[account(seeds = [b"reward_vault"], bump)]
+            pub reward_vault: Account<'info, TokenAccount>,
+
+            pub owner: Signer<'info>,
+        }
+
+        #[event]
+        pub struct UnstakeRequested {
+            pub owner: Pubkey,
+            pub amount: u64,
+            pub unbonding_amount: u64,
+            pub unbonding_ends_at: i64,
+        }
+    }
+
+    pub mod withdraw_unbonded {
+        use super::*;
+        use anchor_spl::token::Transfer;
+        use crate::errors::ErrorCode;
+
+        pub fn handler(ctx: Context<WithdrawUnbonded>) -> Result<()> {
+            let pool = &ctx.accounts.stake_pool;
+            let stake_account = &mut ctx.accounts.stake_account;
+
+            let amount = stake_account.unbonding_amount;
+            require!(amount > 0, ErrorCode::NothingToClaim);
+            require!(
+                Clock::get()?.unix_timestamp >= stake_account.unbonding_ends_at,
+                ErrorCode::StillUnbonding
+            );
+
+            let seeds = &[b"stake_pool".as_ref(), &[pool.bump]];
+            let signer = &[&seeds[..]];
+
+            token::transfer(
+                CpiContext::new_with_signer(
+                    ctx.accounts.token_program.to_account_info(),
+                    Transfer {
+                        from: ctx.accounts.stake_vault.to_account_info(),
+                        to: ctx.accounts.owner_token.to_account_info(),
+                        authority: pool.to_account_info(),
+                    },
+                    signer,
+                ),
+                amount,
+            )?;
+
+            stake_account.unbonding_amount = 0;
+
+            emit!(UnbondedWithdrawn {
+                owner: stake_account.owner,
+                amount,
+            });
+
+            Ok(())
+        }
+
+        #[derive(Accounts)]
+        pub struct WithdrawUnbonded<'info> {
+            #[account(seeds = [b"stake_pool"], bump = stake_pool.bump)]
+            pub stake_pool: Account<'info, StakePool>,
+
+            #[account(
+                mut,
+                seeds = [b"stake", owner.key().as_ref()],
+                bump = stake_account.bump,
+                has_one = owner
+            )]
+            pub stake_account: Account<'info, StakeAccount>,
+
+            #[account(mut, seeds = [b"stake_vault"], bump)]
+            pub stake_vault: Account<'info, TokenAccount>,
+
+            pub owner: Signer<'info>,
+
+            #[account(
+                mut,
+                constraint = owner_token.owner == owner.key(),
+                constraint = owner_token.mint == stake_vault.mint
+            )]
+            pub owner_token: Account<'info, TokenAccount>,
+
+            pub token_program: Program<'info, Token>,
+        }
+
+        #[event]
+        pub struct UnbondedWithdrawn {
+            pub owner: Pubkey,
+            pub amount: u64,
+        }
+    }
+
+    pub mod claim_staking_rewards {
+        use super::*;
+        use anchor_spl::token::Transfer;
+        use crate::errors::ErrorCode;
+
+        pub fn handler(ctx: Context<ClaimStakingRewards>) -> Result<()> {
+            let pool = &mut ctx.accounts.stake_pool;
+            let stake_account = &mut ctx.accounts.stake_account;
+
+            pool.accrue(ctx.accounts.reward_vault.amount)?;
+            stake_account.settle(pool.reward_index)?;
+
+            let amount = stake_account.pending_rewards;
+            require!(amount > 0, ErrorCode::NothingToClaim);
+
+            let seeds = &[b"stake_pool".as_ref(), &[pool.bump]];
+            let signer = &[&seeds[..]];
+
+            token::transfer(
+                CpiContext::new_with_signer(
+                    ctx.accounts.token_program.to_account_info(),
+                    Transfer {
+                        from: ctx.accounts.reward_vault.to_account_info(),
+                        to: ctx.accounts.owner_reward_token.to_account_info(),
+                        authority: pool.to_account_info(),
+                    },
+                    signer,
+                ),
+                amount,
+            )?;
+
+            stake_account.pending_rewards = 0;
+            pool.reward_reserve = pool.reward_reserve
+                .checked_sub(amount)
+                .ok_or(ErrorCode::MathOverflow)?;
+
+            emit!(StakingRewardsClaimed {
+                owner: stake_account.owner,
+                amount,
+                reward_index: pool.reward_index,
+            });
+
+            Ok(())
+        }
+
+        #[derive(Accounts)]
+        pub struct ClaimStakingRewards<'info> {
+            #[account(
+                mut,
+                seeds = [b"stake_pool"],
+                bump = stake_pool.bump
+            )]
+            pub stake_pool: Account<'info, StakePool>,
+
+            #[account(
+                mut,
+                seeds = [b"stake", owner.key().as_ref()],
+                bump = stake_account.bump,
+                has_one = owner
+            )]
+            pub stake_account: Account<'info, StakeAccount>,
+
+            #[account(mut, seeds = [b"reward_vault"], bump)]
+            pub reward_vault: Account<'info, TokenAccount>,
+
+            pub owner: Signer<'info>,
+
+            #[account(
+                mut,
+                constraint = owner_reward_token.owner == owner.key(),
+                constraint = owner_reward_token.mint == stake_pool.reward_mint
+            )]
+            pub owner_reward_token: Account<'info, TokenAccount>,
+
+            pub token_program: Program<'info, Token>,
+        }
+
+        #[event]
+        pub struct StakingRewardsClaimed {
+            pub owner: Pubkey,
+            pub amount: u64,
+            pub reward_index: u128,
+        }
+    }
+}
+
+pub mod state {
+    use super::*;
+    use crate::errors::ErrorCode;
+
+    #[account]
+    #[derive(InitSpace)]
+    pub struct TokenConfig {
+        pub authority: Pubkey,
+        pub mint: Pubkey,
+        pub decimals: u8,
+        pub max_supply: u64,
+        pub total_supply: u64,
+        pub bump: u8,
+    }
+
+    impl TokenConfig {
+        /// Account for newly minted tokens against the supply cap
+        pub fn record_mint(&mut self, amount: u64) -> Result<()> {
+            let total_supply = self.total_supply
+                .checked_add(amount)
+                .ok_or(ErrorCode::MathOverflow)?;
+            require!(total_supply <= self.max_supply, ErrorCode::MaxSupplyExceeded);
+            self.total_supply = total_supply;
+            Ok(())
+        }
+    }
+
+    /// Fixed emission plan enforced by `mint_tokens`. Each bucket unlocks
+    /// its per-epoch allowance at the start of that epoch; unminted
+    /// allowance carries over, nothing unlocks past the last epoch.
+    #[account]
+    #[derive(InitSpace)]
+    pub struct EmissionSchedule {
+        pub config: Pubkey,
+        pub start_time: i64,
+        pub epoch_duration: i64,
+        #[max_len(8)]
+        pub buckets: Vec<EmissionBucket>,
+        pub bump: u8,
+    }
+
+    impl EmissionSchedule {
+        pub const MAX_BUCKETS: usize = 8;
+
+        /// Zero-based epoch at `now`, or `None` before `start_time`
+        pub fn current_epoch(&self, now: i64) -> Option<u64> {
+            if now < self.start_time || self.epoch_duration <= 0 {
+                return None;
+            }
+            Some(((now - self.start_time) / self.epoch_duration) as u64)
+        }
+
+        /// Charge `amount` against a bucket's unlocked allowance on behalf
+        /// of `minter`, returning the current epoch
+        pub fn reserve(
+            &mut self,
+            bucket: u8,
+            minter: &Pubkey,
+            amount: u64,
+            now: i64,
+        ) -> Result<u64> {
+            let epoch = self.current_epoch(now).ok_or(ErrorCode::EmissionNotStarted)?;
+
+            let emission_bucket = self
+                .buckets
+                .get_mut(bucket as usize)
+                .ok_or(ErrorCode::InvalidBucket)?;
+            require!(
+                emission_bucket.minter == *minter,
+                ErrorCode::UnauthorizedMinter
+            );
+
+            let unlocked = emission_bucket.unlocked(epoch).ok_or(ErrorCode::MathOverflow)?;
+            let minted = emission_bucket.minted
+                .checked_add(amount)
+                .ok_or(ErrorCode::MathOverflow)?;
+            require!(minted <= unlocked, ErrorCode::EmissionAllowanceExceeded);
+            emission_bucket.minted = minted;
+
+            Ok(epoch)
+        }
+    }
+
+    #[derive(AnchorSerialize, AnchorDeserialize, Clone, InitSpace)]
+    pub struct EmissionBucket {
+        #[max_len(32)]
+        pub name: String,
+        pub minter: Pubkey,
+        #[max_len(16)]
+        pub epoch_allowances: Vec<u64>,
+        pub minted: u64,
+    }
+
+    impl EmissionBucket {
+        pub const MAX_NAME_LEN: usize = 32;
+        pub const MAX_EPOCHS: usize = 16;
+
+        pub fn total_allowance(&self) -> Option<u64> {
+            self.epoch_allowances
+                .iter()
+                .try_fold(0u64, |acc, a| acc.checked_add(*a))
+        }
+
+        /// Cumulative allowance unlocked up to and including `epoch`
+        pub fn unlocked(&self, epoch: u64) -> Option<u64> {
+            let epochs = (epoch as usize).saturating_add(1).min(self.epoch_allowances.len());
+            self.epoch_allowances[..epochs]
+                .iter()
+                .try_fold(0u64, |acc, a| acc.checked_add(*a))
+        }
+    }
+
+    /// Linear vesting with a cliff. Nothing is claimable before
+    /// `cliff_time`; after it the vested amount grows linearly from
+    /// `start_time` to `end_time`.
+    #[account]
+    #[derive(InitSpace)]
+    pub struct VestingAccount {
+        pub id: u64,
+        pub beneficiary: Pubkey,
+        pub funder: Pubkey,
+        pub start_time: i64,
+        pub cliff_time: i64,
+        pub end_time: i64,
+        pub total_amount: u64,
+        pub released: u64,
+        pub revocable: bool,
+        pub revoked: bool,
+        pub bump: u8,
+    }
+
+    impl VestingAccount {
+        pub fn vested_amount(&self, now: i64) -> Result<u64> {
+            if now < self.cliff_time {
+                return Ok(0);
+            }
+            if now >= self.end_time {
+                return Ok(self.total_amount);
+            }
+
+            let elapsed = (now - self.start_time) as u128;
+            let duration = (self.end_time - self.start_time) as u128;
+            let vested = (self.total_amount as u128)
+                .checked_mul(elapsed)
+                .ok_or(ErrorCode::MathOverflow)?
+                .checked_div(duration)
+                .ok_or(ErrorCode::MathOverflow)?;
+
+            Ok(vested as u64)
+        }
+    }
+
+    /// Merkle airdrop. Leaves are built by `crate::merkle::leaf` and the
+    /// off-chain `airdrop-tree` tool.
+    #[account]
+    #[derive(InitSpace)]
+    pub struct MerkleDistributor {
+        pub id: u64,
+        pub admin: Pubkey,
+        pub mint: Pubkey,
+        pub root: [u8; 32],
+        pub max_total_claim: u64,
+        pub max_num_nodes: u64,
+        pub total_amount_claimed: u64,
+        pub num_nodes_claimed: u64,
+        pub clawback_start_ts: i64,
+        pub clawback_receiver: Pubkey,
+        pub clawed_back: bool,
+        pub bump: u8,
+    }
+
+    #[account]
+    #[derive(InitSpace)]
+    pub struct ClaimStatus {
+        pub claimant: Pubkey,
+        pub amount: u64,
+        pub claimed_at: i64,
+        pub bump: u8,
+    }
+
+    /// Governance token staking. Fees pushed into the reward vault are
+    /// picked up lazily by `accrue` and spread over `total_staked` through
+    /// a cumulative per-token `reward_index`.
+    #[account]
+    #[derive(InitSpace)]
+    pub struct StakePool {
+        pub config: Pubkey,
+        pub reward_mint: Pubkey,
+        pub total_staked: u64,
+        pub reward_index: u128,
+        /// Reward vault balance already folded into `reward_index` and not yet paid out
+        pub reward_reserve: u64,
+        pub unbonding_period: i64,
+        pub bump: u8,
+    }
+
+    impl StakePool {
+        pub const INDEX_PRECISION: u128 = 1_000_000_000_000;
+
+        /// Fold any new reward vault balance into the index. With nothing
+        /// staked the balance waits for the first staker.
+        pub fn accrue(&mut self, reward_vault_balance: u64) -> Result<()> {
+            if self.total_staked == 0 {
+                return Ok(());
+            }
+
+            let new_rewards = reward_vault_balance.saturating_sub(self.reward_reserve);
+            if new_rewards == 0 {
+                return Ok(());
+            }
+
+            let delta = (new_rewards as u128)
+                .checked_mul(Self::INDEX_PRECISION)
+                .ok_or(ErrorCode::MathOverflow)?
+                / self.total_staked as u128;
+            self.reward_index = self.reward_index
+                .checked_add(delta)
+                .ok_or(ErrorCode::MathOverflow)?;
+            self.reward_reserve = reward_vault_balance;
+
+            Ok(())
+        }
+    }
+
+    #[account]
+    #[derive(InitSpace)]
+    pub struct StakeAccount {
+        pub owner: Pubkey,
+        pub amount: u64,
+        pub reward_index: u128,
+        pub pending_rewards: u64,
+        pub unbonding_amount: u64,
+        pub unbonding_ends_at: i64,
+        pub bump: u8,
+    }
+
+    impl StakeAccount {
+        /// Move rewards earned since the last snapshot into `pending_rewards`
+        pub fn settle(&mut self, pool_index: u128) -> Result<()> {
+            let earned = pool_index
+                .checked_sub(self.reward_index)
+                .ok_or(ErrorCode::MathOverflow)?
+                .checked_mul(self.amount as u128)
+                .ok_or(ErrorCode::MathOverflow)?
+                / StakePool::INDEX_PRECISION;
+            self.pending_rewards = self.pending_rewards
+                .checked_add(earned as u64)
+                .ok_or(ErrorCode::MathOverflow)?;
+            self.reward_index = pool_index;
+
+            Ok(())
+        }
+    }
+}
+
+/// Hashing shared by the on-chain claim check and the off-chain tree builder.
+/// Leaves and inner nodes are domain-separated and pairs are sorted, so a
+/// proof is just the list of sibling hashes.
+pub mod merkle {
+    use anchor_lang::prelude::Pubkey;
+    use anchor_lang::solana_program::hash::hashv;
+
+    const LEAF_PREFIX: &[u8] = &[0];
+    const NODE_PREFIX: &[u8] = &[1];
+
+    pub fn leaf(index: u64, claimant: &Pubkey, amount: u64) -> [u8; 32] {
+        hashv(&[
+            LEAF_PREFIX,
+            &index.to_le_bytes(),
+            claimant.as_ref(),
+            &amount.to_le_bytes(),
+        ])
+        .to_bytes()
+    }
+
+    pub fn hash_pair(a: &[u8; 32], b: &[u8; 32]) -> [u8; 32] {
+        let (lo, hi) = if a <= b { (a, b) } else { (b, a) };
+        hashv(&[NODE_PREFIX, lo, hi]).to_bytes()
+    }
+
+    pub fn verify(proof: &[[u8; 32]], root: [u8; 32], leaf: [u8; 32]) -> bool {
+        let computed = proof
+            .iter()
+            .fold(leaf, |node, sibling| hash_pair(&node, sibling));
+        computed == root
+    }
+}
+
+pub mod errors {
+    use super::*;
+
+    #[error_code]
+    pub enum ErrorCode {
+        #[msg("Max supply must be greater than zero")]
+        InvalidMaxSupply,
+
+        #[msg("Epoch duration must be positive")]
+        InvalidEpochDuration,
+
+        #[msg("Invalid number of emission buckets (1-8)")]
+        InvalidBucketCount,
+
+        #[msg("Bucket name too long (max 32 characters)")]
+        BucketNameTooLong,
+
+        #[msg("Invalid number of epochs (1-16)")]
+        InvalidEpochCount,
+
+        #[msg("Emission schedule exceeds max supply")]
+        ScheduleExceedsMaxSupply,
+
+        #[msg("Emission schedule has not started")]
+        EmissionNotStarted,
+
+        #[msg("Invalid emission bucket")]
+        InvalidBucket,
+
+        #[msg("Signer is not the bucket minter")]
+        UnauthorizedMinter,
+
+        #[msg("Emission allowance exceeded for this epoch")]
+        EmissionAllowanceExceeded,
+
+        #[msg("Max supply exceeded")]
+        MaxSupplyExceeded,
+
+        #[msg("Amount must be greater than zero")]
+        ZeroAmount,
+
+        #[msg("Invalid vesting schedule")]
+        InvalidVestingSchedule,
+
+        #[msg("Funding account missing for the chosen funding source")]
+        MissingFundingAccount,
+
+        #[msg("Nothing to claim")]
+        NothingToClaim,
+
+        #[msg("Vesting is not revocable")]
+        NotRevocable,
+
+        #[msg("Vesting already revoked")]
+        AlreadyRevoked,
+
+        #[msg("Clawback time must be in the future")]
+        InvalidClawbackTime,
+
+        #[msg("Airdrop claim window has closed")]
+        AirdropExpired,
+
+        #[msg("Invalid merkle proof")]
+        InvalidProof,
+
+        #[msg("Claim exceeds distributor total")]
+        ExceededMaxClaim,
+
+        #[msg("Claim exceeds distributor node count")]
+        ExceededMaxNumNodes,
+
+        #[msg("Clawback period not started")]
+        ClawbackNotStarted,
+
+        #[msg("Airdrop already clawed back")]
+        AlreadyClawedBack,
+
+        #[msg("Unbonding period cannot be negative")]
+        InvalidUnbondingPeriod,
+
+        #[msg("Insufficient staked balance")]
+        InsufficientStake,
+
+        #[msg("Tokens are still unbonding")]
+        StillUnbonding,
+
+        #[msg("Math overflow")]
+        MathOverflow,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::state::*;
+    use anchor_lang::prelude::Pubkey;
+
+    fn bucket(allowances: Vec<u64>) -> EmissionBucket {
+        EmissionBucket {
+            name: "liquidity_mining".to_string(),
+            minter: Pubkey::default(),
+            epoch_allowances: allowances,
+            minted: 0,
+        }
+    }
+
+    #[test]
+    fn test_unlocked_allowance_accumulates() {
+        let b = bucket(vec![100, 50, 25]);
+        assert_eq!(b.unlocked(0), Some(100));
+        assert_eq!(b.unlocked(1), Some(150));
+        assert_eq!(b.unlocked(2), Some(175));
+        assert_eq!(b.unlocked(99), Some(175)); // nothing past the last epoch
+        assert_eq!(b.total_allowance(), Some(175));
+    }
+
+    #[test]
+    fn test_allowance_overflow() {
+        let b = bucket(vec![u64::MAX, 1]);
+        assert_eq!(b.unlocked(0), Some(u64::MAX));
+        assert_eq!(b.unlocked(1), None);
+        assert_eq!(b.total_allowance(), None);
+    }
+
+    fn vesting(total_amount: u64) -> VestingAccount {
+        VestingAccount {
+            id: 0,
+            beneficiary: Pubkey::default(),
+            funder: Pubkey::default(),
+            start_time: 1_000,
+            cliff_time: 1_250,
+            end_time: 2_000,
+            total_amount,
+            released: 0,
+            revocable: true,
+            revoked: false,
+            bump: 0,
+        }
+    }
+
+    #[test]
+    fn test_vested_amount() {
+        let v = vesting(1_000_000);
+        assert_eq!(v.vested_amount(999).unwrap(), 0);
+        assert_eq!(v.vested_amount(1_249).unwrap(), 0); // before cliff
+        assert_eq!(v.vested_amount(1_250).unwrap(), 250_000); // cliff releases accrued
+        assert_eq!(v.vested_amount(1_500).unwrap(), 500_000);
+        assert_eq!(v.vested_amount(2_000).unwrap(), 1_000_000);
+        assert_eq!(v.vested_amount(5_000).unwrap(), 1_000_000);
+    }
+
+    #[test]
+    fn test_vested_amount_no_overflow() {
+        let v = vesting(u64::MAX);
+        assert_eq!(v.vested_amount(1_500).unwrap(), u64::MAX / 2);
+    }
+
+    #[test]
+    fn test_merkle_verify() {
+        use crate::merkle;
+
+        let alice = Pubkey::new_unique();
+        let bob = Pubkey::new_unique();
+        let carol = Pubkey::new_unique();
+        let leaves = [
+            merkle::leaf(0, &alice, 100),
+            merkle::leaf(1, &bob, 200),
+            merkle::leaf(2, &carol, 300),
+        ];
+        let ab = merkle::hash_pair(&leaves[0], &leaves[1]);
+        let root = merkle::hash_pair(&ab, &leaves[2]);
+
+        assert!(merkle::verify(&[leaves[1], leaves[2]], root, leaves[0]));
+        assert!(merkle::verify(&[leaves[0], leaves[2]], root, leaves[1]));
+        assert!(merkle::verify(&[ab], root, leaves[2]));
+
+        // Wrong amount or claimant must not verify
+        assert!(!merkle::verify(&[ab], root, merkle::leaf(2, &carol, 301)));
+        assert!(!merkle::verify(&[ab], root, merkle::leaf(2, &alice, 300)));
+    }
+
+    fn stake_pool() -> StakePool {
+        StakePool {
+            config: Pubkey::default(),
+            reward_mint: Pubkey::default(),
+            total_staked: 0,
+            reward_index: 0,
+            reward_reserve: 0,
+            unbonding_period: 0,
+            bump: 0,
+        }
+    }
+
+    fn staker(amount: u64, reward_index: u128) -> StakeAccount {
+        StakeAccount {
+            owner: Pubkey::default(),
+            amount,
+            reward_index,
+            pending_rewards: 0,
+            unbonding_amount: 0,
+            unbonding_ends_at: 0,
+            bump: 0,
+        }
+    }
+
+    #[test]
+    fn test_rewards_split_pro_rata() {
+        let mut pool = stake_pool();
+        let mut alice = staker(300, 0);
+        let mut bob = staker(100, 0);
+        pool.total_staked = 400;
+
+        // 1_000 USDC base units pushed into the reward vault
+        pool.accrue(1_000).unwrap();
+        alice.settle(pool.reward_index).unwrap();
+        bob.settle(pool.reward_index).unwrap();
+        assert_eq!(alice.pending_rewards, 750);
+        assert_eq!(bob.pending_rewards, 250);
+
+        // Re-accruing the same balance adds nothing
+        pool.accrue(1_000).unwrap();
+        alice.settle(pool.reward_index).unwrap();
+        assert_eq!(alice.pending_rewards, 750);
+    }
+
+    #[test]
+    fn test_late_staker_gets_no_past_rewards() {
+        let mut pool = stake_pool();
+        let mut alice = staker(100, 0);
+        pool.total_staked = 100;
+        pool.accrue(500).unwrap();
+
+        // Bob joins at the current index
+        let mut bob = staker(100, pool.reward_index);
+        pool.total_staked = 200;
+        pool.accrue(700).unwrap();
+
+        alice.settle(pool.reward_index).unwrap();
+        bob.settle(pool.reward_index).unwrap();
+        assert_eq!(alice.pending_rewards, 600);
+        assert_eq!(bob.pending_rewards, 100);
+    }
+
+    #[test]
+    fn test_rewards_wait_for_stakers() {
+        let mut pool = stake_pool();
+        pool.accrue(1_000).unwrap();
+        assert_eq!(pool.reward_index, 0);
+        assert_eq!(pool.reward_reserve, 0);
+
+        let mut alice = staker(10, pool.reward_index);
+        pool.total_staked = 10;
+        pool.accrue(1_000).unwrap();
+        alice.settle(pool.reward_index).unwrap();
+        assert_eq!(alice.pending_rewards, 1_000);
+    }
+
+    #[test]
+    fn test_current_epoch() {
+        let schedule = EmissionSchedule {
+            config: Pubkey::default(),
+            start_time: 1_000,
+            epoch_duration: 100,
+            buckets: vec![],
+            bump: 0,
+        };
+        assert_eq!(schedule.current_epoch(999), None);
+        assert_eq!(schedule.current_epoch(1_000), Some(0));
+        assert_eq!(schedule.current_epoch(1_099), Some(0));
+        assert_eq!(schedule.current_epoch(1_100), Some(1));
     }
 }
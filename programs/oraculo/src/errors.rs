@@ -70,4 +70,208 @@ pub enum ErrorCode {
     
     #[msg("Invalid supermajority percentage")]
     InvalidSupermajority,
+    
+    #[msg("Protocol paused")]
+    ProtocolPaused,
+    
+    #[msg("Market halted")]
+    MarketHalted,
+    
+    #[msg("Invalid pause flags")]
+    InvalidPauseFlags,
+    
+    #[msg("Unauthorized")]
+    Unauthorized,
+    
+    #[msg("Protocol fee too high (max 10%)")]
+    InvalidProtocolFee,
+    
+    #[msg("Memo too long (max 200 characters)")]
+    MemoTooLong,
+    
+    #[msg("Amount must be greater than zero")]
+    ZeroAmount,
+    
+    #[msg("Insufficient governance token balance")]
+    InsufficientStake,
+    
+    #[msg("Treasury timelock not expired")]
+    TimelockNotExpired,
+    
+    #[msg("Insufficient treasury balance")]
+    InsufficientTreasuryBalance,
+    
+    #[msg("Invalid gauge parameters")]
+    InvalidGaugeParams,
+    
+    #[msg("Reward position does not belong to this market's gauge")]
+    InvalidRewardPosition,
+    
+    #[msg("Nothing to claim")]
+    NothingToClaim,
+    
+    #[msg("Collateral mint is not enabled")]
+    CollateralNotEnabled,
+    
+    #[msg("Account does not match the market collateral")]
+    InvalidCollateral,
+    
+    #[msg("Invalid price condition")]
+    InvalidPriceCondition,
+    
+    #[msg("Market does not resolve from a price oracle")]
+    NotOracleMarket,
+    
+    #[msg("Market resolves from a price oracle")]
+    ResolvedByOracle,
+    
+    #[msg("Invalid oracle price account")]
+    InvalidOracleAccount,
+    
+    #[msg("Oracle price is not currently available")]
+    OraclePriceUnavailable,
+    
+    #[msg("Oracle price is stale")]
+    StaleOraclePrice,
+    
+    #[msg("Oracle confidence interval too wide")]
+    OracleConfidenceTooWide,
+    
+    #[msg("Invalid resolution mode")]
+    InvalidResolutionMode,
+    
+    #[msg("Voting is not allowed for this resolution mode")]
+    VotingNotAllowed,
+    
+    #[msg("Designated reporter window is still open")]
+    ReporterWindowOpen,
+    
+    #[msg("Designated reporter window has closed")]
+    ReporterWindowClosed,
+    
+    #[msg("Reporter bond already posted")]
+    ReporterBondPosted,
+    
+    #[msg("Reporter bond has not been posted")]
+    ReporterBondMissing,
+    
+    #[msg("Outcome already reported")]
+    AlreadyReported,
+    
+    #[msg("Report challenge window is still open")]
+    ChallengeWindowOpen,
+    
+    #[msg("Report challenge window has closed")]
+    ChallengeWindowClosed,
+    
+    #[msg("Report has been disputed")]
+    ReportDisputed,
+    
+    #[msg("Market cannot fall back to a DAO vote")]
+    FallbackNotAllowed,
+    
+    #[msg("Market must fall back to a DAO vote first")]
+    FallbackRequired,
+    
+    #[msg("Invalid event time")]
+    InvalidEventTime,
+    
+    #[msg("No late bets to roll back")]
+    NoLateBets,
+    
+    #[msg("Invalid outcome token account")]
+    InvalidOutcomeTokenAccount,
+    
+    #[msg("Invalid resolution period")]
+    InvalidResolutionPeriod,
+    
+    #[msg("Resolution deadline has passed")]
+    ResolutionDeadlinePassed,
+    
+    #[msg("Resolution deadline has not passed")]
+    ResolutionDeadlineNotPassed,
+    
+    #[msg("Invalid order")]
+    InvalidOrder,
+    
+    #[msg("Post-only order would match")]
+    OrderWouldCross,
+    
+    #[msg("Order book is full")]
+    OrderBookFull,
+    
+    #[msg("Fill queue is full, consume fills first")]
+    FillQueueFull,
+    
+    #[msg("Order not found")]
+    OrderNotFound,
+    
+    #[msg("Order book does not support transfer-fee collateral")]
+    TransferFeeNotSupported,
+    
+    #[msg("Cost exceeds the maximum allowed")]
+    SlippageExceeded,
+    
+    #[msg("Invalid TWAP window")]
+    InvalidTwapWindow,
+    
+    #[msg("Price history does not cover the TWAP window")]
+    InsufficientPriceHistory,
+    
+    #[msg("Invalid parent market")]
+    InvalidParentMarket,
+    
+    #[msg("Parent market has not resolved against this market's condition")]
+    ParentConditionNotFailed,
+    
+    #[msg("Market is not cancelled")]
+    MarketNotCancelled,
+    
+    #[msg("Invalid market template")]
+    InvalidTemplate,
+    
+    #[msg("Market template is inactive")]
+    TemplateInactive,
+    
+    #[msg("Next market in the series is not due yet")]
+    SpawnNotDue,
+    
+    #[msg("Metadata URI too long (max 200 chars)")]
+    MetadataUriTooLong,
+    
+    #[msg("Market can no longer be edited after the first bet")]
+    MarketHasBets,
+    
+    #[msg("Unknown category")]
+    UnknownCategory,
+    
+    #[msg("Category is disabled")]
+    CategoryDisabled,
+    
+    #[msg("Invalid category")]
+    InvalidCategory,
+    
+    #[msg("Invalid tags (max 4, 1-32 chars each)")]
+    InvalidTags,
+    
+    #[msg("Proposal has not been finalized")]
+    ProposalNotFinalized,
+    
+    #[msg("A resolution proposal can still be executed")]
+    ResolutionPending,
+    
+    #[msg("Every open resolution proposal must be passed")]
+    ProposalsMissing,
+    
+    #[msg("Order is below the minimum size to rest on the book")]
+    OrderTooSmall,
+    
+    #[msg("Too many resting orders for this owner")]
+    TooManyOrders,
+    
+    #[msg("Open orders account passed more than once")]
+    DuplicateOpenOrders,
+    
+    #[msg("Market template must be paused first")]
+    TemplateActive,
 }
@@ -0,0 +1,72 @@
+use anchor_lang::prelude::*;
+use crate::state::{Config, Market, MarketStatus, PriceHistory};
+use crate::errors::ErrorCode;
+
+pub fn handler(ctx: Context<CancelConditionalMarket>) -> Result<()> {
+    let parent_market = &ctx.accounts.parent_market;
+    let market = &mut ctx.accounts.market;
+
+    // Validations
+    require!(
+        !ctx.accounts.config.is_paused(Config::PAUSE_PROPOSALS),
+        ErrorCode::ProtocolPaused
+    );
+    require!(!market.halted, ErrorCode::MarketHalted);
+    let condition = market.parent.ok_or(ErrorCode::InvalidParentMarket)?;
+    require!(
+        market.status != MarketStatus::Resolved && market.status != MarketStatus::Cancelled,
+        ErrorCode::MarketNotActive
+    );
+
+    // The condition fails when the parent resolves the other way or is cancelled
+    let failed = match parent_market.status {
+        MarketStatus::Resolved => parent_market.outcome != Some(condition.outcome),
+        MarketStatus::Cancelled => true,
+        _ => false,
+    };
+    require!(failed, ErrorCode::ParentConditionNotFailed);
+
+    // Trading stops by end time, so the price is settled up to then
+    let now = Clock::get()?.unix_timestamp;
+    market.refund_price = Some(ctx.accounts.price_history.settlement_price(now.min(market.end_time))?);
+    market.status = MarketStatus::Cancelled;
+    market.resolved_at = Some(now);
+
+    emit!(ConditionalMarketCancelled {
+        market: market.key(),
+        parent_market: parent_market.key(),
+        parent_outcome: parent_market.outcome,
+        refund_price: market.refund_price.unwrap_or_default(),
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct CancelConditionalMarket<'info> {
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, Config>,
+
+    #[account(mut)]
+    pub market: Account<'info, Market>,
+
+    #[account(
+        constraint = market.parent.map(|parent| parent.market) == Some(parent_market.key())
+            @ ErrorCode::InvalidParentMarket
+    )]
+    pub parent_market: Account<'info, Market>,
+
+    #[account(
+        seeds = [b"price_history", market.key().as_ref()],
+        bump = price_history.bump
+    )]
+    pub price_history: Account<'info, PriceHistory>,
+}
+
+#[event]
+pub struct ConditionalMarketCancelled {
+    pub market: Pubkey,
+    pub parent_market: Pubkey,
+    pub parent_outcome: Option<bool>,
+    pub refund_price: u16,
+}
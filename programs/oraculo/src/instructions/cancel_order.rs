@@ -0,0 +1,64 @@
+use anchor_lang::prelude::*;
+use crate::state::{Config, Market, OpenOrders, OrderBook};
+use crate::errors::ErrorCode;
+
+pub fn handler(ctx: Context<CancelOrder>, order_id: u64) -> Result<()> {
+    // Validations
+    require!(
+        !ctx.accounts.config.is_paused(Config::PAUSE_TRADING),
+        ErrorCode::ProtocolPaused
+    );
+    require!(!ctx.accounts.market.halted, ErrorCode::MarketHalted);
+
+    let order = ctx
+        .accounts
+        .order_book
+        .remove(&ctx.accounts.owner.key(), order_id)?;
+
+    // Escrow is released to the owner's free balances for `settle_funds`
+    ctx.accounts
+        .open_orders
+        .credit(&order.kind.escrow(order.price, order.lots)?)?;
+
+    emit!(OrderCancelled {
+        market: ctx.accounts.order_book.market,
+        owner: order.owner,
+        order_id,
+        lots: order.lots,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct CancelOrder<'info> {
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, Config>,
+
+    #[account(address = order_book.market)]
+    pub market: Account<'info, Market>,
+
+    #[account(
+        mut,
+        seeds = [b"order_book", order_book.market.as_ref()],
+        bump = order_book.bump
+    )]
+    pub order_book: Account<'info, OrderBook>,
+
+    #[account(
+        mut,
+        seeds = [b"open_orders", order_book.market.as_ref(), owner.key().as_ref()],
+        bump = open_orders.bump
+    )]
+    pub open_orders: Account<'info, OpenOrders>,
+
+    pub owner: Signer<'info>,
+}
+
+#[event]
+pub struct OrderCancelled {
+    pub market: Pubkey,
+    pub owner: Pubkey,
+    pub order_id: u64,
+    pub lots: u64,
+}
@@ -0,0 +1,145 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{self, Burn, Mint, TokenAccount, TokenInterface, TransferChecked};
+use crate::state::{Config, Market, MarketStatus};
+use crate::errors::ErrorCode;
+use crate::utils::{refund_value, transfer_fee};
+
+pub fn handler(
+    ctx: Context<ClaimRefund>,
+    amount: u64,
+    outcome: bool,
+) -> Result<()> {
+    let config = &ctx.accounts.config;
+    let market = &ctx.accounts.market;
+
+    // Validations
+    require!(
+        !config.is_paused(Config::PAUSE_CLAIMS),
+        ErrorCode::ProtocolPaused
+    );
+    require!(!market.halted, ErrorCode::MarketHalted);
+
+    let outcome_mint = if outcome {
+        &ctx.accounts.yes_token_mint
+    } else {
+        &ctx.accounts.no_token_mint
+    };
+    require!(
+        ctx.accounts.user_outcome_token.mint == outcome_mint.key(),
+        ErrorCode::InvalidOutcomeTokenAccount
+    );
+
+    // A TWAP frozen at cancellation, so a last-minute trade cannot set it
+    let yes_price = market.refund_price.ok_or(ErrorCode::MarketNotCancelled)?;
+    let refund = refund_value(amount, ctx.accounts.collateral_mint.decimals, yes_price, outcome)?;
+    require!(refund > 0, ErrorCode::ZeroAmount);
+
+    token_interface::burn(
+        CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            Burn {
+                mint: outcome_mint.to_account_info(),
+                from: ctx.accounts.user_outcome_token.to_account_info(),
+                authority: ctx.accounts.user.to_account_info(),
+            },
+        ),
+        amount,
+    )?;
+
+    let market_id = market.market_id.to_le_bytes();
+    let bump = [market.bump];
+    let seeds = &[b"market".as_ref(), &market_id, &bump];
+    let signer = &[&seeds[..]];
+
+    token_interface::transfer_checked(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            TransferChecked {
+                from: ctx.accounts.liquidity_vault.to_account_info(),
+                mint: ctx.accounts.collateral_mint.to_account_info(),
+                to: ctx.accounts.user_collateral.to_account_info(),
+                authority: market.to_account_info(),
+            },
+            signer,
+        ),
+        refund,
+        ctx.accounts.collateral_mint.decimals,
+    )?;
+
+    let received = refund
+        .checked_sub(transfer_fee(&ctx.accounts.collateral_mint.to_account_info(), refund)?)
+        .ok_or(ErrorCode::MathUnderflow)?;
+
+    emit!(RefundClaimed {
+        market: market.key(),
+        user: ctx.accounts.user.key(),
+        outcome,
+        amount,
+        yes_price,
+        refund,
+        received,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct ClaimRefund<'info> {
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        constraint = market.status == MarketStatus::Cancelled @ ErrorCode::MarketNotCancelled
+    )]
+    pub market: Account<'info, Market>,
+
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    #[account(
+        mut,
+        constraint = user_collateral.owner == user.key(),
+        constraint = user_collateral.mint == market.collateral_mint @ ErrorCode::InvalidCollateral
+    )]
+    pub user_collateral: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        seeds = [b"vault", market.key().as_ref()],
+        bump
+    )]
+    pub liquidity_vault: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(address = market.collateral_mint @ ErrorCode::InvalidCollateral)]
+    pub collateral_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        seeds = [b"yes_mint", market.key().as_ref()],
+        bump
+    )]
+    pub yes_token_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        seeds = [b"no_mint", market.key().as_ref()],
+        bump
+    )]
+    pub no_token_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(mut)]
+    pub user_outcome_token: InterfaceAccount<'info, TokenAccount>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+#[event]
+pub struct RefundClaimed {
+    pub market: Pubkey,
+    pub user: Pubkey,
+    pub outcome: bool,
+    pub amount: u64,
+    pub yes_price: u16,
+    pub refund: u64,
+    pub received: u64,
+}
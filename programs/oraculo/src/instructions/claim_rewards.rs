@@ -0,0 +1,118 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{Token, TokenAccount};
+use governance_token::program::GovernanceToken;
+use crate::state::{Config, Gauge, Market, RewardPosition};
+use crate::errors::ErrorCode;
+
+pub fn handler(ctx: Context<ClaimRewards>) -> Result<()> {
+    let config = &ctx.accounts.config;
+    let gauge = &mut ctx.accounts.gauge;
+    let position = &mut ctx.accounts.reward_position;
+
+    require!(
+        !config.is_paused(Config::PAUSE_CLAIMS),
+        ErrorCode::ProtocolPaused
+    );
+    require!(!ctx.accounts.market.halted, ErrorCode::MarketHalted);
+
+    let clock = Clock::get()?;
+    gauge.update(&ctx.accounts.market, clock.unix_timestamp)?;
+    position.settle(gauge)?;
+    position.sync_lp_shares(gauge, &ctx.accounts.market)?;
+
+    let amount = position.pending_rewards;
+    require!(amount > 0, ErrorCode::NothingToClaim);
+
+    // The rewards minter PDA is the minter of the gauge's emission bucket
+    let seeds = &[b"rewards_minter".as_ref(), &[ctx.bumps.rewards_minter]];
+    let signer = &[&seeds[..]];
+
+    governance_token::cpi::mint_tokens(
+        CpiContext::new_with_signer(
+            ctx.accounts.governance_program.to_account_info(),
+            governance_token::cpi::accounts::MintTokens {
+                config: ctx.accounts.governance_config.to_account_info(),
+                emission_schedule: ctx.accounts.emission_schedule.to_account_info(),
+                minter: ctx.accounts.rewards_minter.to_account_info(),
+                mint: ctx.accounts.governance_token_mint.to_account_info(),
+                destination: ctx.accounts.user_gov_token.to_account_info(),
+                token_program: ctx.accounts.token_program.to_account_info(),
+            },
+            signer,
+        ),
+        gauge.emission_bucket,
+        amount,
+    )?;
+
+    position.pending_rewards = 0;
+    position.total_claimed = position.total_claimed.checked_add(amount).unwrap();
+
+    emit!(RewardsClaimed {
+        gauge: gauge.key(),
+        owner: position.owner,
+        amount,
+        total_claimed: position.total_claimed,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct ClaimRewards<'info> {
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, Config>,
+
+    #[account(address = gauge.market)]
+    pub market: Account<'info, Market>,
+
+    #[account(mut)]
+    pub gauge: Account<'info, Gauge>,
+
+    #[account(
+        mut,
+        seeds = [
+            b"reward_position",
+            gauge.key().as_ref(),
+            owner.key().as_ref()
+        ],
+        bump = reward_position.bump,
+        has_one = owner
+    )]
+    pub reward_position: Account<'info, RewardPosition>,
+
+    pub owner: Signer<'info>,
+
+    #[account(
+        mut,
+        constraint = user_gov_token.owner == owner.key(),
+        constraint = user_gov_token.mint == config.governance_token_mint
+    )]
+    pub user_gov_token: Account<'info, TokenAccount>,
+
+    /// CHECK: PDA signer only; authorised as a bucket minter in the governance program
+    #[account(seeds = [b"rewards_minter"], bump)]
+    pub rewards_minter: UncheckedAccount<'info>,
+
+    /// CHECK: Validated by the governance token program
+    #[account(mut)]
+    pub governance_config: UncheckedAccount<'info>,
+
+    /// CHECK: Validated by the governance token program
+    #[account(mut)]
+    pub emission_schedule: UncheckedAccount<'info>,
+
+    /// CHECK: Validated by the governance token program
+    #[account(mut, address = config.governance_token_mint)]
+    pub governance_token_mint: UncheckedAccount<'info>,
+
+    pub governance_program: Program<'info, GovernanceToken>,
+    pub token_program: Program<'info, Token>,
+}
+
+#[event]
+pub struct RewardsClaimed {
+    pub gauge: Pubkey,
+    pub owner: Pubkey,
+    pub amount: u64,
+    pub total_claimed: u64,
+}
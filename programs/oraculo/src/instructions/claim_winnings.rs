@@ -1,15 +1,22 @@
 use anchor_lang::prelude::*;
-use anchor_spl::token::{self, Token, TokenAccount, Mint, Burn, Transfer};
-use crate::state::{Market, MarketStatus};
+use anchor_spl::token_interface::{self, Burn, Mint, TokenAccount, TokenInterface, TransferChecked};
+use crate::state::{Config, Market, MarketStatus};
 use crate::errors::ErrorCode;
+use crate::utils::{outcome_to_collateral, transfer_fee};
 
 pub fn handler(
     ctx: Context<ClaimWinnings>,
     amount: u64,
 ) -> Result<()> {
+    let config = &ctx.accounts.config;
     let market = &ctx.accounts.market;
     
     // Validations
+    require!(
+        !config.is_paused(Config::PAUSE_CLAIMS),
+        ErrorCode::ProtocolPaused
+    );
+    require!(!market.halted, ErrorCode::MarketHalted);
     require!(
         market.status == MarketStatus::Resolved,
         ErrorCode::MarketNotResolved
@@ -17,6 +24,15 @@ pub fn handler(
     
     let outcome = market.outcome.ok_or(ErrorCode::OutcomeNotSet)?;
 
+    // Each whole winning token redeems one whole collateral unit, less its
+    // share of any winnings from bets placed after an early-resolved event
+    let value = match &market.late_bets {
+        Some(late_bets) => late_bets.winning_value(amount, outcome)?,
+        None => amount,
+    };
+    let payout = outcome_to_collateral(value, ctx.accounts.collateral_mint.decimals)?;
+    require!(payout > 0, ErrorCode::ZeroAmount);
+
     // Burn winning tokens
     let winning_mint = if outcome {
         &ctx.accounts.yes_token_mint
@@ -24,7 +40,7 @@ pub fn handler(
         &ctx.accounts.no_token_mint
     };
 
-    token::burn(
+    token_interface::burn(
         CpiContext::new(
             ctx.accounts.token_program.to_account_info(),
             Burn {
@@ -36,32 +52,40 @@ pub fn handler(
         amount,
     )?;
 
-    // Transfer 1 USDC per winning token
+    // Pay out one collateral unit per winning token
     let seeds = &[
-        b"market",
-        market.creator.as_ref(),
-        &market.created_at.to_le_bytes(),
+        b"market".as_ref(),
+        &market.market_id.to_le_bytes(),
         &[market.bump],
     ];
     let signer = &[&seeds[..]];
 
-    token::transfer(
+    token_interface::transfer_checked(
         CpiContext::new_with_signer(
             ctx.accounts.token_program.to_account_info(),
-            Transfer {
+            TransferChecked {
                 from: ctx.accounts.liquidity_vault.to_account_info(),
-                to: ctx.accounts.user_usdc.to_account_info(),
+                mint: ctx.accounts.collateral_mint.to_account_info(),
+                to: ctx.accounts.user_collateral.to_account_info(),
                 authority: market.to_account_info(),
             },
             signer,
         ),
-        amount, // 1:1 ratio
+        payout,
+        ctx.accounts.collateral_mint.decimals,
     )?;
 
+    // A transfer-fee collateral mint withholds its fee from the payout
+    let received = payout
+        .checked_sub(transfer_fee(&ctx.accounts.collateral_mint.to_account_info(), payout)?)
+        .ok_or(ErrorCode::MathUnderflow)?;
+
     emit!(WinningsClaimed {
         market: market.key(),
         user: ctx.accounts.user.key(),
         amount,
+        payout,
+        received,
     });
 
     Ok(())
@@ -69,6 +93,9 @@ pub fn handler(
 
 #[derive(Accounts)]
 pub struct ClaimWinnings<'info> {
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, Config>,
+
     #[account(
         constraint = market.status == MarketStatus::Resolved
     )]
@@ -79,35 +106,39 @@ pub struct ClaimWinnings<'info> {
 
     #[account(
         mut,
-        constraint = user_usdc.owner == user.key()
+        constraint = user_collateral.owner == user.key(),
+        constraint = user_collateral.mint == market.collateral_mint @ ErrorCode::InvalidCollateral
     )]
-    pub user_usdc: Account<'info, TokenAccount>,
+    pub user_collateral: InterfaceAccount<'info, TokenAccount>,
 
     #[account(
         mut,
         seeds = [b"vault", market.key().as_ref()],
         bump
     )]
-    pub liquidity_vault: Account<'info, TokenAccount>,
+    pub liquidity_vault: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(address = market.collateral_mint @ ErrorCode::InvalidCollateral)]
+    pub collateral_mint: InterfaceAccount<'info, Mint>,
 
     #[account(
         mut,
         seeds = [b"yes_mint", market.key().as_ref()],
         bump
     )]
-    pub yes_token_mint: Account<'info, Mint>,
+    pub yes_token_mint: InterfaceAccount<'info, Mint>,
 
     #[account(
         mut,
         seeds = [b"no_mint", market.key().as_ref()],
         bump
     )]
-    pub no_token_mint: Account<'info, Mint>,
+    pub no_token_mint: InterfaceAccount<'info, Mint>,
 
     #[account(mut)]
-    pub user_outcome_token: Account<'info, TokenAccount>,
+    pub user_outcome_token: InterfaceAccount<'info, TokenAccount>,
 
-    pub token_program: Program<'info, Token>,
+    pub token_program: Interface<'info, TokenInterface>,
 }
 
 #[event]
@@ -115,4 +146,6 @@ pub struct WinningsClaimed {
     pub market: Pubkey,
     pub user: Pubkey,
     pub amount: u64,
+    pub payout: u64,
+    pub received: u64,
 }
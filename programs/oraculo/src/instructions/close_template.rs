@@ -0,0 +1,110 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{
+    self, CloseAccount, Mint, TokenAccount, TokenInterface, TransferChecked,
+};
+use crate::state::{Config, MarketTemplate};
+use crate::errors::ErrorCode;
+
+pub fn handler(ctx: Context<CloseTemplate>) -> Result<()> {
+    let template = &ctx.accounts.template;
+
+    require!(
+        !ctx.accounts.config.is_paused(Config::PAUSE_CLAIMS),
+        ErrorCode::ProtocolPaused
+    );
+    // A paused series cannot spawn while its vault is being emptied
+    require!(!template.active, ErrorCode::TemplateActive);
+
+    let template_id = template.template_id.to_le_bytes();
+    let seeds = &[
+        b"template".as_ref(),
+        template.creator.as_ref(),
+        &template_id,
+        &[template.bump],
+    ];
+    let signer = &[&seeds[..]];
+
+    let withdrawn = ctx.accounts.template_vault.amount;
+    if withdrawn > 0 {
+        token_interface::transfer_checked(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                TransferChecked {
+                    from: ctx.accounts.template_vault.to_account_info(),
+                    mint: ctx.accounts.collateral_mint.to_account_info(),
+                    to: ctx.accounts.creator_collateral.to_account_info(),
+                    authority: template.to_account_info(),
+                },
+                signer,
+            ),
+            withdrawn,
+            ctx.accounts.collateral_mint.decimals,
+        )?;
+    }
+
+    token_interface::close_account(CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        CloseAccount {
+            account: ctx.accounts.template_vault.to_account_info(),
+            destination: ctx.accounts.creator.to_account_info(),
+            authority: template.to_account_info(),
+        },
+        signer,
+    ))?;
+
+    emit!(TemplateClosed {
+        template: template.key(),
+        creator: template.creator,
+        withdrawn,
+        series_index: template.series_index,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct CloseTemplate<'info> {
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        mut,
+        close = creator,
+        has_one = creator @ ErrorCode::Unauthorized,
+        seeds = [b"template", template.creator.as_ref(), &template.template_id.to_le_bytes()],
+        bump = template.bump
+    )]
+    pub template: Account<'info, MarketTemplate>,
+
+    #[account(
+        mut,
+        seeds = [b"template_vault", template.key().as_ref()],
+        bump
+    )]
+    pub template_vault: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(mut)]
+    pub creator: Signer<'info>,
+
+    #[account(
+        mut,
+        constraint = creator_collateral.owner == creator.key(),
+        constraint = creator_collateral.mint == template.collateral_mint @ ErrorCode::InvalidCollateral
+    )]
+    pub creator_collateral: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(address = template.collateral_mint @ ErrorCode::InvalidCollateral)]
+    pub collateral_mint: InterfaceAccount<'info, Mint>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+#[event]
+pub struct TemplateClosed {
+    pub template: Pubkey,
+    pub creator: Pubkey,
+    /// Collateral returned from the vault
+    pub withdrawn: u64,
+    /// Markets scheduled over the series' life
+    pub series_index: u64,
+}
@@ -0,0 +1,74 @@
+use anchor_lang::prelude::*;
+use crate::state::{Config, Market, OpenOrders, OrderBook};
+use crate::errors::ErrorCode;
+
+pub fn handler<'info>(ctx: Context<'_, '_, 'info, 'info, ConsumeFills<'info>>, limit: u8) -> Result<()> {
+    // Validations
+    require!(
+        !ctx.accounts.config.is_paused(Config::PAUSE_TRADING),
+        ErrorCode::ProtocolPaused
+    );
+    require!(!ctx.accounts.market.halted, ErrorCode::MarketHalted);
+
+    let order_book = &mut ctx.accounts.order_book;
+    let market = order_book.market;
+
+    let mut makers: Vec<Account<OpenOrders>> = Vec::with_capacity(ctx.remaining_accounts.len());
+    for info in ctx.remaining_accounts.iter() {
+        let open_orders = Account::<OpenOrders>::try_from(info)?;
+        require!(open_orders.market == market, ErrorCode::InvalidOrder);
+        // A second copy would be written back last and drop the first's credits
+        require!(
+            makers.iter().all(|maker| maker.key() != open_orders.key()),
+            ErrorCode::DuplicateOpenOrders
+        );
+        makers.push(open_orders);
+    }
+
+    // Oldest first; stop at the first maker whose account was not passed
+    let mut consumed = 0;
+    while consumed < (limit as usize).min(order_book.fills.len()) {
+        let fill = order_book.fills[consumed];
+        let Some(maker) = makers.iter_mut().find(|maker| maker.owner == fill.maker) else {
+            break;
+        };
+        maker.credit(&fill.credit)?;
+        consumed += 1;
+    }
+    order_book.fills.drain(..consumed);
+
+    for maker in makers.iter() {
+        maker.exit(ctx.program_id)?;
+    }
+
+    emit!(FillsConsumed {
+        market,
+        consumed: consumed as u8,
+        pending: order_book.fills.len() as u8,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct ConsumeFills<'info> {
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, Config>,
+
+    #[account(address = order_book.market)]
+    pub market: Account<'info, Market>,
+
+    #[account(
+        mut,
+        seeds = [b"order_book", order_book.market.as_ref()],
+        bump = order_book.bump
+    )]
+    pub order_book: Account<'info, OrderBook>,
+}
+
+#[event]
+pub struct FillsConsumed {
+    pub market: Pubkey,
+    pub consumed: u8,
+    pub pending: u8,
+}
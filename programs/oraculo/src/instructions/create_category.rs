@@ -0,0 +1,87 @@
+use anchor_lang::prelude::*;
+use crate::state::{Category, Config};
+use crate::errors::ErrorCode;
+
+pub fn handler(
+    ctx: Context<CreateCategory>,
+    id: u16,
+    name: String,
+    parent: Option<u16>,
+    min_liquidity: u64,
+    fee_bps: Option<u16>,
+) -> Result<()> {
+    // No pause check: governance-only, and must work while paused
+    Category::check_name(id, &name)?;
+    if let Some(fee_bps) = fee_bps {
+        require!(fee_bps <= Config::MAX_PROTOCOL_FEE_BPS, ErrorCode::InvalidProtocolFee);
+    }
+
+    // Subcategories must name a registered top-level parent; the reserved
+    // buckets are top-level themselves
+    let parent_category = ctx.accounts.parent_category.as_ref();
+    require!(
+        parent_category.map(|category| category.id) == parent
+            && parent_category.map_or(true, |category| category.parent.is_none())
+            && (parent.is_none() || id as usize >= Category::RESERVED.len()),
+        ErrorCode::InvalidCategory
+    );
+
+    let category = &mut ctx.accounts.category;
+    category.id = id;
+    category.name = name.clone();
+    category.parent = parent;
+    category.enabled = true;
+    category.min_liquidity = min_liquidity;
+    category.fee_bps = fee_bps;
+    category.bump = ctx.bumps.category;
+
+    emit!(CategoryCreated {
+        id,
+        name,
+        parent,
+        min_liquidity,
+        fee_bps,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(id: u16)]
+pub struct CreateCategory<'info> {
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        has_one = authority @ ErrorCode::Unauthorized
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + Category::INIT_SPACE,
+        seeds = [b"category".as_ref(), &id.to_le_bytes()],
+        bump
+    )]
+    pub category: Account<'info, Category>,
+
+    #[account(
+        seeds = [b"category".as_ref(), &parent_category.id.to_le_bytes()],
+        bump = parent_category.bump
+    )]
+    pub parent_category: Option<Account<'info, Category>>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[event]
+pub struct CategoryCreated {
+    pub id: u16,
+    pub name: String,
+    pub parent: Option<u16>,
+    pub min_liquidity: u64,
+    pub fee_bps: Option<u16>,
+}
@@ -0,0 +1,84 @@
+use anchor_lang::prelude::*;
+use crate::state::{Config, Gauge, Market, MarketStatus};
+use crate::errors::ErrorCode;
+
+pub fn handler(
+    ctx: Context<CreateGauge>,
+    emission_bucket: u8,
+    emission_rate: u64,
+    lp_weight_bps: u16,
+    start_time: i64,
+    end_time: i64,
+) -> Result<()> {
+    // No pause or halt check: governance-only, and must work while paused
+    require!(
+        lp_weight_bps <= 10_000 && end_time > start_time,
+        ErrorCode::InvalidGaugeParams
+    );
+
+    let gauge = &mut ctx.accounts.gauge;
+    gauge.market = ctx.accounts.market.key();
+    gauge.emission_bucket = emission_bucket;
+    gauge.emission_rate = emission_rate;
+    gauge.lp_weight_bps = lp_weight_bps;
+    gauge.start_time = start_time;
+    gauge.end_time = end_time;
+    gauge.last_update = start_time;
+    gauge.acc_reward_per_lp_share = 0;
+    gauge.acc_reward_per_volume = 0;
+    gauge.total_lp_shares = 0;
+    gauge.total_volume = 0;
+    gauge.bump = ctx.bumps.gauge;
+
+    emit!(GaugeCreated {
+        gauge: gauge.key(),
+        market: gauge.market,
+        emission_bucket,
+        emission_rate,
+        lp_weight_bps,
+        start_time,
+        end_time,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct CreateGauge<'info> {
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        has_one = authority @ ErrorCode::Unauthorized
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        constraint = market.status == MarketStatus::Active @ ErrorCode::MarketNotActive
+    )]
+    pub market: Account<'info, Market>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + Gauge::INIT_SPACE,
+        seeds = [b"gauge", market.key().as_ref()],
+        bump
+    )]
+    pub gauge: Account<'info, Gauge>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[event]
+pub struct GaugeCreated {
+    pub gauge: Pubkey,
+    pub market: Pubkey,
+    pub emission_bucket: u8,
+    pub emission_rate: u64,
+    pub lp_weight_bps: u16,
+    pub start_time: i64,
+    pub end_time: i64,
+}
@@ -1,24 +1,85 @@
 use anchor_lang::prelude::*;
-use anchor_spl::token::{self, Token, TokenAccount, Mint, Transfer};
-use crate::state::{Config, Market, MarketCategory, MarketStatus};
+use anchor_spl::{
+    metadata::{
+        create_metadata_accounts_v3, mpl_token_metadata::types::DataV2,
+        CreateMetadataAccountsV3, Metadata,
+    },
+    token_interface::{self, Mint, TokenAccount, TokenInterface, TransferChecked},
+};
+use crate::state::{
+    Category, CollateralConfig, Config, CreatorIndex, CreatorMarket, Market, MarketStatus, OffChainMetadata,
+    ParentOutcome, PriceCondition, PriceHistory, ResolutionMode, validate_tags,
+};
 use crate::errors::ErrorCode;
+use crate::resolution::REPORTING_WINDOW;
+use crate::utils::{implied_yes_price, outcome_token_name, outcome_token_symbol, transfer_fee, OUTCOME_DECIMALS};
 
-pub fn handler(
-    ctx: Context<CreateMarket>,
-    question: String,
-    description: String,
-    category: u8,
-    end_time: i64,
-    resolution_source: String,
-    initial_liquidity: u64,
-) -> Result<()> {
-    let config = &ctx.accounts.config;
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct CreateMarketParams {
+    pub question: String,
+    pub description: String,
+    /// Registered category id
+    pub category: u16,
+    pub end_time: i64,
+    pub resolution_source: String,
+    pub initial_liquidity: u64,
+    pub price_condition: Option<PriceCondition>,
+    pub resolution_mode: ResolutionMode,
+    pub resolver: Option<Pubkey>,
+    /// Governance default if `None`
+    pub voting_period: Option<i64>,
+    /// Governance default if `None`
+    pub resolution_grace: Option<i64>,
+    pub metadata: Option<OffChainMetadata>,
+    pub tags: Vec<String>,
+}
+
+pub fn handler(ctx: Context<CreateMarket>, params: CreateMarketParams) -> Result<()> {
+    let CreateMarketParams {
+        question,
+        description,
+        category,
+        end_time,
+        resolution_source,
+        initial_liquidity,
+        price_condition,
+        resolution_mode,
+        resolver,
+        voting_period,
+        resolution_grace,
+        metadata,
+        tags,
+    } = params;
+    let config = &mut ctx.accounts.config;
     
     // Validations
+    require!(
+        !config.is_paused(Config::PAUSE_MARKET_CREATION),
+        ErrorCode::ProtocolPaused
+    );
     require!(question.len() <= 200, ErrorCode::QuestionTooLong);
     require!(description.len() <= 500, ErrorCode::DescriptionTooLong);
     require!(resolution_source.len() <= 200, ErrorCode::SourceTooLong);
-    require!(initial_liquidity >= config.min_liquidity, ErrorCode::InsufficientLiquidity);
+    validate_tags(&tags)?;
+    let market_category = Category::load(
+        &ctx.accounts.category_account,
+        ctx.accounts.parent_category_account.as_ref().map(|parent| parent.as_ref()),
+    )?;
+    if let Some(metadata) = &metadata {
+        require!(
+            metadata.uri.len() <= OffChainMetadata::MAX_URI_LEN,
+            ErrorCode::MetadataUriTooLong
+        );
+    }
+
+    // Transfer-fee collateral lands in the vault net of the withheld fee
+    let deposited = initial_liquidity
+        .checked_sub(transfer_fee(&ctx.accounts.collateral_mint.to_account_info(), initial_liquidity)?)
+        .ok_or(ErrorCode::MathUnderflow)?;
+    require!(
+        deposited >= market_category.min_liquidity_for(&ctx.accounts.collateral)?,
+        ErrorCode::InsufficientLiquidity
+    );
     
     let clock = Clock::get()?;
     require!(end_time > clock.unix_timestamp, ErrorCode::InvalidEndTime);
@@ -27,120 +88,318 @@ pub fn handler(
         ErrorCode::EndTimeTooFar
     );
 
-    let market = &mut ctx.accounts.market;
-    let market_category = match category {
-        0 => MarketCategory::Crypto,
-        1 => MarketCategory::Sports,
-        2 => MarketCategory::Politics,
-        3 => MarketCategory::Entertainment,
-        4 => MarketCategory::Technology,
-        _ => MarketCategory::Other,
+    if let Some(condition) = &price_condition {
+        require!(
+            condition.threshold > 0
+                && condition.max_staleness > 0
+                && condition.max_conf_bps > 0
+                && condition.max_conf_bps <= 10_000,
+            ErrorCode::InvalidPriceCondition
+        );
+    }
+
+    require!(
+        (resolution_mode == ResolutionMode::PriceOracle) == price_condition.is_some()
+            && resolution_mode.has_named_resolver() == resolver.is_some(),
+        ErrorCode::InvalidResolutionMode
+    );
+
+    // Votes must be able to finish inside the resolution window
+    let voting_period = config.voting_period(voting_period)?;
+    let resolution_grace = config.resolution_grace(resolution_grace)?;
+    let reporting_window = if resolution_mode == ResolutionMode::DesignatedReporter {
+        REPORTING_WINDOW
+    } else {
+        0
+    };
+    require!(
+        reporting_window + voting_period <= resolution_grace,
+        ErrorCode::InvalidResolutionPeriod
+    );
+
+    // A conditional market's collateral is one of its parent's outcome tokens,
+    // and only conditional markets may use outcome tokens as collateral
+    let collateral_mint = ctx.accounts.collateral_mint.key();
+    let parent = match &ctx.accounts.parent_market {
+        Some(parent_market) => {
+            require!(
+                parent_market.status == MarketStatus::Active,
+                ErrorCode::InvalidParentMarket
+            );
+            require!(
+                collateral_mint == parent_market.yes_mint || collateral_mint == parent_market.no_mint,
+                ErrorCode::InvalidParentMarket
+            );
+            Some(ParentOutcome {
+                market: parent_market.key(),
+                outcome: collateral_mint == parent_market.yes_mint,
+            })
+        }
+        None => None,
     };
+    require!(
+        ctx.accounts.collateral.outcome_of == parent.map(|parent| parent.market),
+        ErrorCode::InvalidParentMarket
+    );
+
+    let market = &mut ctx.accounts.market;
     
     // Initialize market
+    market.market_id = config.total_markets;
     market.creator = ctx.accounts.creator.key();
+    market.collateral_mint = collateral_mint;
     market.question = question.clone();
     market.description = description;
-    market.category = market_category;
+    market.category = category;
+    market.tags = tags.clone();
+    market.fee_bps = market_category.fee_bps;
     market.created_at = clock.unix_timestamp;
     market.end_time = end_time;
-    market.resolution_time = end_time + (7 * 24 * 60 * 60); // +7 days for voting
+    market.resolution_time = end_time + resolution_grace;
+    market.voting_period = voting_period;
+    market.resolution_grace = resolution_grace;
     market.resolution_source = resolution_source;
+    market.metadata = metadata;
+    market.edits = 0;
+    market.has_trades = false;
+    market.price_condition = price_condition;
+    market.resolution_mode = resolution_mode;
+    market.resolver = resolver;
+    market.parent = parent;
+    market.reporter_bond = 0;
+    market.reported_outcome = None;
+    market.reported_at = None;
+    market.report_disputed = false;
+    market.late_bets = None;
+    market.open_proposals = 0;
     market.status = MarketStatus::Active;
-    market.total_liquidity = initial_liquidity;
-    market.yes_pool = initial_liquidity / 2;
-    market.no_pool = initial_liquidity / 2;
+    market.total_liquidity = deposited;
+    market.creator_liquidity = deposited;
+    market.yes_pool = deposited / 2;
+    market.no_pool = deposited / 2;
     market.yes_mint = ctx.accounts.yes_token_mint.key();
     market.no_mint = ctx.accounts.no_token_mint.key();
     market.volume = 0;
     market.unique_bettors = 0;
+    market.halted = false;
     market.bump = ctx.bumps.market;
 
+    config.total_markets = config.total_markets.checked_add(1).ok_or(ErrorCode::MathOverflow)?;
+
+    // Index the market under its creator
+    let creator_index = &mut ctx.accounts.creator_index;
+    creator_index.creator = market.creator;
+    creator_index.bump = ctx.bumps.creator_index;
+    let creator_market = &mut ctx.accounts.creator_market;
+    creator_market.creator = market.creator;
+    creator_market.index = creator_index.next_index()?;
+    creator_market.market = market.key();
+    creator_market.market_id = market.market_id;
+    creator_market.bump = ctx.bumps.creator_market;
+
+    ctx.accounts.price_history.init(
+        market.key(),
+        clock.unix_timestamp,
+        implied_yes_price(market.yes_pool, market.no_pool)?,
+        ctx.bumps.price_history,
+    );
+
     // Lock creator liquidity (Proof of Liquidity - anti-rug)
-    token::transfer(
+    token_interface::transfer_checked(
         CpiContext::new(
             ctx.accounts.token_program.to_account_info(),
-            Transfer {
-                from: ctx.accounts.creator_usdc.to_account_info(),
+            TransferChecked {
+                from: ctx.accounts.creator_collateral.to_account_info(),
+                mint: ctx.accounts.collateral_mint.to_account_info(),
                 to: ctx.accounts.liquidity_vault.to_account_info(),
                 authority: ctx.accounts.creator.to_account_info(),
             },
         ),
         initial_liquidity,
+        ctx.accounts.collateral_mint.decimals,
     )?;
 
+    // Name the outcome tokens after the question so wallets can tell them apart
+    let market_id = market.market_id.to_le_bytes();
+    let seeds = &[b"market".as_ref(), &market_id, &[market.bump]];
+    let signer = &[&seeds[..]];
+
+    for (outcome, mint, metadata) in [
+        (true, &ctx.accounts.yes_token_mint, &ctx.accounts.yes_metadata),
+        (false, &ctx.accounts.no_token_mint, &ctx.accounts.no_metadata),
+    ] {
+        create_metadata_accounts_v3(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_metadata_program.to_account_info(),
+                CreateMetadataAccountsV3 {
+                    metadata: metadata.to_account_info(),
+                    mint: mint.to_account_info(),
+                    mint_authority: market.to_account_info(),
+                    payer: ctx.accounts.creator.to_account_info(),
+                    update_authority: market.to_account_info(),
+                    system_program: ctx.accounts.system_program.to_account_info(),
+                    rent: ctx.accounts.rent.to_account_info(),
+                },
+                signer,
+            ),
+            DataV2 {
+                name: outcome_token_name(&question, outcome),
+                symbol: outcome_token_symbol(outcome),
+                uri: String::new(),
+                seller_fee_basis_points: 0,
+                creators: None,
+                collection: None,
+                uses: None,
+            },
+            true,
+            true,
+            None,
+        )?;
+    }
+
     emit!(MarketCreated {
         market: market.key(),
+        market_id: market.market_id,
         creator: market.creator,
         question,
-        category: market.category.clone(),
+        category,
+        tags,
         end_time: market.end_time,
-        initial_liquidity,
+        initial_liquidity: deposited,
+        parent,
+        metadata: market.metadata.clone(),
     });
 
     Ok(())
 }
 
 #[derive(Accounts)]
-#[instruction(question: String)]
+#[instruction(params: CreateMarketParams)]
 pub struct CreateMarket<'info> {
-    #[account(seeds = [b"config"], bump = config.bump)]
+    #[account(mut, seeds = [b"config"], bump = config.bump)]
     pub config: Account<'info, Config>,
 
     #[account(
         init,
         payer = creator,
         space = 8 + Market::INIT_SPACE,
-        seeds = [
-            b"market",
-            creator.key().as_ref(),
-            &Clock::get()?.unix_timestamp.to_le_bytes()
-        ],
+        seeds = [b"market".as_ref(), &config.total_markets.to_le_bytes()],
         bump
     )]
     pub market: Account<'info, Market>,
 
+    #[account(
+        init_if_needed,
+        payer = creator,
+        space = 8 + CreatorIndex::INIT_SPACE,
+        seeds = [b"creator_index", creator.key().as_ref()],
+        bump
+    )]
+    pub creator_index: Account<'info, CreatorIndex>,
+
+    #[account(
+        init,
+        payer = creator,
+        space = 8 + CreatorMarket::INIT_SPACE,
+        seeds = [b"creator_market", creator.key().as_ref(), &creator_index.market_count.to_le_bytes()],
+        bump
+    )]
+    pub creator_market: Account<'info, CreatorMarket>,
+
+    #[account(
+        init,
+        payer = creator,
+        space = 8 + PriceHistory::INIT_SPACE,
+        seeds = [b"price_history", market.key().as_ref()],
+        bump
+    )]
+    pub price_history: Account<'info, PriceHistory>,
+
     #[account(mut)]
     pub creator: Signer<'info>,
 
     #[account(
         mut,
-        constraint = creator_usdc.owner == creator.key()
+        constraint = creator_collateral.owner == creator.key(),
+        constraint = creator_collateral.mint == collateral_mint.key() @ ErrorCode::InvalidCollateral
     )]
-    pub creator_usdc: Account<'info, TokenAccount>,
+    pub creator_collateral: InterfaceAccount<'info, TokenAccount>,
 
     #[account(
         init,
         payer = creator,
-        token::mint = usdc_mint,
+        token::mint = collateral_mint,
         token::authority = market,
+        token::token_program = token_program,
         seeds = [b"vault", market.key().as_ref()],
         bump
     )]
-    pub liquidity_vault: Account<'info, TokenAccount>,
+    pub liquidity_vault: InterfaceAccount<'info, TokenAccount>,
 
     #[account(
         init,
         payer = creator,
-        mint::decimals = 9,
+        mint::decimals = OUTCOME_DECIMALS,
         mint::authority = market,
+        mint::token_program = token_program,
         seeds = [b"yes_mint", market.key().as_ref()],
         bump
     )]
-    pub yes_token_mint: Account<'info, Mint>,
+    pub yes_token_mint: InterfaceAccount<'info, Mint>,
 
     #[account(
         init,
         payer = creator,
-        mint::decimals = 9,
+        mint::decimals = OUTCOME_DECIMALS,
         mint::authority = market,
+        mint::token_program = token_program,
         seeds = [b"no_mint", market.key().as_ref()],
         bump
     )]
-    pub no_token_mint: Account<'info, Mint>,
+    pub no_token_mint: InterfaceAccount<'info, Mint>,
+
+    /// CHECK: Created and validated by the token metadata program
+    #[account(
+        mut,
+        seeds = [b"metadata", token_metadata_program.key().as_ref(), yes_token_mint.key().as_ref()],
+        bump,
+        seeds::program = token_metadata_program.key()
+    )]
+    pub yes_metadata: UncheckedAccount<'info>,
+
+    /// CHECK: Created and validated by the token metadata program
+    #[account(
+        mut,
+        seeds = [b"metadata", token_metadata_program.key().as_ref(), no_token_mint.key().as_ref()],
+        bump,
+        seeds::program = token_metadata_program.key()
+    )]
+    pub no_metadata: UncheckedAccount<'info>,
+
+    #[account(
+        seeds = [b"collateral", collateral_mint.key().as_ref()],
+        bump = collateral.bump,
+        constraint = collateral.enabled @ ErrorCode::CollateralNotEnabled
+    )]
+    pub collateral: Account<'info, CollateralConfig>,
+
+    /// CHECK: The category's PDA, empty if the id was never registered
+    #[account(
+        seeds = [b"category".as_ref(), &params.category.to_le_bytes()],
+        bump
+    )]
+    pub category_account: UncheckedAccount<'info>,
+
+    /// CHECK: The category's parent, for subcategories; checked by `Category::load`
+    pub parent_category_account: Option<UncheckedAccount<'info>>,
+
+    /// Market whose outcome token is the collateral, for conditional markets
+    pub parent_market: Option<Account<'info, Market>>,
 
-    pub usdc_mint: Account<'info, Mint>,
-    pub token_program: Program<'info, Token>,
+    #[account(mint::token_program = token_program)]
+    pub collateral_mint: InterfaceAccount<'info, Mint>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub token_metadata_program: Program<'info, Metadata>,
     pub system_program: Program<'info, System>,
     pub rent: Sysvar<'info, Rent>,
 }
@@ -148,9 +407,13 @@ pub struct CreateMarket<'info> {
 #[event]
 pub struct MarketCreated {
     pub market: Pubkey,
+    pub market_id: u64,
     pub creator: Pubkey,
     pub question: String,
-    pub category: MarketCategory,
+    pub category: u16,
+    pub tags: Vec<String>,
     pub end_time: i64,
     pub initial_liquidity: u64,
+    pub parent: Option<ParentOutcome>,
+    pub metadata: Option<OffChainMetadata>,
 }
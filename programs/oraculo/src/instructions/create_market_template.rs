@@ -0,0 +1,211 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+use crate::state::{
+    Category, CollateralConfig, Config, MarketTemplate, PriceCondition, ResolutionMode,
+};
+use crate::errors::ErrorCode;
+use crate::resolution::REPORTING_WINDOW;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct CreateMarketTemplateParams {
+    pub template_id: u64,
+    /// Question with `{index}` and `{date}` placeholders
+    pub question_pattern: String,
+    pub description: String,
+    /// Registered category id
+    pub category: u16,
+    pub resolution_source: String,
+    pub price_condition: Option<PriceCondition>,
+    pub resolution_mode: ResolutionMode,
+    pub resolver: Option<Pubkey>,
+    /// Governance default if `None`
+    pub voting_period: Option<i64>,
+    /// Governance default if `None`
+    pub resolution_grace: Option<i64>,
+    /// Seconds each market trades for
+    pub duration: i64,
+    /// Seconds between market starts
+    pub interval: i64,
+    /// Liquidity each market is seeded with from the template vault
+    pub liquidity: u64,
+    pub first_spawn_at: i64,
+}
+
+pub fn handler(ctx: Context<CreateMarketTemplate>, params: CreateMarketTemplateParams) -> Result<()> {
+    let CreateMarketTemplateParams {
+        template_id,
+        question_pattern,
+        description,
+        category,
+        resolution_source,
+        price_condition,
+        resolution_mode,
+        resolver,
+        voting_period,
+        resolution_grace,
+        duration,
+        interval,
+        liquidity,
+        first_spawn_at,
+    } = params;
+    let config = &ctx.accounts.config;
+    let collateral = &ctx.accounts.collateral;
+
+    // Validations, as in `create_market`
+    require!(
+        !config.is_paused(Config::PAUSE_MARKET_CREATION),
+        ErrorCode::ProtocolPaused
+    );
+    // No halt check: a template has no market of its own, and each market
+    // it spawns starts unhalted
+    require!(question_pattern.len() <= 200, ErrorCode::QuestionTooLong);
+    require!(description.len() <= 500, ErrorCode::DescriptionTooLong);
+    require!(resolution_source.len() <= 200, ErrorCode::SourceTooLong);
+    require!(collateral.outcome_of.is_none(), ErrorCode::InvalidParentMarket);
+    let market_category = Category::load(
+        &ctx.accounts.category_account,
+        ctx.accounts.parent_category_account.as_ref().map(|parent| parent.as_ref()),
+    )?;
+    require!(
+        liquidity >= market_category.min_liquidity_for(collateral)?,
+        ErrorCode::InsufficientLiquidity
+    );
+    // A schedule starting in the past would make the first spawn skip
+    // through every slot since then
+    let clock = Clock::get()?;
+    require!(
+        duration > 0
+            && duration < 365 * 24 * 60 * 60
+            && interval >= 1
+            && first_spawn_at >= clock.unix_timestamp,
+        ErrorCode::InvalidTemplate
+    );
+
+    if let Some(condition) = &price_condition {
+        require!(
+            condition.threshold > 0
+                && condition.max_staleness > 0
+                && condition.max_conf_bps > 0
+                && condition.max_conf_bps <= 10_000,
+            ErrorCode::InvalidPriceCondition
+        );
+    }
+
+    require!(
+        (resolution_mode == ResolutionMode::PriceOracle) == price_condition.is_some()
+            && resolution_mode.has_named_resolver() == resolver.is_some(),
+        ErrorCode::InvalidResolutionMode
+    );
+
+    let voting_period = config.voting_period(voting_period)?;
+    let resolution_grace = config.resolution_grace(resolution_grace)?;
+    let reporting_window = if resolution_mode == ResolutionMode::DesignatedReporter {
+        REPORTING_WINDOW
+    } else {
+        0
+    };
+    require!(
+        reporting_window + voting_period <= resolution_grace,
+        ErrorCode::InvalidResolutionPeriod
+    );
+
+    let template = &mut ctx.accounts.template;
+    template.creator = ctx.accounts.creator.key();
+    template.template_id = template_id;
+    template.collateral_mint = ctx.accounts.collateral_mint.key();
+    template.question_pattern = question_pattern;
+    template.description = description;
+    template.category = category;
+    template.resolution_source = resolution_source;
+    template.price_condition = price_condition;
+    template.resolution_mode = resolution_mode;
+    template.resolver = resolver;
+    template.voting_period = voting_period;
+    template.resolution_grace = resolution_grace;
+    template.duration = duration;
+    template.interval = interval;
+    template.liquidity = liquidity;
+    template.series_index = 0;
+    template.next_spawn_at = first_spawn_at;
+    template.last_market = None;
+    template.active = true;
+    template.bump = ctx.bumps.template;
+
+    emit!(MarketTemplateCreated {
+        template: template.key(),
+        creator: template.creator,
+        template_id,
+        question_pattern: template.question_pattern.clone(),
+        duration,
+        interval,
+        liquidity,
+        first_spawn_at,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(params: CreateMarketTemplateParams)]
+pub struct CreateMarketTemplate<'info> {
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        init,
+        payer = creator,
+        space = 8 + MarketTemplate::INIT_SPACE,
+        seeds = [b"template", creator.key().as_ref(), &params.template_id.to_le_bytes()],
+        bump
+    )]
+    pub template: Account<'info, MarketTemplate>,
+
+    /// Funds spawned markets' liquidity; anyone can top it up
+    #[account(
+        init,
+        payer = creator,
+        token::mint = collateral_mint,
+        token::authority = template,
+        token::token_program = token_program,
+        seeds = [b"template_vault", template.key().as_ref()],
+        bump
+    )]
+    pub template_vault: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(mut)]
+    pub creator: Signer<'info>,
+
+    #[account(
+        seeds = [b"collateral", collateral_mint.key().as_ref()],
+        bump = collateral.bump,
+        constraint = collateral.enabled @ ErrorCode::CollateralNotEnabled
+    )]
+    pub collateral: Account<'info, CollateralConfig>,
+
+    /// CHECK: The category's PDA, empty if the id was never registered
+    #[account(
+        seeds = [b"category".as_ref(), &params.category.to_le_bytes()],
+        bump
+    )]
+    pub category_account: UncheckedAccount<'info>,
+
+    /// CHECK: The category's parent, for subcategories; checked by `Category::load`
+    pub parent_category_account: Option<UncheckedAccount<'info>>,
+
+    #[account(mint::token_program = token_program)]
+    pub collateral_mint: InterfaceAccount<'info, Mint>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+#[event]
+pub struct MarketTemplateCreated {
+    pub template: Pubkey,
+    pub creator: Pubkey,
+    pub template_id: u64,
+    pub question_pattern: String,
+    pub duration: i64,
+    pub interval: i64,
+    pub liquidity: u64,
+    pub first_spawn_at: i64,
+}
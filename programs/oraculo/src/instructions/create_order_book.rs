@@ -0,0 +1,113 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+use crate::state::{Config, Market, MarketStatus, OrderBook};
+use crate::errors::ErrorCode;
+use crate::utils::transfer_fee;
+
+pub fn handler(ctx: Context<CreateOrderBook>) -> Result<()> {
+    // Validations
+    require!(
+        !ctx.accounts.config.is_paused(Config::PAUSE_TRADING),
+        ErrorCode::ProtocolPaused
+    );
+    require!(!ctx.accounts.market.halted, ErrorCode::MarketHalted);
+    // Escrow math is exact, so the collateral must arrive in full
+    require!(
+        transfer_fee(&ctx.accounts.collateral_mint.to_account_info(), 1_000_000)? == 0,
+        ErrorCode::TransferFeeNotSupported
+    );
+
+    let order_book = &mut ctx.accounts.order_book;
+    order_book.market = ctx.accounts.market.key();
+    order_book.next_order_id = 0;
+    order_book.bids = Vec::new();
+    order_book.asks = Vec::new();
+    order_book.fills = Vec::new();
+    order_book.bump = ctx.bumps.order_book;
+
+    emit!(OrderBookCreated {
+        market: order_book.market,
+        order_book: order_book.key(),
+        payer: ctx.accounts.payer.key(),
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct CreateOrderBook<'info> {
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        constraint = market.status == MarketStatus::Active @ ErrorCode::MarketNotActive
+    )]
+    pub market: Account<'info, Market>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + OrderBook::INIT_SPACE,
+        seeds = [b"order_book", market.key().as_ref()],
+        bump
+    )]
+    pub order_book: Account<'info, OrderBook>,
+
+    #[account(
+        init,
+        payer = payer,
+        token::mint = collateral_mint,
+        token::authority = market,
+        seeds = [b"book_collateral", market.key().as_ref()],
+        bump
+    )]
+    pub book_collateral_vault: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        init,
+        payer = payer,
+        token::mint = yes_token_mint,
+        token::authority = market,
+        seeds = [b"book_yes", market.key().as_ref()],
+        bump
+    )]
+    pub book_yes_vault: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        init,
+        payer = payer,
+        token::mint = no_token_mint,
+        token::authority = market,
+        seeds = [b"book_no", market.key().as_ref()],
+        bump
+    )]
+    pub book_no_vault: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(address = market.collateral_mint @ ErrorCode::InvalidCollateral)]
+    pub collateral_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        seeds = [b"yes_mint", market.key().as_ref()],
+        bump
+    )]
+    pub yes_token_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        seeds = [b"no_mint", market.key().as_ref()],
+        bump
+    )]
+    pub no_token_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+#[event]
+pub struct OrderBookCreated {
+    pub market: Pubkey,
+    pub order_book: Pubkey,
+    pub payer: Pubkey,
+}
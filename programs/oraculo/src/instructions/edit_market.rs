@@ -0,0 +1,207 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    metadata::{
+        mpl_token_metadata::types::DataV2, update_metadata_accounts_v2, Metadata,
+        UpdateMetadataAccountsV2,
+    },
+    token_interface::Mint,
+};
+use crate::state::{Config, Market, MarketStatus, OffChainMetadata, OrderBook};
+use crate::errors::ErrorCode;
+use crate::utils::{outcome_token_name, outcome_token_symbol};
+
+pub fn handler(
+    ctx: Context<EditMarket>,
+    question: Option<String>,
+    description: Option<String>,
+    resolution_source: Option<String>,
+    metadata: Option<OffChainMetadata>,
+) -> Result<()> {
+    let market = &mut ctx.accounts.market;
+
+    // Validations
+    require!(
+        !ctx.accounts.config.is_paused(Config::PAUSE_MARKET_CREATION),
+        ErrorCode::ProtocolPaused
+    );
+    require!(!market.halted, ErrorCode::MarketHalted);
+    require!(
+        market.status == MarketStatus::Active,
+        ErrorCode::MarketNotActive
+    );
+    // Any trade, resting order or outcome token in circulation means someone
+    // has traded on the text
+    require!(
+        !market.has_trades
+            && ctx.accounts.yes_token_mint.supply == 0
+            && ctx.accounts.no_token_mint.supply == 0,
+        ErrorCode::MarketHasBets
+    );
+    let book = &ctx.accounts.order_book;
+    if !book.data_is_empty() {
+        let book = OrderBook::try_deserialize(&mut &book.try_borrow_data()?[..])?;
+        require!(
+            book.bids.is_empty() && book.asks.is_empty() && book.fills.is_empty(),
+            ErrorCode::MarketHasBets
+        );
+    }
+    if let Some(question) = &question {
+        require!(question.len() <= 200, ErrorCode::QuestionTooLong);
+    }
+    if let Some(description) = &description {
+        require!(description.len() <= 500, ErrorCode::DescriptionTooLong);
+    }
+    if let Some(resolution_source) = &resolution_source {
+        require!(resolution_source.len() <= 200, ErrorCode::SourceTooLong);
+    }
+    if let Some(metadata) = &metadata {
+        require!(
+            metadata.uri.len() <= OffChainMetadata::MAX_URI_LEN,
+            ErrorCode::MetadataUriTooLong
+        );
+    }
+
+    let before = MarketText::of(market);
+    let question_changed = question.as_ref().is_some_and(|question| *question != market.question);
+    if let Some(question) = question {
+        market.question = question;
+    }
+    if let Some(description) = description {
+        market.description = description;
+    }
+    if let Some(resolution_source) = resolution_source {
+        market.resolution_source = resolution_source;
+    }
+    if metadata.is_some() {
+        market.metadata = metadata;
+    }
+    market.edits = market.edits.checked_add(1).ok_or(ErrorCode::MathOverflow)?;
+
+    // Outcome token names follow the question
+    if question_changed {
+        let market_id = market.market_id.to_le_bytes();
+        let seeds = &[b"market".as_ref(), &market_id, &[market.bump]];
+        let signer = &[&seeds[..]];
+
+        for (outcome, metadata) in [
+            (true, &ctx.accounts.yes_metadata),
+            (false, &ctx.accounts.no_metadata),
+        ] {
+            update_metadata_accounts_v2(
+                CpiContext::new_with_signer(
+                    ctx.accounts.token_metadata_program.to_account_info(),
+                    UpdateMetadataAccountsV2 {
+                        metadata: metadata.to_account_info(),
+                        update_authority: market.to_account_info(),
+                    },
+                    signer,
+                ),
+                None,
+                Some(DataV2 {
+                    name: outcome_token_name(&market.question, outcome),
+                    symbol: outcome_token_symbol(outcome),
+                    uri: String::new(),
+                    seller_fee_basis_points: 0,
+                    creators: None,
+                    collection: None,
+                    uses: None,
+                }),
+                None,
+                None,
+            )?;
+        }
+    }
+
+    emit!(MarketEdited {
+        market: market.key(),
+        creator: market.creator,
+        edit: market.edits,
+        before,
+        after: MarketText::of(market),
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct EditMarket<'info> {
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        mut,
+        has_one = creator @ ErrorCode::Unauthorized
+    )]
+    pub market: Account<'info, Market>,
+
+    pub creator: Signer<'info>,
+
+    #[account(
+        seeds = [b"yes_mint", market.key().as_ref()],
+        bump
+    )]
+    pub yes_token_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        seeds = [b"no_mint", market.key().as_ref()],
+        bump
+    )]
+    pub no_token_mint: InterfaceAccount<'info, Mint>,
+
+    /// CHECK: The market's order book PDA, empty if no book was opened
+    #[account(
+        seeds = [b"order_book", market.key().as_ref()],
+        bump
+    )]
+    pub order_book: UncheckedAccount<'info>,
+
+    /// CHECK: Validated by the token metadata program
+    #[account(
+        mut,
+        seeds = [b"metadata", token_metadata_program.key().as_ref(), yes_token_mint.key().as_ref()],
+        bump,
+        seeds::program = token_metadata_program.key()
+    )]
+    pub yes_metadata: UncheckedAccount<'info>,
+
+    /// CHECK: Validated by the token metadata program
+    #[account(
+        mut,
+        seeds = [b"metadata", token_metadata_program.key().as_ref(), no_token_mint.key().as_ref()],
+        bump,
+        seeds::program = token_metadata_program.key()
+    )]
+    pub no_metadata: UncheckedAccount<'info>,
+
+    pub token_metadata_program: Program<'info, Metadata>,
+}
+
+/// A market's editable text, as logged before and after each edit
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq)]
+pub struct MarketText {
+    pub question: String,
+    pub description: String,
+    pub resolution_source: String,
+    pub metadata: Option<OffChainMetadata>,
+}
+
+impl MarketText {
+    fn of(market: &Market) -> Self {
+        Self {
+            question: market.question.clone(),
+            description: market.description.clone(),
+            resolution_source: market.resolution_source.clone(),
+            metadata: market.metadata.clone(),
+        }
+    }
+}
+
+#[event]
+pub struct MarketEdited {
+    pub market: Pubkey,
+    pub creator: Pubkey,
+    /// Running count of edits to the market
+    pub edit: u16,
+    pub before: MarketText,
+    pub after: MarketText,
+}
@@ -1,6 +1,10 @@
 use anchor_lang::prelude::*;
-use crate::state::{Config, Market, MarketStatus, Proposal, ProposalStatus};
+use anchor_spl::token_interface::{self, Mint, TokenAccount, TokenInterface, TransferChecked};
+use crate::state::{
+    Config, Market, MarketStatus, Proposal, ProposalStatus, TreasuryMovement, TreasuryMovementKind,
+};
 use crate::errors::ErrorCode;
+use crate::resolution::{early_resolution_outcome, resolver_for};
 
 pub fn handler(ctx: Context<ExecuteResolution>) -> Result<()> {
     let config = &ctx.accounts.config;
@@ -11,53 +15,117 @@ pub fn handler(ctx: Context<ExecuteResolution>) -> Result<()> {
     
     // Validations
     require!(
-        proposal.status == ProposalStatus::Active,
-        ErrorCode::ProposalNotActive
+        !config.is_paused(Config::PAUSE_PROPOSALS),
+        ErrorCode::ProtocolPaused
     );
+    require!(!market.halted, ErrorCode::MarketHalted);
     require!(
-        clock.unix_timestamp >= proposal.voting_ends_at,
-        ErrorCode::VotingNotEnded
+        proposal.status == ProposalStatus::Active,
+        ErrorCode::ProposalNotActive
     );
-
-    // Check quorum
     let total_votes = proposal.votes_for.checked_add(proposal.votes_against).unwrap();
-    require!(
-        total_votes >= config.quorum,
-        ErrorCode::QuorumNotReached
-    );
 
-    // Check supermajority
-    let winner_votes = proposal.votes_for.max(proposal.votes_against);
-    let supermajority_threshold = total_votes
-        .checked_mul(config.supermajority_percent as u64).unwrap()
-        .checked_div(100).unwrap();
-    
-    require!(
-        winner_votes >= supermajority_threshold,
-        ErrorCode::NoSupermajority
-    );
+    // Early proposals need the early supermajority; otherwise trading resumes
+    let outcome = if proposal.early {
+        require!(
+            market.status == MarketStatus::ResolvingEarly,
+            ErrorCode::MarketNotActive
+        );
+        early_resolution_outcome(config, proposal, clock.unix_timestamp)?
+    } else {
+        require!(
+            market.status == MarketStatus::Active,
+            ErrorCode::MarketNotActive
+        );
+        Some(resolver_for(market.resolution_mode).finalise(
+            config,
+            market,
+            proposal,
+            clock.unix_timestamp,
+        )?)
+    };
 
-    // Determine outcome
-    let outcome = proposal.votes_for > proposal.votes_against;
-    
-    // Update market
-    market.status = MarketStatus::Resolved;
-    market.outcome = Some(outcome);
-    market.resolved_at = Some(clock.unix_timestamp);
+    market.open_proposals = market.open_proposals.saturating_sub(1);
+    match outcome {
+        Some(outcome) => {
+            market.status = MarketStatus::Resolved;
+            market.outcome = Some(outcome);
+            market.resolved_at = Some(clock.unix_timestamp);
+            if proposal.early {
+                market.late_bets = proposal.late_bets;
+            }
+            proposal.status = ProposalStatus::Executed;
+        }
+        None => {
+            market.status = MarketStatus::Active;
+            proposal.status = ProposalStatus::Rejected;
+        }
+    }
+
+    // Slash proposer if wrong or rejected, return stake if correct
+    let correct_proposal = outcome == Some(proposal.outcome);
+    let stake = ctx.accounts.proposal_stake_vault.amount;
+
+    let seed_prefix: &[u8] = if proposal.early { b"early_proposal" } else { b"proposal" };
+    let proposal_seeds = &[
+        seed_prefix,
+        proposal.market.as_ref(),
+        proposal.proposer.as_ref(),
+        &[proposal.bump],
+    ];
+    let signer = &[&proposal_seeds[..]];
 
-    // Update proposal
-    proposal.status = ProposalStatus::Executed;
+    let destination = if correct_proposal {
+        ctx.accounts.proposer_gov_token.to_account_info()
+    } else {
+        ctx.accounts.treasury_gov_vault.to_account_info()
+    };
 
-    // Slash proposer if wrong, reward if correct
-    let correct_proposal = outcome == proposal.outcome;
+    if stake > 0 {
+        token_interface::transfer_checked(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                TransferChecked {
+                    from: ctx.accounts.proposal_stake_vault.to_account_info(),
+                    mint: ctx.accounts.governance_token_mint.to_account_info(),
+                    to: destination,
+                    authority: proposal.to_account_info(),
+                },
+                signer,
+            ),
+            stake,
+            ctx.accounts.governance_token_mint.decimals,
+        )?;
+
+        if !correct_proposal {
+            emit!(TreasuryMovement {
+                kind: TreasuryMovementKind::SlashedStake,
+                mint: config.governance_token_mint,
+                amount: stake,
+                counterparty: proposal.proposer,
+                reference: proposal.key(),
+                timestamp: clock.unix_timestamp,
+            });
+        }
+    }
     
-    emit!(MarketResolved {
-        market: market.key(),
-        proposal: proposal.key(),
-        outcome,
-        total_votes,
-        proposer_correct: correct_proposal,
-    });
+    let stake_slashed = if correct_proposal { 0 } else { stake };
+    match outcome {
+        Some(outcome) => emit!(MarketResolved {
+            market: market.key(),
+            proposal: proposal.key(),
+            outcome,
+            total_votes,
+            proposer_correct: correct_proposal,
+            stake_slashed,
+        }),
+        None => emit!(EarlyResolutionRejected {
+            market: market.key(),
+            proposal: proposal.key(),
+            total_votes,
+            stake_slashed,
+        }),
+    }
 
     Ok(())
 }
@@ -76,6 +144,31 @@ pub struct ExecuteResolution<'info> {
     )]
     pub proposal: Account<'info, Proposal>,
 
+    #[account(
+        mut,
+        seeds = [b"proposal_stake", proposal.key().as_ref()],
+        bump
+    )]
+    pub proposal_stake_vault: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        constraint = proposer_gov_token.owner == proposal.proposer,
+        constraint = proposer_gov_token.mint == config.governance_token_mint
+    )]
+    pub proposer_gov_token: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        seeds = [b"treasury_vault", config.governance_token_mint.as_ref()],
+        bump
+    )]
+    pub treasury_gov_vault: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(address = config.governance_token_mint)]
+    pub governance_token_mint: InterfaceAccount<'info, Mint>,
+
+    pub token_program: Interface<'info, TokenInterface>,
     pub system_program: Program<'info, System>,
 }
 
@@ -86,4 +179,13 @@ pub struct MarketResolved {
     pub outcome: bool,
     pub total_votes: u64,
     pub proposer_correct: bool,
+    pub stake_slashed: u64,
+}
+
+#[event]
+pub struct EarlyResolutionRejected {
+    pub market: Pubkey,
+    pub proposal: Pubkey,
+    pub total_votes: u64,
+    pub stake_slashed: u64,
 }
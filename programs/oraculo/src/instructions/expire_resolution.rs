@@ -0,0 +1,165 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{self, Mint, TokenAccount, TokenInterface, TransferChecked};
+use crate::state::{
+    Config, Market, MarketStatus, PriceHistory, Proposal, ProposalStatus, ResolutionFallback,
+};
+use crate::errors::ErrorCode;
+use crate::resolution::proposal_passes;
+
+pub fn handler<'info>(ctx: Context<'_, '_, 'info, 'info, ExpireResolution<'info>>) -> Result<()> {
+    let config = &ctx.accounts.config;
+    let market = &mut ctx.accounts.market;
+
+    // Validations
+    require!(
+        !config.is_paused(Config::PAUSE_PROPOSALS),
+        ErrorCode::ProtocolPaused
+    );
+    require!(!market.halted, ErrorCode::MarketHalted);
+    // An early proposal is settled by executing it, which resolves the
+    // market or resumes trading and handles the proposer's stake
+    require!(
+        market.status != MarketStatus::ResolvingEarly,
+        ErrorCode::ResolutionPending
+    );
+    require!(
+        market.status == MarketStatus::Active,
+        ErrorCode::MarketNotActive
+    );
+
+    let clock = Clock::get()?;
+    require!(
+        clock.unix_timestamp > market.resolution_time,
+        ErrorCode::ResolutionDeadlineNotPassed
+    );
+    let mut stakes_returned: u64 = 0;
+
+    // Every open proposal must be passed with its stake vault and the
+    // proposer's governance token account, and none may be executable
+    require!(
+        ctx.remaining_accounts.len() == market.open_proposals as usize * 3,
+        ErrorCode::ProposalsMissing
+    );
+    let mut proposals: Vec<Account<Proposal>> = Vec::with_capacity(market.open_proposals as usize);
+    for accounts in ctx.remaining_accounts.chunks(3) {
+        let proposal = Account::<Proposal>::try_from(&accounts[0])?;
+        require!(
+            proposal.market == market.key()
+                && proposal.status == ProposalStatus::Active
+                && proposals.iter().all(|seen| seen.key() != proposal.key()),
+            ErrorCode::ProposalsMissing
+        );
+        require!(
+            !proposal_passes(config, market, &proposal, clock.unix_timestamp),
+            ErrorCode::ResolutionPending
+        );
+
+        // Voting ended without a decision, so nobody showed the proposer wrong
+        let (stake_vault_key, _) = Pubkey::find_program_address(
+            &[b"proposal_stake", proposal.key().as_ref()],
+            ctx.program_id,
+        );
+        require_keys_eq!(accounts[1].key(), stake_vault_key, ErrorCode::ProposalsMissing);
+        let stake_vault = InterfaceAccount::<TokenAccount>::try_from(&accounts[1])?;
+        let proposer_gov_token = InterfaceAccount::<TokenAccount>::try_from(&accounts[2])?;
+        require!(
+            proposer_gov_token.owner == proposal.proposer
+                && proposer_gov_token.mint == config.governance_token_mint,
+            ErrorCode::ProposalsMissing
+        );
+
+        if stake_vault.amount > 0 {
+            let seed_prefix: &[u8] = if proposal.early { b"early_proposal" } else { b"proposal" };
+            let proposal_seeds = &[
+                seed_prefix,
+                proposal.market.as_ref(),
+                proposal.proposer.as_ref(),
+                &[proposal.bump],
+            ];
+            token_interface::transfer_checked(
+                CpiContext::new_with_signer(
+                    ctx.accounts.token_program.to_account_info(),
+                    TransferChecked {
+                        from: stake_vault.to_account_info(),
+                        mint: ctx.accounts.governance_token_mint.to_account_info(),
+                        to: proposer_gov_token.to_account_info(),
+                        authority: proposal.to_account_info(),
+                    },
+                    &[&proposal_seeds[..]],
+                ),
+                stake_vault.amount,
+                ctx.accounts.governance_token_mint.decimals,
+            )?;
+        }
+        stakes_returned = stakes_returned
+            .checked_add(stake_vault.amount)
+            .ok_or(ErrorCode::MathOverflow)?;
+        proposals.push(proposal);
+    }
+
+    // Voting has ended on all of them, so none can pass any more
+    for proposal in proposals.iter_mut() {
+        proposal.status = ProposalStatus::Rejected;
+        proposal.exit(ctx.program_id)?;
+    }
+    market.open_proposals = 0;
+
+    let previous_resolution_time = market.resolution_time;
+    match config.unresolved_fallback {
+        ResolutionFallback::ExtendRound => {
+            market.resolution_time = clock
+                .unix_timestamp
+                .checked_add(market.resolution_grace)
+                .ok_or(ErrorCode::MathOverflow)?;
+        }
+        ResolutionFallback::Cancel => {
+            // Trading stopped at end time, so the price is settled up to then
+            let end = clock.unix_timestamp.min(market.end_time);
+            market.refund_price = Some(ctx.accounts.price_history.settlement_price(end)?);
+            market.status = MarketStatus::Cancelled;
+            market.resolved_at = Some(clock.unix_timestamp);
+        }
+    }
+
+    emit!(ResolutionExpired {
+        market: market.key(),
+        fallback: config.unresolved_fallback,
+        previous_resolution_time,
+        resolution_time: market.resolution_time,
+        proposals_rejected: proposals.len() as u16,
+        stakes_returned,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct ExpireResolution<'info> {
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, Config>,
+
+    #[account(mut)]
+    pub market: Account<'info, Market>,
+
+    #[account(
+        seeds = [b"price_history", market.key().as_ref()],
+        bump = price_history.bump
+    )]
+    pub price_history: Account<'info, PriceHistory>,
+
+    #[account(address = config.governance_token_mint)]
+    pub governance_token_mint: InterfaceAccount<'info, Mint>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+#[event]
+pub struct ResolutionExpired {
+    pub market: Pubkey,
+    pub fallback: ResolutionFallback,
+    pub previous_resolution_time: i64,
+    pub resolution_time: i64,
+    pub proposals_rejected: u16,
+    /// Governance tokens returned to the rejected proposals' proposers
+    pub stakes_returned: u64,
+}
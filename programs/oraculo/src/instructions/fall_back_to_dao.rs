@@ -0,0 +1,113 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{self, Mint, TokenAccount, TokenInterface, TransferChecked};
+use crate::state::{
+    Config, Market, MarketStatus, ResolutionMode, TreasuryMovement, TreasuryMovementKind,
+};
+use crate::errors::ErrorCode;
+use crate::resolution::DesignatedReporter;
+
+pub fn handler(ctx: Context<FallBackToDao>) -> Result<()> {
+    let config = &ctx.accounts.config;
+    let market = &mut ctx.accounts.market;
+
+    // Validations
+    require!(
+        !config.is_paused(Config::PAUSE_PROPOSALS),
+        ErrorCode::ProtocolPaused
+    );
+    require!(!market.halted, ErrorCode::MarketHalted);
+    require!(
+        market.resolution_mode == ResolutionMode::DesignatedReporter,
+        ErrorCode::InvalidResolutionMode
+    );
+
+    let clock = Clock::get()?;
+    DesignatedReporter::check_fallback(market, clock.unix_timestamp)?;
+
+    // The reporter missed the window or was disputed: the bond goes to the treasury
+    let bond = market.reporter_bond;
+    if bond > 0 {
+        let bond_vault = ctx
+            .accounts
+            .reporter_bond_vault
+            .as_ref()
+            .ok_or(ErrorCode::ReporterBondMissing)?;
+
+        let market_id = market.market_id.to_le_bytes();
+        let seeds = &[b"market".as_ref(), &market_id, &[market.bump]];
+        let signer = &[&seeds[..]];
+
+        token_interface::transfer_checked(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                TransferChecked {
+                    from: bond_vault.to_account_info(),
+                    mint: ctx.accounts.governance_token_mint.to_account_info(),
+                    to: ctx.accounts.treasury_gov_vault.to_account_info(),
+                    authority: market.to_account_info(),
+                },
+                signer,
+            ),
+            bond,
+            ctx.accounts.governance_token_mint.decimals,
+        )?;
+
+        emit!(TreasuryMovement {
+            kind: TreasuryMovementKind::SlashedStake,
+            mint: config.governance_token_mint,
+            amount: bond,
+            counterparty: market.resolver.unwrap_or_default(),
+            reference: market.key(),
+            timestamp: clock.unix_timestamp,
+        });
+    }
+
+    market.reporter_bond = 0;
+    market.resolution_mode = ResolutionMode::DaoVote;
+
+    emit!(ReporterFallback {
+        market: market.key(),
+        disputed: market.report_disputed,
+        bond_forfeited: bond,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct FallBackToDao<'info> {
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        mut,
+        constraint = market.status == MarketStatus::Active @ ErrorCode::MarketNotActive
+    )]
+    pub market: Account<'info, Market>,
+
+    #[account(
+        mut,
+        seeds = [b"reporter_bond", market.key().as_ref()],
+        bump
+    )]
+    pub reporter_bond_vault: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        seeds = [b"treasury_vault", config.governance_token_mint.as_ref()],
+        bump
+    )]
+    pub treasury_gov_vault: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(address = config.governance_token_mint)]
+    pub governance_token_mint: InterfaceAccount<'info, Mint>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+#[event]
+pub struct ReporterFallback {
+    pub market: Pubkey,
+    pub disputed: bool,
+    pub bond_forfeited: u64,
+}
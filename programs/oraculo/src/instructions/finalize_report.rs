@@ -0,0 +1,99 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{self, Mint, TokenAccount, TokenInterface, TransferChecked};
+use crate::state::{Config, Market, MarketStatus, ResolutionMode};
+use crate::errors::ErrorCode;
+use crate::resolution::DesignatedReporter;
+
+pub fn handler(ctx: Context<FinalizeReport>) -> Result<()> {
+    let config = &ctx.accounts.config;
+    let market = &mut ctx.accounts.market;
+
+    // Validations
+    require!(
+        !config.is_paused(Config::PAUSE_PROPOSALS),
+        ErrorCode::ProtocolPaused
+    );
+    require!(!market.halted, ErrorCode::MarketHalted);
+    require!(
+        market.resolution_mode == ResolutionMode::DesignatedReporter,
+        ErrorCode::InvalidResolutionMode
+    );
+
+    let clock = Clock::get()?;
+    let outcome = DesignatedReporter::check_finalise_report(market, clock.unix_timestamp)?;
+
+    market.status = MarketStatus::Resolved;
+    market.outcome = Some(outcome);
+    market.resolved_at = Some(clock.unix_timestamp);
+
+    // Unchallenged report: return the reporter's bond
+    let bond = market.reporter_bond;
+    let market_id = market.market_id.to_le_bytes();
+    let seeds = &[b"market".as_ref(), &market_id, &[market.bump]];
+    let signer = &[&seeds[..]];
+
+    token_interface::transfer_checked(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            TransferChecked {
+                from: ctx.accounts.reporter_bond_vault.to_account_info(),
+                mint: ctx.accounts.governance_token_mint.to_account_info(),
+                to: ctx.accounts.reporter_gov_token.to_account_info(),
+                authority: market.to_account_info(),
+            },
+            signer,
+        ),
+        bond,
+        ctx.accounts.governance_token_mint.decimals,
+    )?;
+
+    market.reporter_bond = 0;
+
+    emit!(ReportFinalized {
+        market: market.key(),
+        reporter: ctx.accounts.reporter_gov_token.owner,
+        outcome,
+        bond_returned: bond,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct FinalizeReport<'info> {
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        mut,
+        constraint = market.status == MarketStatus::Active @ ErrorCode::MarketNotActive
+    )]
+    pub market: Account<'info, Market>,
+
+    #[account(
+        mut,
+        seeds = [b"reporter_bond", market.key().as_ref()],
+        bump
+    )]
+    pub reporter_bond_vault: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        constraint = Some(reporter_gov_token.owner) == market.resolver @ ErrorCode::Unauthorized,
+        constraint = reporter_gov_token.mint == config.governance_token_mint
+    )]
+    pub reporter_gov_token: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(address = config.governance_token_mint)]
+    pub governance_token_mint: InterfaceAccount<'info, Mint>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+#[event]
+pub struct ReportFinalized {
+    pub market: Pubkey,
+    pub reporter: Pubkey,
+    pub outcome: bool,
+    pub bond_returned: u64,
+}
@@ -0,0 +1,63 @@
+use anchor_lang::prelude::*;
+use crate::state::{Market, MarketStatus, PriceHistory, PRICE_SCALE_BPS};
+use crate::utils::implied_yes_price;
+
+/// Read-only view of a market's price, returned to callers through return data
+pub fn handler(ctx: Context<GetMarketPrice>, twap_window: Option<i64>) -> Result<MarketPrice> {
+    // No pause or halt check: read-only, and reports `halted` to the caller
+    let market = &ctx.accounts.market;
+
+    let yes_price = implied_yes_price(market.yes_pool, market.no_pool)?;
+    let twap = match twap_window {
+        Some(window) => Some(
+            ctx.accounts
+                .price_history
+                .twap(Clock::get()?.unix_timestamp, window)?,
+        ),
+        None => None,
+    };
+
+    Ok(MarketPrice {
+        market: market.key(),
+        status: market.status.clone(),
+        outcome: market.outcome,
+        halted: market.halted,
+        end_time: market.end_time,
+        yes_price,
+        no_price: PRICE_SCALE_BPS - yes_price,
+        twap,
+        yes_pool: market.yes_pool,
+        no_pool: market.no_pool,
+        total_liquidity: market.total_liquidity,
+        volume: market.volume,
+    })
+}
+
+#[derive(Accounts)]
+pub struct GetMarketPrice<'info> {
+    pub market: Account<'info, Market>,
+
+    #[account(
+        seeds = [b"price_history", market.key().as_ref()],
+        bump = price_history.bump
+    )]
+    pub price_history: Account<'info, PriceHistory>,
+}
+
+/// Market price and pool state. Prices are implied probabilities in basis points.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq)]
+pub struct MarketPrice {
+    pub market: Pubkey,
+    pub status: MarketStatus,
+    pub outcome: Option<bool>,
+    pub halted: bool,
+    pub end_time: i64,
+    pub yes_price: u16,
+    pub no_price: u16,
+    /// Time-weighted average YES price over the requested window
+    pub twap: Option<u16>,
+    pub yes_pool: u64,
+    pub no_pool: u64,
+    pub total_liquidity: u64,
+    pub volume: u64,
+}
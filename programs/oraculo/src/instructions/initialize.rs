@@ -1,6 +1,7 @@
 use anchor_lang::prelude::*;
-use anchor_spl::token::{Token, Mint};
-use crate::state::Config;
+use anchor_spl::token_interface::Mint;
+use crate::state::{Config, ResolutionFallback, Treasury};
+use crate::errors::ErrorCode;
 
 pub fn handler(
     ctx: Context<InitializeConfig>,
@@ -8,8 +9,12 @@ pub fn handler(
     proposal_stake: u64,
     quorum: u64,
     supermajority_percent: u8,
+    guardian: Pubkey,
+    protocol_fee_bps: u16,
 ) -> Result<()> {
-    require!(supermajority_percent >= 51 && supermajority_percent <= 100, crate::errors::ErrorCode::InvalidSupermajority);
+    // No pause check: runs once, before there is a config to pause
+    require!(supermajority_percent >= 51 && supermajority_percent <= 100, ErrorCode::InvalidSupermajority);
+    require!(protocol_fee_bps <= Config::MAX_PROTOCOL_FEE_BPS, ErrorCode::InvalidProtocolFee);
     
     let config = &mut ctx.accounts.config;
     config.authority = ctx.accounts.authority.key();
@@ -21,14 +26,32 @@ pub fn handler(
     config.treasury = ctx.accounts.treasury.key();
     config.total_markets = 0;
     config.total_volume = 0;
+    config.guardian = guardian;
+    config.paused = 0;
+    config.protocol_fee_bps = protocol_fee_bps;
+    config.min_voting_period = Config::MIN_VOTING_PERIOD;
+    config.max_voting_period = Config::MAX_VOTING_PERIOD;
+    config.default_voting_period = Config::DEFAULT_VOTING_PERIOD;
+    config.min_resolution_grace = Config::MIN_RESOLUTION_GRACE;
+    config.max_resolution_grace = Config::MAX_RESOLUTION_GRACE;
+    config.default_resolution_grace = Config::DEFAULT_RESOLUTION_GRACE;
+    config.unresolved_fallback = ResolutionFallback::ExtendRound;
     config.bump = ctx.bumps.config;
 
+    let treasury = &mut ctx.accounts.treasury;
+    treasury.config = config.key();
+    treasury.spend_proposal_count = 0;
+    treasury.bump = ctx.bumps.treasury;
+
     emit!(ConfigInitialized {
         authority: config.authority,
         governance_token_mint: config.governance_token_mint,
         min_liquidity,
         proposal_stake,
         quorum,
+        guardian,
+        treasury: config.treasury,
+        protocol_fee_bps,
     });
 
     Ok(())
@@ -48,10 +71,16 @@ pub struct InitializeConfig<'info> {
     #[account(mut)]
     pub authority: Signer<'info>,
 
-    pub governance_token_mint: Account<'info, Mint>,
+    pub governance_token_mint: InterfaceAccount<'info, Mint>,
 
-    /// CHECK: Treasury account
-    pub treasury: AccountInfo<'info>,
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + Treasury::INIT_SPACE,
+        seeds = [b"treasury"],
+        bump
+    )]
+    pub treasury: Account<'info, Treasury>,
 
     pub system_program: Program<'info, System>,
 }
@@ -63,4 +92,7 @@ pub struct ConfigInitialized {
     pub min_liquidity: u64,
     pub proposal_stake: u64,
     pub quorum: u64,
+    pub guardian: Pubkey,
+    pub treasury: Pubkey,
+    pub protocol_fee_bps: u16,
 }
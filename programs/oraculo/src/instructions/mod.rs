@@ -5,6 +5,49 @@ pub mod propose_resolution;
 pub mod vote_resolution;
 pub mod execute_resolution;
 pub mod claim_winnings;
+pub mod set_guardian;
+pub mod set_pause_flags;
+pub mod set_market_halted;
+pub mod open_treasury_vault;
+pub mod propose_treasury_spend;
+pub mod vote_treasury_spend;
+pub mod treasury_spend;
+pub mod withdraw_treasury_vote;
+pub mod reclaim_treasury_stake;
+pub mod create_gauge;
+pub mod update_gauge;
+pub mod open_reward_position;
+pub mod claim_rewards;
+pub mod register_collateral;
+pub mod update_collateral;
+pub mod resolve_from_oracle;
+pub mod post_reporter_bond;
+pub mod report_outcome;
+pub mod finalize_report;
+pub mod fall_back_to_dao;
+pub mod propose_early_resolution;
+pub mod refund_late_bets;
+pub mod set_resolution_periods;
+pub mod expire_resolution;
+pub mod create_order_book;
+pub mod place_order;
+pub mod cancel_order;
+pub mod consume_fills;
+pub mod settle_funds;
+pub mod place_bet_routed;
+pub mod get_market_price;
+pub mod register_outcome_collateral;
+pub mod cancel_conditional_market;
+pub mod claim_refund;
+pub mod withdraw_creator_liquidity;
+pub mod create_market_template;
+pub mod spawn_from_template;
+pub mod set_template_active;
+pub mod close_template;
+pub mod edit_market;
+pub mod create_category;
+pub mod update_category;
+pub mod reclaim_resolution_stake;
 
 pub use initialize::*;
 pub use create_market::*;
@@ -13,3 +56,78 @@ pub use propose_resolution::*;
 pub use vote_resolution::*;
 pub use execute_resolution::*;
 pub use claim_winnings::*;
+pub use set_guardian::*;
+pub use set_pause_flags::*;
+pub use set_market_halted::*;
+pub use open_treasury_vault::*;
+pub use propose_treasury_spend::*;
+pub use vote_treasury_spend::*;
+pub use treasury_spend::*;
+pub use withdraw_treasury_vote::*;
+pub use reclaim_treasury_stake::*;
+pub use create_gauge::*;
+pub use update_gauge::*;
+pub use open_reward_position::*;
+pub use claim_rewards::*;
+pub use register_collateral::*;
+pub use update_collateral::*;
+pub use resolve_from_oracle::*;
+pub use post_reporter_bond::*;
+pub use report_outcome::*;
+pub use finalize_report::*;
+pub use fall_back_to_dao::*;
+pub use propose_early_resolution::*;
+pub use refund_late_bets::*;
+pub use set_resolution_periods::*;
+pub use expire_resolution::*;
+pub use create_order_book::*;
+pub use place_order::*;
+pub use cancel_order::*;
+pub use consume_fills::*;
+pub use settle_funds::*;
+pub use place_bet_routed::*;
+pub use get_market_price::*;
+pub use register_outcome_collateral::*;
+pub use cancel_conditional_market::*;
+pub use claim_refund::*;
+pub use withdraw_creator_liquidity::*;
+pub use create_market_template::*;
+pub use spawn_from_template::*;
+pub use set_template_active::*;
+pub use close_template::*;
+pub use edit_market::*;
+pub use create_category::*;
+pub use update_category::*;
+pub use reclaim_resolution_stake::*;
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    /// Every handler checks the pause flags and, if it touches a market, the
+    /// market's halt, or says why it does not
+    #[test]
+    fn test_every_handler_checks_pause_and_halt() {
+        let dir = concat!(env!("CARGO_MANIFEST_DIR"), "/src/instructions");
+        for entry in fs::read_dir(dir).unwrap() {
+            let path = entry.unwrap().path();
+            if path.file_name().unwrap() == "mod.rs" {
+                continue;
+            }
+            let source = fs::read_to_string(&path).unwrap();
+            assert!(
+                source.contains("is_paused(") || source.contains("No pause check")
+                    || source.contains("No pause or halt check"),
+                "{} has no pause check",
+                path.display()
+            );
+            if source.contains("Account<'info, Market>") {
+                assert!(
+                    source.contains(".halted") || source.contains("halt check"),
+                    "{} has no halt check",
+                    path.display()
+                );
+            }
+        }
+    }
+}
@@ -0,0 +1,85 @@
+use anchor_lang::prelude::*;
+use crate::state::{Config, Gauge, Market, RewardPosition};
+use crate::errors::ErrorCode;
+
+pub fn handler(ctx: Context<OpenRewardPosition>) -> Result<()> {
+    let market = &ctx.accounts.market;
+    let gauge = &mut ctx.accounts.gauge;
+    let position = &mut ctx.accounts.reward_position;
+
+    // Validations
+    require!(
+        !ctx.accounts.config.is_paused(Config::PAUSE_TRADING),
+        ErrorCode::ProtocolPaused
+    );
+    require!(!market.halted, ErrorCode::MarketHalted);
+
+    let clock = Clock::get()?;
+    gauge.update(market, clock.unix_timestamp)?;
+
+    position.gauge = gauge.key();
+    position.owner = ctx.accounts.owner.key();
+    position.volume = 0;
+    position.pending_rewards = 0;
+    position.total_claimed = 0;
+    position.bump = ctx.bumps.reward_position;
+
+    // The creator's locked liquidity is the market's LP share
+    position.lp_shares = if position.owner == market.creator {
+        market.creator_liquidity
+    } else {
+        0
+    };
+    gauge.total_lp_shares = gauge.total_lp_shares
+        .checked_add(position.lp_shares)
+        .unwrap();
+    position.checkpoint(gauge)?;
+
+    emit!(RewardPositionOpened {
+        gauge: gauge.key(),
+        owner: position.owner,
+        lp_shares: position.lp_shares,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct OpenRewardPosition<'info> {
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, Config>,
+
+    pub market: Account<'info, Market>,
+
+    #[account(
+        mut,
+        seeds = [b"gauge", market.key().as_ref()],
+        bump = gauge.bump
+    )]
+    pub gauge: Account<'info, Gauge>,
+
+    #[account(
+        init,
+        payer = owner,
+        space = 8 + RewardPosition::INIT_SPACE,
+        seeds = [
+            b"reward_position",
+            gauge.key().as_ref(),
+            owner.key().as_ref()
+        ],
+        bump
+    )]
+    pub reward_position: Account<'info, RewardPosition>,
+
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[event]
+pub struct RewardPositionOpened {
+    pub gauge: Pubkey,
+    pub owner: Pubkey,
+    pub lp_shares: u64,
+}
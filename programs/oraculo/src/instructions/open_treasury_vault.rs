@@ -0,0 +1,47 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+use crate::state::Treasury;
+
+pub fn handler(ctx: Context<OpenTreasuryVault>) -> Result<()> {
+    // No pause check: opening an empty fee vault moves no funds
+    emit!(TreasuryVaultOpened {
+        treasury: ctx.accounts.treasury.key(),
+        mint: ctx.accounts.mint.key(),
+        vault: ctx.accounts.treasury_vault.key(),
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct OpenTreasuryVault<'info> {
+    #[account(seeds = [b"treasury"], bump = treasury.bump)]
+    pub treasury: Account<'info, Treasury>,
+
+    #[account(
+        init,
+        payer = payer,
+        token::mint = mint,
+        token::authority = treasury,
+        token::token_program = token_program,
+        seeds = [b"treasury_vault", mint.key().as_ref()],
+        bump
+    )]
+    pub treasury_vault: InterfaceAccount<'info, TokenAccount>,
+
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+#[event]
+pub struct TreasuryVaultOpened {
+    pub treasury: Pubkey,
+    pub mint: Pubkey,
+    pub vault: Pubkey,
+}
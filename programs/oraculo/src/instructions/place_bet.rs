@@ -1,20 +1,33 @@
 use anchor_lang::prelude::*;
 use anchor_spl::{
-    token::{self, Token, TokenAccount, Mint, MintTo, Transfer},
+    token_interface::{self, Mint, MintTo, TokenAccount, TokenInterface, TransferChecked},
     associated_token::AssociatedToken,
 };
-use crate::state::{Market, MarketStatus};
+use crate::state::{
+    CollateralConfig, Config, Gauge, Market, MarketStatus, PriceHistory, RewardPosition, TreasuryMovement,
+    TreasuryMovementKind,
+};
 use crate::errors::ErrorCode;
-use crate::utils::calculate_bonding_curve;
+use crate::utils::{
+    calculate_bonding_curve, calculate_fee, collateral_to_outcome, implied_yes_price, inverse_transfer_fee,
+    transfer_fee,
+};
 
 pub fn handler(
     ctx: Context<PlaceBet>,
     amount: u64,
     bet_on_yes: bool,
+    max_cost: u64,
 ) -> Result<()> {
+    let config = &mut ctx.accounts.config;
     let market = &mut ctx.accounts.market;
     
     // Validations
+    require!(
+        !config.is_paused(Config::PAUSE_TRADING),
+        ErrorCode::ProtocolPaused
+    );
+    require!(!market.halted, ErrorCode::MarketHalted);
     require!(
         market.status == MarketStatus::Active,
         ErrorCode::MarketNotActive
@@ -26,49 +39,87 @@ pub fn handler(
         ErrorCode::MarketEnded
     );
     
-    require!(amount >= 1_000_000, ErrorCode::BetTooSmall); // Min 1 USDC
+    require!(amount >= ctx.accounts.collateral.min_bet, ErrorCode::BetTooSmall);
 
     // Calculate bonding curve pricing
-    let (cost, tokens_out) = calculate_bonding_curve(
+    let (cost, shares) = calculate_bonding_curve(
         market.yes_pool,
         market.no_pool,
         amount,
         bet_on_yes,
     )?;
+    let fee = calculate_fee(cost, market.trading_fee_bps(config))?;
+
+    // Transfer-fee collateral is grossed up so the vault nets the full cost
+    // the shares and pools are sized from
+    let collateral_mint = ctx.accounts.collateral_mint.to_account_info();
+    let paid = cost
+        .checked_add(inverse_transfer_fee(&collateral_mint, cost)?)
+        .ok_or(ErrorCode::MathOverflow)?;
+    require!(
+        paid.checked_add(fee).ok_or(ErrorCode::MathOverflow)? <= max_cost,
+        ErrorCode::SlippageExceeded
+    );
+
+    // Pools are in collateral units; outcome mints have their own decimals
+    let tokens_out = collateral_to_outcome(shares, ctx.accounts.collateral_mint.decimals)?;
 
-    // Transfer USDC from user to vault
-    token::transfer(
+    // Transfer collateral from user to vault
+    token_interface::transfer_checked(
         CpiContext::new(
             ctx.accounts.token_program.to_account_info(),
-            Transfer {
-                from: ctx.accounts.user_usdc.to_account_info(),
+            TransferChecked {
+                from: ctx.accounts.user_collateral.to_account_info(),
+                mint: ctx.accounts.collateral_mint.to_account_info(),
                 to: ctx.accounts.liquidity_vault.to_account_info(),
                 authority: ctx.accounts.user.to_account_info(),
             },
         ),
-        cost,
+        paid,
+        ctx.accounts.collateral_mint.decimals,
     )?;
 
-    // Mint conditional tokens to user
-    let outcome_mint = if bet_on_yes {
-        &ctx.accounts.yes_token_mint
-    } else {
-        &ctx.accounts.no_token_mint
-    };
+    // Trading fee goes to the treasury vault for this collateral
+    if fee > 0 {
+        token_interface::transfer_checked(
+            CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                TransferChecked {
+                    from: ctx.accounts.user_collateral.to_account_info(),
+                    mint: ctx.accounts.collateral_mint.to_account_info(),
+                    to: ctx.accounts.treasury_vault.to_account_info(),
+                    authority: ctx.accounts.user.to_account_info(),
+                },
+            ),
+            fee,
+            ctx.accounts.collateral_mint.decimals,
+        )?;
+
+        emit!(TreasuryMovement {
+            kind: TreasuryMovementKind::ProtocolFee,
+            mint: ctx.accounts.treasury_vault.mint,
+            amount: fee
+                .checked_sub(transfer_fee(&collateral_mint, fee)?)
+                .ok_or(ErrorCode::MathUnderflow)?,
+            counterparty: ctx.accounts.user.key(),
+            reference: market.key(),
+            timestamp: clock.unix_timestamp,
+        });
+    }
 
+    // Mint conditional tokens to user
     let seeds = &[
-        b"market",
-        market.creator.as_ref(),
-        &market.created_at.to_le_bytes(),
+        b"market".as_ref(),
+        &market.market_id.to_le_bytes(),
         &[market.bump],
     ];
     let signer = &[&seeds[..]];
 
-    token::mint_to(
+    token_interface::mint_to(
         CpiContext::new_with_signer(
             ctx.accounts.token_program.to_account_info(),
             MintTo {
-                mint: outcome_mint.to_account_info(),
+                mint: ctx.accounts.outcome_token_mint.to_account_info(),
                 to: ctx.accounts.user_outcome_token.to_account_info(),
                 authority: market.to_account_info(),
             },
@@ -85,6 +136,36 @@ pub fn handler(
     }
     market.total_liquidity = market.total_liquidity.checked_add(cost).unwrap();
     market.volume = market.volume.checked_add(cost).unwrap();
+    market.has_trades = true;
+    config.total_volume = config.total_volume.checked_add(cost).unwrap();
+
+    // Count the mint so the bet can be rolled back after an early resolution
+    let price_history = &mut ctx.accounts.price_history;
+    price_history.record_mint(
+        clock.unix_timestamp,
+        bet_on_yes,
+        tokens_out,
+        collateral_to_outcome(cost, ctx.accounts.collateral_mint.decimals)?,
+    )?;
+    price_history.record(clock.unix_timestamp, implied_yes_price(market.yes_pool, market.no_pool)?)?;
+
+    // Credit trading volume to the market's liquidity mining gauge
+    if let (Some(gauge), Some(position)) = (
+        ctx.accounts.gauge.as_mut(),
+        ctx.accounts.reward_position.as_mut(),
+    ) {
+        require!(gauge.market == market.key(), ErrorCode::InvalidRewardPosition);
+        require!(
+            position.gauge == gauge.key() && position.owner == ctx.accounts.user.key(),
+            ErrorCode::InvalidRewardPosition
+        );
+
+        gauge.update(market, clock.unix_timestamp)?;
+        position.settle(gauge)?;
+        position.volume = position.volume.checked_add(cost).unwrap();
+        gauge.total_volume = gauge.total_volume.checked_add(cost).unwrap();
+        position.checkpoint(gauge)?;
+    }
 
     emit!(BetPlaced {
         market: market.key(),
@@ -92,62 +173,110 @@ pub fn handler(
         bet_on_yes,
         amount: tokens_out,
         cost,
+        fee,
         yes_pool: market.yes_pool,
         no_pool: market.no_pool,
+        fills: BetFills {
+            amm_shares: amount,
+            amm_cost: cost,
+            ..Default::default()
+        },
     });
 
     Ok(())
 }
 
 #[derive(Accounts)]
+#[instruction(amount: u64, bet_on_yes: bool)]
 pub struct PlaceBet<'info> {
+    #[account(mut, seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, Config>,
+
     #[account(mut)]
     pub market: Account<'info, Market>,
 
+    #[account(
+        mut,
+        seeds = [b"price_history", market.key().as_ref()],
+        bump = price_history.bump
+    )]
+    pub price_history: Account<'info, PriceHistory>,
+
     #[account(mut)]
     pub user: Signer<'info>,
 
     #[account(
         mut,
-        constraint = user_usdc.owner == user.key()
+        constraint = user_collateral.owner == user.key(),
+        constraint = user_collateral.mint == market.collateral_mint @ ErrorCode::InvalidCollateral
     )]
-    pub user_usdc: Account<'info, TokenAccount>,
+    pub user_collateral: InterfaceAccount<'info, TokenAccount>,
 
     #[account(
         mut,
         seeds = [b"vault", market.key().as_ref()],
         bump
     )]
-    pub liquidity_vault: Account<'info, TokenAccount>,
+    pub liquidity_vault: InterfaceAccount<'info, TokenAccount>,
 
     #[account(
         mut,
-        seeds = [b"yes_mint", market.key().as_ref()],
+        seeds = [b"treasury_vault", market.collateral_mint.as_ref()],
         bump
     )]
-    pub yes_token_mint: Account<'info, Mint>,
+    pub treasury_vault: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        seeds = [b"collateral", market.collateral_mint.as_ref()],
+        bump = collateral.bump
+    )]
+    pub collateral: Account<'info, CollateralConfig>,
+
+    #[account(address = market.collateral_mint @ ErrorCode::InvalidCollateral)]
+    pub collateral_mint: InterfaceAccount<'info, Mint>,
 
+    /// The YES or NO mint, whichever `bet_on_yes` picks
     #[account(
         mut,
-        seeds = [b"no_mint", market.key().as_ref()],
-        bump
+        address = if bet_on_yes { market.yes_mint } else { market.no_mint }
+            @ ErrorCode::InvalidOutcomeTokenAccount
     )]
-    pub no_token_mint: Account<'info, Mint>,
+    pub outcome_token_mint: InterfaceAccount<'info, Mint>,
 
     #[account(
         init_if_needed,
         payer = user,
-        associated_token::mint = yes_token_mint,
-        associated_token::authority = user
+        associated_token::mint = outcome_token_mint,
+        associated_token::authority = user,
+        associated_token::token_program = token_program
     )]
-    pub user_outcome_token: Account<'info, TokenAccount>,
+    pub user_outcome_token: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(mut)]
+    pub gauge: Option<Account<'info, Gauge>>,
 
-    pub token_program: Program<'info, Token>,
+    #[account(mut)]
+    pub reward_position: Option<Account<'info, RewardPosition>>,
+
+    pub token_program: Interface<'info, TokenInterface>,
     pub associated_token_program: Program<'info, AssociatedToken>,
     pub system_program: Program<'info, System>,
     pub rent: Sysvar<'info, Rent>,
 }
 
+/// Where a bet's shares came from
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default, PartialEq, Eq, Debug)]
+pub struct BetFills {
+    pub amm_shares: u64,
+    /// AMM cost before the protocol fee
+    pub amm_cost: u64,
+    /// Order book lots bought from resting sellers
+    pub book_lots: u64,
+    /// Order book lots minted as complete sets against resting buyers of the other outcome
+    pub minted_lots: u64,
+    pub book_cost: u64,
+}
+
 #[event]
 pub struct BetPlaced {
     pub market: Pubkey,
@@ -155,6 +284,8 @@ pub struct BetPlaced {
     pub bet_on_yes: bool,
     pub amount: u64,
     pub cost: u64,
+    pub fee: u64,
     pub yes_pool: u64,
     pub no_pool: u64,
+    pub fills: BetFills,
 }
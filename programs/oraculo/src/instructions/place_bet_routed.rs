@@ -0,0 +1,412 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{self, Mint, MintTo, TokenAccount, TokenInterface, TransferChecked};
+use crate::state::{
+    CollateralConfig, Config, Gauge, Market, MarketStatus, OrderBook, OrderKind, PriceHistory, RewardPosition,
+    TreasuryMovement, TreasuryMovementKind, PRICE_SCALE_BPS,
+};
+use crate::errors::ErrorCode;
+use crate::instructions::place_bet::{BetFills, BetPlaced};
+use crate::utils::{
+    calculate_bonding_curve, calculate_fee, collateral_to_outcome, implied_yes_price, lot_size, route_order,
+    transfer_fee,
+};
+
+pub fn handler(
+    ctx: Context<PlaceBetRouted>,
+    amount: u64,
+    bet_on_yes: bool,
+    max_cost: u64,
+) -> Result<()> {
+    let config = &mut ctx.accounts.config;
+    let market = &mut ctx.accounts.market;
+    let order_book = &mut ctx.accounts.order_book;
+
+    // Validations
+    require!(
+        !config.is_paused(Config::PAUSE_TRADING),
+        ErrorCode::ProtocolPaused
+    );
+    require!(!market.halted, ErrorCode::MarketHalted);
+    require!(
+        market.status == MarketStatus::Active,
+        ErrorCode::MarketNotActive
+    );
+
+    let clock = Clock::get()?;
+    require!(
+        clock.unix_timestamp < market.end_time,
+        ErrorCode::MarketEnded
+    );
+
+    require!(amount >= ctx.accounts.collateral.min_bet, ErrorCode::BetTooSmall);
+
+    let (kind, outcome_mint, book_vault) = if bet_on_yes {
+        (OrderKind::BuyYes, &ctx.accounts.yes_token_mint, &ctx.accounts.book_yes_vault)
+    } else {
+        (OrderKind::BuyNo, &ctx.accounts.no_token_mint, &ctx.accounts.book_no_vault)
+    };
+    require!(
+        ctx.accounts.user_outcome_token.mint == outcome_mint.key(),
+        ErrorCode::InvalidOutcomeTokenAccount
+    );
+
+    // Cheapest split between resting orders and the curve
+    let levels = order_book.levels(kind)?;
+    let (yes_pool, no_pool) = (market.yes_pool, market.no_pool);
+    let fee_bps = market.trading_fee_bps(config);
+    let route = route_order(
+        &levels.iter().map(|&(cost, lots, _)| (cost, lots)).collect::<Vec<_>>(),
+        amount,
+        |shares| {
+            let (cost, _) = calculate_bonding_curve(yes_pool, no_pool, shares, bet_on_yes)?;
+            let fee = calculate_fee(cost, fee_bps)?;
+            Ok(cost.checked_add(fee).ok_or(ErrorCode::MathOverflow)?)
+        },
+    )?;
+    require!(route.total_cost()? <= max_cost, ErrorCode::SlippageExceeded);
+
+    let collateral_decimals = ctx.accounts.collateral_mint.decimals;
+    let lot = lot_size(collateral_decimals)?;
+
+    let market_id = market.market_id.to_le_bytes();
+    let bump = [market.bump];
+    let seeds = &[b"market".as_ref(), &market_id, &bump];
+    let signer = &[&seeds[..]];
+
+    let mut fills = BetFills::default();
+    let mut tokens_out: u64 = 0;
+
+    // Take the routed lots from the book, limited at the last order reached
+    if route.book_lots > 0 {
+        let mut reached = 0u64;
+        let limit = levels
+            .iter()
+            .find(|&&(_, lots, _)| {
+                reached += lots;
+                reached >= route.book_lots
+            })
+            .map(|&(_, _, price)| price)
+            .ok_or(ErrorCode::InvalidOrder)?;
+
+        let result = order_book.take(kind, limit, route.book_lots, |_| Ok(None))?;
+        require!(result.remaining_lots == 0, ErrorCode::InvalidOrder);
+        let book_cost = kind
+            .escrow(limit, route.book_lots)?
+            .collateral
+            .checked_sub(result.taker_credit.collateral)
+            .ok_or(ErrorCode::MathUnderflow)?;
+
+        token_interface::transfer_checked(
+            CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                TransferChecked {
+                    from: ctx.accounts.user_collateral.to_account_info(),
+                    mint: ctx.accounts.collateral_mint.to_account_info(),
+                    to: ctx.accounts.book_collateral_vault.to_account_info(),
+                    authority: ctx.accounts.user.to_account_info(),
+                },
+            ),
+            book_cost,
+            collateral_decimals,
+        )?;
+
+        // Resting buyers of the other outcome fill by minting complete sets
+        if result.sets_minted > 0 {
+            let collateral = result
+                .sets_minted
+                .checked_mul(PRICE_SCALE_BPS as u64)
+                .ok_or(ErrorCode::MathOverflow)?;
+
+            token_interface::transfer_checked(
+                CpiContext::new_with_signer(
+                    ctx.accounts.token_program.to_account_info(),
+                    TransferChecked {
+                        from: ctx.accounts.book_collateral_vault.to_account_info(),
+                        mint: ctx.accounts.collateral_mint.to_account_info(),
+                        to: ctx.accounts.liquidity_vault.to_account_info(),
+                        authority: market.to_account_info(),
+                    },
+                    signer,
+                ),
+                collateral,
+                collateral_decimals,
+            )?;
+            for (mint, vault) in [
+                (&ctx.accounts.yes_token_mint, &ctx.accounts.book_yes_vault),
+                (&ctx.accounts.no_token_mint, &ctx.accounts.book_no_vault),
+            ] {
+                token_interface::mint_to(
+                    CpiContext::new_with_signer(
+                        ctx.accounts.token_program.to_account_info(),
+                        MintTo {
+                            mint: mint.to_account_info(),
+                            to: vault.to_account_info(),
+                            authority: market.to_account_info(),
+                        },
+                        signer,
+                    ),
+                    result.sets_minted.checked_mul(lot).ok_or(ErrorCode::MathOverflow)?,
+                )?;
+            }
+            market.total_liquidity = market.total_liquidity.checked_add(collateral).unwrap();
+            ctx.accounts.price_history.record_set_mint(
+                clock.unix_timestamp,
+                result.sets_minted.checked_mul(lot).ok_or(ErrorCode::MathOverflow)?,
+                collateral_to_outcome(result.sets_yes_cost, collateral_decimals)?,
+            )?;
+        }
+
+        // Deliver the bought tokens straight to the bettor
+        let book_tokens = route.book_lots.checked_mul(lot).ok_or(ErrorCode::MathOverflow)?;
+        token_interface::transfer_checked(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                TransferChecked {
+                    from: book_vault.to_account_info(),
+                    mint: outcome_mint.to_account_info(),
+                    to: ctx.accounts.user_outcome_token.to_account_info(),
+                    authority: market.to_account_info(),
+                },
+                signer,
+            ),
+            book_tokens,
+            outcome_mint.decimals,
+        )?;
+
+        tokens_out = book_tokens;
+        fills.book_lots = route.book_lots - result.sets_minted;
+        fills.minted_lots = result.sets_minted;
+        fills.book_cost = book_cost;
+    }
+
+    // Buy the rest from the curve as `place_bet` does
+    let mut fee = 0;
+    if route.amm_shares > 0 {
+        let (cost, shares) = calculate_bonding_curve(market.yes_pool, market.no_pool, route.amm_shares, bet_on_yes)?;
+        let amm_tokens = collateral_to_outcome(shares, collateral_decimals)?;
+
+        token_interface::transfer_checked(
+            CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                TransferChecked {
+                    from: ctx.accounts.user_collateral.to_account_info(),
+                    mint: ctx.accounts.collateral_mint.to_account_info(),
+                    to: ctx.accounts.liquidity_vault.to_account_info(),
+                    authority: ctx.accounts.user.to_account_info(),
+                },
+            ),
+            cost,
+            collateral_decimals,
+        )?;
+
+        let collateral_mint = ctx.accounts.collateral_mint.to_account_info();
+        let deposited = cost
+            .checked_sub(transfer_fee(&collateral_mint, cost)?)
+            .ok_or(ErrorCode::MathUnderflow)?;
+
+        fee = calculate_fee(cost, fee_bps)?;
+        if fee > 0 {
+            token_interface::transfer_checked(
+                CpiContext::new(
+                    ctx.accounts.token_program.to_account_info(),
+                    TransferChecked {
+                        from: ctx.accounts.user_collateral.to_account_info(),
+                        mint: ctx.accounts.collateral_mint.to_account_info(),
+                        to: ctx.accounts.treasury_vault.to_account_info(),
+                        authority: ctx.accounts.user.to_account_info(),
+                    },
+                ),
+                fee,
+                collateral_decimals,
+            )?;
+
+            emit!(TreasuryMovement {
+                kind: TreasuryMovementKind::ProtocolFee,
+                mint: ctx.accounts.treasury_vault.mint,
+                amount: fee
+                    .checked_sub(transfer_fee(&collateral_mint, fee)?)
+                    .ok_or(ErrorCode::MathUnderflow)?,
+                counterparty: ctx.accounts.user.key(),
+                reference: market.key(),
+                timestamp: clock.unix_timestamp,
+            });
+        }
+
+        token_interface::mint_to(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                MintTo {
+                    mint: outcome_mint.to_account_info(),
+                    to: ctx.accounts.user_outcome_token.to_account_info(),
+                    authority: market.to_account_info(),
+                },
+                signer,
+            ),
+            amm_tokens,
+        )?;
+
+        if bet_on_yes {
+            market.yes_pool = market.yes_pool.checked_add(route.amm_shares).unwrap();
+        } else {
+            market.no_pool = market.no_pool.checked_add(route.amm_shares).unwrap();
+        }
+        market.total_liquidity = market.total_liquidity.checked_add(deposited).unwrap();
+
+        ctx.accounts.price_history.record_mint(
+            clock.unix_timestamp,
+            bet_on_yes,
+            amm_tokens,
+            collateral_to_outcome(deposited, collateral_decimals)?,
+        )?;
+
+        tokens_out = tokens_out.checked_add(amm_tokens).unwrap();
+        fills.amm_shares = route.amm_shares;
+        fills.amm_cost = cost;
+    }
+
+    let cost = fills.book_cost.checked_add(fills.amm_cost).unwrap();
+    market.volume = market.volume.checked_add(cost).unwrap();
+    market.has_trades = true;
+    config.total_volume = config.total_volume.checked_add(cost).unwrap();
+    ctx.accounts
+        .price_history
+        .record(clock.unix_timestamp, implied_yes_price(market.yes_pool, market.no_pool)?)?;
+
+    // Credit trading volume to the market's liquidity mining gauge
+    if let (Some(gauge), Some(position)) = (
+        ctx.accounts.gauge.as_mut(),
+        ctx.accounts.reward_position.as_mut(),
+    ) {
+        require!(gauge.market == market.key(), ErrorCode::InvalidRewardPosition);
+        require!(
+            position.gauge == gauge.key() && position.owner == ctx.accounts.user.key(),
+            ErrorCode::InvalidRewardPosition
+        );
+
+        gauge.update(market, clock.unix_timestamp)?;
+        position.settle(gauge)?;
+        position.volume = position.volume.checked_add(cost).unwrap();
+        gauge.total_volume = gauge.total_volume.checked_add(cost).unwrap();
+        position.checkpoint(gauge)?;
+    }
+
+    emit!(BetPlaced {
+        market: market.key(),
+        user: ctx.accounts.user.key(),
+        bet_on_yes,
+        amount: tokens_out,
+        cost,
+        fee,
+        yes_pool: market.yes_pool,
+        no_pool: market.no_pool,
+        fills,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct PlaceBetRouted<'info> {
+    #[account(mut, seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, Config>,
+
+    #[account(mut)]
+    pub market: Account<'info, Market>,
+
+    #[account(
+        mut,
+        seeds = [b"order_book", market.key().as_ref()],
+        bump = order_book.bump
+    )]
+    pub order_book: Account<'info, OrderBook>,
+
+    #[account(
+        mut,
+        seeds = [b"price_history", market.key().as_ref()],
+        bump = price_history.bump
+    )]
+    pub price_history: Account<'info, PriceHistory>,
+
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    #[account(
+        mut,
+        constraint = user_collateral.owner == user.key(),
+        constraint = user_collateral.mint == market.collateral_mint @ ErrorCode::InvalidCollateral
+    )]
+    pub user_collateral: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        constraint = user_outcome_token.owner == user.key() @ ErrorCode::InvalidOutcomeTokenAccount
+    )]
+    pub user_outcome_token: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        seeds = [b"vault", market.key().as_ref()],
+        bump
+    )]
+    pub liquidity_vault: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        seeds = [b"treasury_vault", market.collateral_mint.as_ref()],
+        bump
+    )]
+    pub treasury_vault: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        seeds = [b"book_collateral", market.key().as_ref()],
+        bump
+    )]
+    pub book_collateral_vault: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        seeds = [b"book_yes", market.key().as_ref()],
+        bump
+    )]
+    pub book_yes_vault: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        seeds = [b"book_no", market.key().as_ref()],
+        bump
+    )]
+    pub book_no_vault: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        seeds = [b"collateral", market.collateral_mint.as_ref()],
+        bump = collateral.bump
+    )]
+    pub collateral: Account<'info, CollateralConfig>,
+
+    #[account(address = market.collateral_mint @ ErrorCode::InvalidCollateral)]
+    pub collateral_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        seeds = [b"yes_mint", market.key().as_ref()],
+        bump
+    )]
+    pub yes_token_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        seeds = [b"no_mint", market.key().as_ref()],
+        bump
+    )]
+    pub no_token_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(mut)]
+    pub gauge: Option<Account<'info, Gauge>>,
+
+    #[account(mut)]
+    pub reward_position: Option<Account<'info, RewardPosition>>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
@@ -0,0 +1,476 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{self, Burn, Mint, MintTo, TokenAccount, TokenInterface, TransferChecked};
+use crate::state::{
+    Config, Market, MarketStatus, OpenOrders, Order, OrderBook, OrderKind, OrderType, PriceHistory, TakeResult,
+    TreasuryMovement, TreasuryMovementKind, PRICE_SCALE_BPS,
+};
+use crate::errors::ErrorCode;
+use crate::utils::{calculate_bonding_curve, calculate_fee, collateral_to_outcome, implied_yes_price, lot_size};
+
+pub fn handler(
+    ctx: Context<PlaceOrder>,
+    kind: OrderKind,
+    order_type: OrderType,
+    price: u16,
+    lots: u64,
+) -> Result<()> {
+    let config = &mut ctx.accounts.config;
+    let market = &mut ctx.accounts.market;
+    let order_book = &mut ctx.accounts.order_book;
+
+    // Validations
+    require!(
+        !config.is_paused(Config::PAUSE_TRADING),
+        ErrorCode::ProtocolPaused
+    );
+    require!(!market.halted, ErrorCode::MarketHalted);
+    require!(
+        market.status == MarketStatus::Active,
+        ErrorCode::MarketNotActive
+    );
+
+    let clock = Clock::get()?;
+    require!(
+        clock.unix_timestamp < market.end_time,
+        ErrorCode::MarketEnded
+    );
+    require!(
+        lots > 0 && price > 0 && price < PRICE_SCALE_BPS,
+        ErrorCode::InvalidOrder
+    );
+    require!(
+        order_type == OrderType::ImmediateOrCancel || lots >= OrderBook::MIN_ORDER_LOTS,
+        ErrorCode::OrderTooSmall
+    );
+    // Even a resting order commits to the market as worded
+    market.has_trades = true;
+
+    let yes_price = kind.yes_price(price);
+    let escrow = kind.escrow(yes_price, lots)?;
+    let collateral_decimals = ctx.accounts.collateral_mint.decimals;
+    let lot = lot_size(collateral_decimals)?;
+
+    // Lock the order's collateral or outcome tokens in the book's vaults
+    if escrow.collateral > 0 {
+        token_interface::transfer_checked(
+            CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                TransferChecked {
+                    from: ctx.accounts.owner_collateral.to_account_info(),
+                    mint: ctx.accounts.collateral_mint.to_account_info(),
+                    to: ctx.accounts.book_collateral_vault.to_account_info(),
+                    authority: ctx.accounts.owner.to_account_info(),
+                },
+            ),
+            escrow.collateral,
+            collateral_decimals,
+        )?;
+    }
+    for (escrow_lots, mint, from, to) in [
+        (escrow.yes, &ctx.accounts.yes_token_mint, &ctx.accounts.owner_yes_token, &ctx.accounts.book_yes_vault),
+        (escrow.no, &ctx.accounts.no_token_mint, &ctx.accounts.owner_no_token, &ctx.accounts.book_no_vault),
+    ] {
+        if escrow_lots == 0 {
+            continue;
+        }
+        let from = from.as_ref().ok_or(ErrorCode::InvalidOutcomeTokenAccount)?;
+        require!(
+            from.mint == mint.key() && from.owner == ctx.accounts.owner.key(),
+            ErrorCode::InvalidOutcomeTokenAccount
+        );
+
+        token_interface::transfer_checked(
+            CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                TransferChecked {
+                    from: from.to_account_info(),
+                    mint: mint.to_account_info(),
+                    to: to.to_account_info(),
+                    authority: ctx.accounts.owner.to_account_info(),
+                },
+            ),
+            escrow_lots.checked_mul(lot).ok_or(ErrorCode::MathOverflow)?,
+            mint.decimals,
+        )?;
+    }
+
+    // Match against the book, routing buys to the AMM when it is cheaper
+    let fee_bps = market.trading_fee_bps(config);
+    let result = if order_type == OrderType::PostOnly {
+        require!(!order_book.crosses(kind, yes_price), ErrorCode::OrderWouldCross);
+        TakeResult {
+            remaining_lots: lots,
+            ..Default::default()
+        }
+    } else {
+        let (yes_pool, no_pool) = (market.yes_pool, market.no_pool);
+        let buy_yes = kind == OrderKind::BuyYes;
+        let can_use_amm = matches!(kind, OrderKind::BuyYes | OrderKind::BuyNo);
+        order_book.take(kind, yes_price, lots, |amm_lots| {
+            if !can_use_amm {
+                return Ok(None);
+            }
+            let shares = amm_lots
+                .checked_mul(PRICE_SCALE_BPS as u64)
+                .ok_or(ErrorCode::MathOverflow)?;
+            let (cost, _) = calculate_bonding_curve(yes_pool, no_pool, shares, buy_yes)?;
+            let fee = calculate_fee(cost, fee_bps)?;
+            Ok(Some(cost.checked_add(fee).ok_or(ErrorCode::MathOverflow)?))
+        })?
+    };
+    let mut taker_credit = result.taker_credit;
+
+    let market_id = market.market_id.to_le_bytes();
+    let bump = [market.bump];
+    let seeds = &[b"market".as_ref(), &market_id, &bump];
+    let signer = &[&seeds[..]];
+
+    // Complete sets matched across YES and NO are minted or merged against
+    // the liquidity vault at one collateral unit per set
+    let set_collateral = |sets: u64| -> Result<u64> {
+        Ok(sets.checked_mul(PRICE_SCALE_BPS as u64).ok_or(ErrorCode::MathOverflow)?)
+    };
+    if result.sets_minted > result.sets_merged {
+        let sets = result.sets_minted - result.sets_merged;
+        let collateral = set_collateral(sets)?;
+
+        token_interface::transfer_checked(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                TransferChecked {
+                    from: ctx.accounts.book_collateral_vault.to_account_info(),
+                    mint: ctx.accounts.collateral_mint.to_account_info(),
+                    to: ctx.accounts.liquidity_vault.to_account_info(),
+                    authority: market.to_account_info(),
+                },
+                signer,
+            ),
+            collateral,
+            collateral_decimals,
+        )?;
+        for (mint, vault) in [
+            (&ctx.accounts.yes_token_mint, &ctx.accounts.book_yes_vault),
+            (&ctx.accounts.no_token_mint, &ctx.accounts.book_no_vault),
+        ] {
+            token_interface::mint_to(
+                CpiContext::new_with_signer(
+                    ctx.accounts.token_program.to_account_info(),
+                    MintTo {
+                        mint: mint.to_account_info(),
+                        to: vault.to_account_info(),
+                        authority: market.to_account_info(),
+                    },
+                    signer,
+                ),
+                sets.checked_mul(lot).ok_or(ErrorCode::MathOverflow)?,
+            )?;
+        }
+        market.total_liquidity = market.total_liquidity.checked_add(collateral).unwrap();
+        ctx.accounts.price_history.record_set_mint(
+            clock.unix_timestamp,
+            sets.checked_mul(lot).ok_or(ErrorCode::MathOverflow)?,
+            collateral_to_outcome(result.sets_yes_cost, collateral_decimals)?,
+        )?;
+    } else if result.sets_merged > result.sets_minted {
+        let sets = result.sets_merged - result.sets_minted;
+        let collateral = set_collateral(sets)?;
+
+        for (mint, vault) in [
+            (&ctx.accounts.yes_token_mint, &ctx.accounts.book_yes_vault),
+            (&ctx.accounts.no_token_mint, &ctx.accounts.book_no_vault),
+        ] {
+            token_interface::burn(
+                CpiContext::new_with_signer(
+                    ctx.accounts.token_program.to_account_info(),
+                    Burn {
+                        mint: mint.to_account_info(),
+                        from: vault.to_account_info(),
+                        authority: market.to_account_info(),
+                    },
+                    signer,
+                ),
+                sets.checked_mul(lot).ok_or(ErrorCode::MathOverflow)?,
+            )?;
+        }
+        token_interface::transfer_checked(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                TransferChecked {
+                    from: ctx.accounts.liquidity_vault.to_account_info(),
+                    mint: ctx.accounts.collateral_mint.to_account_info(),
+                    to: ctx.accounts.book_collateral_vault.to_account_info(),
+                    authority: market.to_account_info(),
+                },
+                signer,
+            ),
+            collateral,
+            collateral_decimals,
+        )?;
+        market.total_liquidity = market.total_liquidity.checked_sub(collateral).unwrap();
+    }
+
+    // Buy the AMM-routed lots from the curve out of the order's escrow
+    let mut amm_fee = 0;
+    if result.amm_lots > 0 {
+        let buy_yes = kind == OrderKind::BuyYes;
+        let shares = result
+            .amm_lots
+            .checked_mul(PRICE_SCALE_BPS as u64)
+            .ok_or(ErrorCode::MathOverflow)?;
+        let (cost, _) = calculate_bonding_curve(market.yes_pool, market.no_pool, shares, buy_yes)?;
+        amm_fee = calculate_fee(cost, fee_bps)?;
+
+        for (amount, to) in [
+            (cost, &ctx.accounts.liquidity_vault),
+            (amm_fee, &ctx.accounts.treasury_vault),
+        ] {
+            if amount == 0 {
+                continue;
+            }
+            token_interface::transfer_checked(
+                CpiContext::new_with_signer(
+                    ctx.accounts.token_program.to_account_info(),
+                    TransferChecked {
+                        from: ctx.accounts.book_collateral_vault.to_account_info(),
+                        mint: ctx.accounts.collateral_mint.to_account_info(),
+                        to: to.to_account_info(),
+                        authority: market.to_account_info(),
+                    },
+                    signer,
+                ),
+                amount,
+                collateral_decimals,
+            )?;
+        }
+
+        let (outcome_mint, vault) = if buy_yes {
+            (&ctx.accounts.yes_token_mint, &ctx.accounts.book_yes_vault)
+        } else {
+            (&ctx.accounts.no_token_mint, &ctx.accounts.book_no_vault)
+        };
+        token_interface::mint_to(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                MintTo {
+                    mint: outcome_mint.to_account_info(),
+                    to: vault.to_account_info(),
+                    authority: market.to_account_info(),
+                },
+                signer,
+            ),
+            result.amm_lots.checked_mul(lot).ok_or(ErrorCode::MathOverflow)?,
+        )?;
+
+        if buy_yes {
+            market.yes_pool = market.yes_pool.checked_add(shares).unwrap();
+            taker_credit.yes = taker_credit.yes.checked_add(result.amm_lots).unwrap();
+        } else {
+            market.no_pool = market.no_pool.checked_add(shares).unwrap();
+            taker_credit.no = taker_credit.no.checked_add(result.amm_lots).unwrap();
+        }
+        market.total_liquidity = market.total_liquidity.checked_add(cost).unwrap();
+        market.volume = market.volume.checked_add(cost).unwrap();
+        ctx.accounts.price_history.record_mint(
+            clock.unix_timestamp,
+            buy_yes,
+            result.amm_lots.checked_mul(lot).ok_or(ErrorCode::MathOverflow)?,
+            collateral_to_outcome(cost, collateral_decimals)?,
+        )?;
+        config.total_volume = config.total_volume.checked_add(cost).unwrap();
+        // No gauge volume here: direct book orders are excluded from volume
+        // rewards (see `Gauge`)
+
+        // Escrow above the curve's price comes back to the taker
+        taker_credit.collateral = kind
+            .escrow(yes_price, result.amm_lots)?
+            .collateral
+            .checked_sub(cost)
+            .and_then(|unspent| unspent.checked_sub(amm_fee))
+            .and_then(|unspent| unspent.checked_add(taker_credit.collateral))
+            .ok_or(ErrorCode::MathUnderflow)?;
+
+        if amm_fee > 0 {
+            emit!(TreasuryMovement {
+                kind: TreasuryMovementKind::ProtocolFee,
+                mint: ctx.accounts.treasury_vault.mint,
+                amount: amm_fee,
+                counterparty: ctx.accounts.owner.key(),
+                reference: market.key(),
+                timestamp: clock.unix_timestamp,
+            });
+        }
+    }
+
+    if result.book_lots > 0 || result.amm_lots > 0 {
+        ctx.accounts
+            .price_history
+            .record(clock.unix_timestamp, implied_yes_price(market.yes_pool, market.no_pool)?)?;
+    }
+
+    // Rest the remainder, or release its escrow if immediate-or-cancel or
+    // too small to rest
+    let mut order_id = None;
+    if result.remaining_lots > 0 {
+        if order_type == OrderType::ImmediateOrCancel
+            || result.remaining_lots < OrderBook::MIN_ORDER_LOTS
+        {
+            taker_credit.add(&kind.escrow(yes_price, result.remaining_lots)?)?;
+        } else {
+            let id = order_book.next_order_id;
+            order_book.next_order_id = id.checked_add(1).unwrap();
+            order_book.insert(Order {
+                id,
+                owner: ctx.accounts.owner.key(),
+                kind,
+                price: yes_price,
+                lots: result.remaining_lots,
+                placed_at: clock.unix_timestamp,
+            })?;
+            order_id = Some(id);
+        }
+    }
+
+    let open_orders = &mut ctx.accounts.open_orders;
+    if open_orders.owner == Pubkey::default() {
+        open_orders.market = market.key();
+        open_orders.owner = ctx.accounts.owner.key();
+        open_orders.bump = ctx.bumps.open_orders;
+    }
+    open_orders.credit(&taker_credit)?;
+
+    emit!(OrderPlaced {
+        market: market.key(),
+        owner: ctx.accounts.owner.key(),
+        order_id,
+        kind,
+        order_type,
+        price,
+        lots,
+        book_lots: result.book_lots,
+        amm_lots: result.amm_lots,
+        resting_lots: if order_id.is_some() { result.remaining_lots } else { 0 },
+        amm_fee,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct PlaceOrder<'info> {
+    #[account(mut, seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, Config>,
+
+    #[account(mut)]
+    pub market: Account<'info, Market>,
+
+    #[account(
+        mut,
+        seeds = [b"order_book", market.key().as_ref()],
+        bump = order_book.bump
+    )]
+    pub order_book: Account<'info, OrderBook>,
+
+    #[account(
+        init_if_needed,
+        payer = owner,
+        space = 8 + OpenOrders::INIT_SPACE,
+        seeds = [b"open_orders", market.key().as_ref(), owner.key().as_ref()],
+        bump
+    )]
+    pub open_orders: Account<'info, OpenOrders>,
+
+    #[account(
+        mut,
+        seeds = [b"price_history", market.key().as_ref()],
+        bump = price_history.bump
+    )]
+    pub price_history: Account<'info, PriceHistory>,
+
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    #[account(
+        mut,
+        constraint = owner_collateral.owner == owner.key(),
+        constraint = owner_collateral.mint == market.collateral_mint @ ErrorCode::InvalidCollateral
+    )]
+    pub owner_collateral: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(mut)]
+    pub owner_yes_token: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(mut)]
+    pub owner_no_token: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        seeds = [b"book_collateral", market.key().as_ref()],
+        bump
+    )]
+    pub book_collateral_vault: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        seeds = [b"book_yes", market.key().as_ref()],
+        bump
+    )]
+    pub book_yes_vault: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        seeds = [b"book_no", market.key().as_ref()],
+        bump
+    )]
+    pub book_no_vault: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        seeds = [b"vault", market.key().as_ref()],
+        bump
+    )]
+    pub liquidity_vault: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        seeds = [b"treasury_vault", market.collateral_mint.as_ref()],
+        bump
+    )]
+    pub treasury_vault: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(address = market.collateral_mint @ ErrorCode::InvalidCollateral)]
+    pub collateral_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        seeds = [b"yes_mint", market.key().as_ref()],
+        bump
+    )]
+    pub yes_token_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        seeds = [b"no_mint", market.key().as_ref()],
+        bump
+    )]
+    pub no_token_mint: InterfaceAccount<'info, Mint>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+#[event]
+pub struct OrderPlaced {
+    pub market: Pubkey,
+    pub owner: Pubkey,
+    /// Id of the resting remainder, if any
+    pub order_id: Option<u64>,
+    pub kind: OrderKind,
+    pub order_type: OrderType,
+    /// Price in the order's own outcome, basis points
+    pub price: u16,
+    pub lots: u64,
+    pub book_lots: u64,
+    pub amm_lots: u64,
+    pub resting_lots: u64,
+    pub amm_fee: u64,
+}
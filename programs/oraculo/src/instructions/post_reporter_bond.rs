@@ -0,0 +1,95 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{self, Mint, TokenAccount, TokenInterface, TransferChecked};
+use crate::state::{Config, Market, MarketStatus, ResolutionMode};
+use crate::errors::ErrorCode;
+
+pub fn handler(ctx: Context<PostReporterBond>) -> Result<()> {
+    let config = &ctx.accounts.config;
+    let market = &mut ctx.accounts.market;
+
+    // Validations
+    require!(
+        !config.is_paused(Config::PAUSE_PROPOSALS),
+        ErrorCode::ProtocolPaused
+    );
+    require!(!market.halted, ErrorCode::MarketHalted);
+    require!(
+        market.resolution_mode == ResolutionMode::DesignatedReporter,
+        ErrorCode::InvalidResolutionMode
+    );
+    require!(
+        market.resolver == Some(ctx.accounts.reporter.key()),
+        ErrorCode::Unauthorized
+    );
+    require!(market.reporter_bond == 0, ErrorCode::ReporterBondPosted);
+
+    // Bond the same stake a DAO proposer would put up
+    token_interface::transfer_checked(
+        CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            TransferChecked {
+                from: ctx.accounts.reporter_gov_token.to_account_info(),
+                mint: ctx.accounts.governance_token_mint.to_account_info(),
+                to: ctx.accounts.reporter_bond_vault.to_account_info(),
+                authority: ctx.accounts.reporter.to_account_info(),
+            },
+        ),
+        config.proposal_stake,
+        ctx.accounts.governance_token_mint.decimals,
+    )?;
+
+    market.reporter_bond = config.proposal_stake;
+
+    emit!(ReporterBondPosted {
+        market: market.key(),
+        reporter: ctx.accounts.reporter.key(),
+        amount: config.proposal_stake,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct PostReporterBond<'info> {
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        mut,
+        constraint = market.status == MarketStatus::Active @ ErrorCode::MarketNotActive
+    )]
+    pub market: Account<'info, Market>,
+
+    #[account(mut)]
+    pub reporter: Signer<'info>,
+
+    #[account(
+        mut,
+        constraint = reporter_gov_token.owner == reporter.key(),
+        constraint = reporter_gov_token.mint == config.governance_token_mint
+    )]
+    pub reporter_gov_token: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        init,
+        payer = reporter,
+        token::mint = governance_token_mint,
+        token::authority = market,
+        seeds = [b"reporter_bond", market.key().as_ref()],
+        bump
+    )]
+    pub reporter_bond_vault: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(address = config.governance_token_mint)]
+    pub governance_token_mint: InterfaceAccount<'info, Mint>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+#[event]
+pub struct ReporterBondPosted {
+    pub market: Pubkey,
+    pub reporter: Pubkey,
+    pub amount: u64,
+}
@@ -0,0 +1,189 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{self, Mint, TokenAccount, TokenInterface, TransferChecked};
+use crate::state::{Config, LateBets, Market, MarketStatus, PriceHistory, Proposal, ProposalStatus};
+use crate::errors::ErrorCode;
+use crate::resolution::resolver_for;
+
+pub fn handler(
+    ctx: Context<ProposeEarlyResolution>,
+    outcome: bool,
+    evidence: String,
+    event_time: i64,
+    refund_late_bets: bool,
+) -> Result<()> {
+    let config = &ctx.accounts.config;
+    let market = &mut ctx.accounts.market;
+    
+    // Validations
+    require!(
+        !config.is_paused(Config::PAUSE_PROPOSALS),
+        ErrorCode::ProtocolPaused
+    );
+    require!(!market.halted, ErrorCode::MarketHalted);
+    require!(evidence.len() <= 500, ErrorCode::EvidenceTooLong);
+    require!(
+        market.status == MarketStatus::Active,
+        ErrorCode::MarketNotActive
+    );
+
+    let clock = Clock::get()?;
+    require!(
+        clock.unix_timestamp < market.end_time,
+        ErrorCode::MarketEnded
+    );
+    require!(
+        event_time >= market.created_at && event_time <= clock.unix_timestamp,
+        ErrorCode::InvalidEventTime
+    );
+
+    // Early resolution is always a DAO vote, so the mode must allow voting
+    resolver_for(market.resolution_mode).check_vote(market)?;
+    let voting_ends_at = clock.unix_timestamp + market.voting_period;
+    require!(
+        voting_ends_at <= market.resolution_time,
+        ErrorCode::ResolutionDeadlinePassed
+    );
+
+    // Lock proposer governance token stake
+    token_interface::transfer_checked(
+        CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            TransferChecked {
+                from: ctx.accounts.proposer_gov_token.to_account_info(),
+                mint: ctx.accounts.governance_token_mint.to_account_info(),
+                to: ctx.accounts.proposal_stake_vault.to_account_info(),
+                authority: ctx.accounts.proposer.to_account_info(),
+            },
+        ),
+        config.proposal_stake,
+        ctx.accounts.governance_token_mint.decimals,
+    )?;
+
+    // Halt trading until the vote is executed
+    market.status = MarketStatus::ResolvingEarly;
+
+    // Initialize proposal
+    let proposal = &mut ctx.accounts.proposal;
+    proposal.market = market.key();
+    proposal.proposer = ctx.accounts.proposer.key();
+    proposal.outcome = outcome;
+    proposal.evidence = evidence.clone();
+    proposal.proposed_at = clock.unix_timestamp;
+    proposal.voting_ends_at = voting_ends_at;
+    proposal.votes_for = 0;
+    proposal.votes_against = 0;
+    proposal.status = ProposalStatus::Active;
+    proposal.early = true;
+    proposal.event_time = event_time;
+    // Trading is halted from here, so the late bets are final
+    proposal.late_bets = if refund_late_bets {
+        Some(LateBets {
+            cutoff: event_time,
+            minted: ctx.accounts.price_history.minted_since(event_time)?,
+            yes_supply: ctx.accounts.yes_token_mint.supply,
+            no_supply: ctx.accounts.no_token_mint.supply,
+        })
+    } else {
+        None
+    };
+    proposal.bump = ctx.bumps.proposal;
+    market.open_proposals = market
+        .open_proposals
+        .checked_add(1)
+        .ok_or(ErrorCode::MathOverflow)?;
+
+    emit!(EarlyResolutionProposed {
+        market: market.key(),
+        proposal: proposal.key(),
+        proposer: proposal.proposer,
+        outcome,
+        evidence,
+        event_time,
+        refund_late_bets,
+        voting_ends_at: proposal.voting_ends_at,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct ProposeEarlyResolution<'info> {
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        mut,
+        constraint = market.status == MarketStatus::Active
+    )]
+    pub market: Account<'info, Market>,
+
+    #[account(
+        seeds = [b"price_history", market.key().as_ref()],
+        bump = price_history.bump
+    )]
+    pub price_history: Account<'info, PriceHistory>,
+
+    #[account(
+        seeds = [b"yes_mint", market.key().as_ref()],
+        bump
+    )]
+    pub yes_token_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        seeds = [b"no_mint", market.key().as_ref()],
+        bump
+    )]
+    pub no_token_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        init,
+        payer = proposer,
+        space = 8 + Proposal::INIT_SPACE,
+        seeds = [
+            b"early_proposal",
+            market.key().as_ref(),
+            proposer.key().as_ref()
+        ],
+        bump
+    )]
+    pub proposal: Account<'info, Proposal>,
+
+    #[account(mut)]
+    pub proposer: Signer<'info>,
+
+    #[account(
+        mut,
+        constraint = proposer_gov_token.owner == proposer.key(),
+        constraint = proposer_gov_token.mint == config.governance_token_mint
+    )]
+    pub proposer_gov_token: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        init,
+        payer = proposer,
+        token::mint = governance_token_mint,
+        token::authority = proposal,
+        seeds = [b"proposal_stake", proposal.key().as_ref()],
+        bump
+    )]
+    pub proposal_stake_vault: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(address = config.governance_token_mint)]
+    pub governance_token_mint: InterfaceAccount<'info, Mint>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+#[event]
+pub struct EarlyResolutionProposed {
+    pub market: Pubkey,
+    pub proposal: Pubkey,
+    pub proposer: Pubkey,
+    pub outcome: bool,
+    pub evidence: String,
+    pub event_time: i64,
+    pub refund_late_bets: bool,
+    pub voting_ends_at: i64,
+}
@@ -1,7 +1,8 @@
 use anchor_lang::prelude::*;
-use anchor_spl::token::{self, Token, TokenAccount, Transfer};
+use anchor_spl::token_interface::{self, Mint, TokenAccount, TokenInterface, TransferChecked};
 use crate::state::{Config, Market, MarketStatus, Proposal, ProposalStatus};
 use crate::errors::ErrorCode;
+use crate::resolution::resolver_for;
 
 pub fn handler(
     ctx: Context<ProposeResolution>,
@@ -9,9 +10,14 @@ pub fn handler(
     evidence: String,
 ) -> Result<()> {
     let config = &ctx.accounts.config;
-    let market = &ctx.accounts.market;
+    let market = &mut ctx.accounts.market;
     
     // Validations
+    require!(
+        !config.is_paused(Config::PAUSE_PROPOSALS),
+        ErrorCode::ProtocolPaused
+    );
+    require!(!market.halted, ErrorCode::MarketHalted);
     require!(evidence.len() <= 500, ErrorCode::EvidenceTooLong);
     require!(
         market.status == MarketStatus::Active,
@@ -19,24 +25,30 @@ pub fn handler(
     );
     
     let clock = Clock::get()?;
+    let resolver = resolver_for(market.resolution_mode);
+    resolver.check_propose(market, &ctx.accounts.proposer.key(), clock.unix_timestamp)?;
+    let voting_ends_at = clock.unix_timestamp + resolver.voting_period(market);
     require!(
-        clock.unix_timestamp > market.end_time,
-        ErrorCode::MarketNotEnded
+        voting_ends_at <= market.resolution_time,
+        ErrorCode::ResolutionDeadlinePassed
     );
+    resolver.on_propose(market);
 
     let proposal = &mut ctx.accounts.proposal;
     
     // Lock proposer governance token stake
-    token::transfer(
+    token_interface::transfer_checked(
         CpiContext::new(
             ctx.accounts.token_program.to_account_info(),
-            Transfer {
+            TransferChecked {
                 from: ctx.accounts.proposer_gov_token.to_account_info(),
+                mint: ctx.accounts.governance_token_mint.to_account_info(),
                 to: ctx.accounts.proposal_stake_vault.to_account_info(),
                 authority: ctx.accounts.proposer.to_account_info(),
             },
         ),
         config.proposal_stake,
+        ctx.accounts.governance_token_mint.decimals,
     )?;
 
     // Initialize proposal
@@ -45,11 +57,16 @@ pub fn handler(
     proposal.outcome = outcome;
     proposal.evidence = evidence.clone();
     proposal.proposed_at = clock.unix_timestamp;
-    proposal.voting_ends_at = clock.unix_timestamp + (48 * 60 * 60); // 48h voting period
+    proposal.voting_ends_at = voting_ends_at;
     proposal.votes_for = 0;
     proposal.votes_against = 0;
     proposal.status = ProposalStatus::Active;
+    proposal.early = false;
     proposal.bump = ctx.bumps.proposal;
+    market.open_proposals = market
+        .open_proposals
+        .checked_add(1)
+        .ok_or(ErrorCode::MathOverflow)?;
 
     emit!(ResolutionProposed {
         market: market.key(),
@@ -69,6 +86,7 @@ pub struct ProposeResolution<'info> {
     pub config: Account<'info, Config>,
 
     #[account(
+        mut,
         constraint = market.status == MarketStatus::Active
     )]
     pub market: Account<'info, Market>,
@@ -94,19 +112,22 @@ pub struct ProposeResolution<'info> {
         constraint = proposer_gov_token.owner == proposer.key(),
         constraint = proposer_gov_token.mint == config.governance_token_mint
     )]
-    pub proposer_gov_token: Account<'info, TokenAccount>,
+    pub proposer_gov_token: InterfaceAccount<'info, TokenAccount>,
 
     #[account(
         init,
         payer = proposer,
-        token::mint = config.governance_token_mint,
+        token::mint = governance_token_mint,
         token::authority = proposal,
         seeds = [b"proposal_stake", proposal.key().as_ref()],
         bump
     )]
-    pub proposal_stake_vault: Account<'info, TokenAccount>,
+    pub proposal_stake_vault: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(address = config.governance_token_mint)]
+    pub governance_token_mint: InterfaceAccount<'info, Mint>,
 
-    pub token_program: Program<'info, Token>,
+    pub token_program: Interface<'info, TokenInterface>,
     pub system_program: Program<'info, System>,
     pub rent: Sysvar<'info, Rent>,
 }
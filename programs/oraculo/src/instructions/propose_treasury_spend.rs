@@ -0,0 +1,146 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{self, Mint, TokenAccount, TokenInterface, TransferChecked};
+use crate::state::{Config, Treasury, TreasuryProposal, ProposalStatus};
+use crate::errors::ErrorCode;
+
+pub fn handler(
+    ctx: Context<ProposeTreasurySpend>,
+    amount: u64,
+    memo: String,
+) -> Result<()> {
+    let config = &ctx.accounts.config;
+
+    // Validations
+    require!(
+        !config.is_paused(Config::PAUSE_PROPOSALS),
+        ErrorCode::ProtocolPaused
+    );
+    require!(memo.len() <= 200, ErrorCode::MemoTooLong);
+    require!(amount > 0, ErrorCode::ZeroAmount);
+    require!(
+        ctx.accounts.proposer_gov_token.amount >= config.proposal_stake,
+        ErrorCode::InsufficientStake
+    );
+
+    // Lock proposer governance token stake until the spend is finalized
+    token_interface::transfer_checked(
+        CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            TransferChecked {
+                from: ctx.accounts.proposer_gov_token.to_account_info(),
+                mint: ctx.accounts.governance_token_mint.to_account_info(),
+                to: ctx.accounts.proposal_stake_vault.to_account_info(),
+                authority: ctx.accounts.proposer.to_account_info(),
+            },
+        ),
+        config.proposal_stake,
+        ctx.accounts.governance_token_mint.decimals,
+    )?;
+
+    let clock = Clock::get()?;
+    let treasury = &mut ctx.accounts.treasury;
+    let proposal = &mut ctx.accounts.proposal;
+
+    proposal.id = treasury.spend_proposal_count;
+    proposal.proposer = ctx.accounts.proposer.key();
+    proposal.mint = ctx.accounts.treasury_vault.mint;
+    proposal.recipient = ctx.accounts.recipient.key();
+    proposal.amount = amount;
+    proposal.memo = memo.clone();
+    proposal.proposed_at = clock.unix_timestamp;
+    proposal.voting_ends_at = clock.unix_timestamp + Treasury::VOTING_PERIOD;
+    proposal.executable_at = proposal.voting_ends_at + Treasury::TIMELOCK;
+    proposal.votes_for = 0;
+    proposal.votes_against = 0;
+    proposal.stake = config.proposal_stake;
+    proposal.status = ProposalStatus::Active;
+    proposal.bump = ctx.bumps.proposal;
+
+    treasury.spend_proposal_count = treasury.spend_proposal_count.checked_add(1).unwrap();
+
+    emit!(TreasurySpendProposed {
+        proposal: proposal.key(),
+        id: proposal.id,
+        proposer: proposal.proposer,
+        mint: proposal.mint,
+        recipient: proposal.recipient,
+        amount,
+        memo,
+        voting_ends_at: proposal.voting_ends_at,
+        executable_at: proposal.executable_at,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct ProposeTreasurySpend<'info> {
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, Config>,
+
+    #[account(mut, seeds = [b"treasury"], bump = treasury.bump)]
+    pub treasury: Account<'info, Treasury>,
+
+    #[account(
+        init,
+        payer = proposer,
+        space = 8 + TreasuryProposal::INIT_SPACE,
+        seeds = [
+            b"treasury_proposal".as_ref(),
+            &treasury.spend_proposal_count.to_le_bytes()
+        ],
+        bump
+    )]
+    pub proposal: Account<'info, TreasuryProposal>,
+
+    #[account(
+        seeds = [b"treasury_vault", treasury_vault.mint.as_ref()],
+        bump
+    )]
+    pub treasury_vault: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        constraint = recipient.mint == treasury_vault.mint
+    )]
+    pub recipient: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(mut)]
+    pub proposer: Signer<'info>,
+
+    #[account(
+        mut,
+        constraint = proposer_gov_token.owner == proposer.key(),
+        constraint = proposer_gov_token.mint == config.governance_token_mint
+    )]
+    pub proposer_gov_token: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        init,
+        payer = proposer,
+        token::mint = governance_token_mint,
+        token::authority = proposal,
+        seeds = [b"proposal_stake", proposal.key().as_ref()],
+        bump
+    )]
+    pub proposal_stake_vault: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(address = config.governance_token_mint)]
+    pub governance_token_mint: InterfaceAccount<'info, Mint>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+#[event]
+pub struct TreasurySpendProposed {
+    pub proposal: Pubkey,
+    pub id: u64,
+    pub proposer: Pubkey,
+    pub mint: Pubkey,
+    pub recipient: Pubkey,
+    pub amount: u64,
+    pub memo: String,
+    pub voting_ends_at: i64,
+    pub executable_at: i64,
+}
@@ -0,0 +1,155 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{
+    self, CloseAccount, Mint, TokenAccount, TokenInterface, TransferChecked,
+};
+use crate::state::{
+    Config, Market, MarketStatus, Proposal, ProposalStatus, TreasuryMovement, TreasuryMovementKind,
+};
+use crate::errors::ErrorCode;
+
+pub fn handler(ctx: Context<ReclaimResolutionStake>) -> Result<()> {
+    let config = &ctx.accounts.config;
+    let market = &mut ctx.accounts.market;
+    let proposal = &mut ctx.accounts.proposal;
+
+    // Validations
+    require!(
+        !config.is_paused(Config::PAUSE_PROPOSALS),
+        ErrorCode::ProtocolPaused
+    );
+    require!(!market.halted, ErrorCode::MarketHalted);
+    require!(
+        proposal.status == ProposalStatus::Active,
+        ErrorCode::ProposalNotActive
+    );
+
+    // Another proposal settled the market, so this one can never be executed.
+    // The proposer is only wrong if the market resolved the other way.
+    let correct_proposal = market.outcome != Some(!proposal.outcome);
+    let stake = ctx.accounts.proposal_stake_vault.amount;
+
+    let seed_prefix: &[u8] = if proposal.early { b"early_proposal" } else { b"proposal" };
+    let proposal_seeds = &[
+        seed_prefix,
+        proposal.market.as_ref(),
+        proposal.proposer.as_ref(),
+        &[proposal.bump],
+    ];
+    let signer = &[&proposal_seeds[..]];
+
+    let destination = if correct_proposal {
+        ctx.accounts.proposer_gov_token.to_account_info()
+    } else {
+        ctx.accounts.treasury_gov_vault.to_account_info()
+    };
+
+    if stake > 0 {
+        token_interface::transfer_checked(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                TransferChecked {
+                    from: ctx.accounts.proposal_stake_vault.to_account_info(),
+                    mint: ctx.accounts.governance_token_mint.to_account_info(),
+                    to: destination,
+                    authority: proposal.to_account_info(),
+                },
+                signer,
+            ),
+            stake,
+            ctx.accounts.governance_token_mint.decimals,
+        )?;
+
+        if !correct_proposal {
+            emit!(TreasuryMovement {
+                kind: TreasuryMovementKind::SlashedStake,
+                mint: config.governance_token_mint,
+                amount: stake,
+                counterparty: proposal.proposer,
+                reference: proposal.key(),
+                timestamp: Clock::get()?.unix_timestamp,
+            });
+        }
+    }
+
+    // Closing the vault makes the stake reclaimable only once
+    token_interface::close_account(CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        CloseAccount {
+            account: ctx.accounts.proposal_stake_vault.to_account_info(),
+            destination: ctx.accounts.proposer.to_account_info(),
+            authority: proposal.to_account_info(),
+        },
+        signer,
+    ))?;
+
+    proposal.status = ProposalStatus::Rejected;
+    market.open_proposals = market.open_proposals.saturating_sub(1);
+
+    emit!(ResolutionStakeReclaimed {
+        market: market.key(),
+        proposal: proposal.key(),
+        proposer: proposal.proposer,
+        stake_returned: if correct_proposal { stake } else { 0 },
+        stake_slashed: if correct_proposal { 0 } else { stake },
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct ReclaimResolutionStake<'info> {
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        mut,
+        constraint = matches!(market.status, MarketStatus::Resolved | MarketStatus::Cancelled)
+            @ ErrorCode::MarketNotResolved
+    )]
+    pub market: Account<'info, Market>,
+
+    #[account(
+        mut,
+        constraint = proposal.market == market.key()
+    )]
+    pub proposal: Account<'info, Proposal>,
+
+    /// CHECK: receives the stake vault's rent; checked against the proposal
+    #[account(mut, address = proposal.proposer @ ErrorCode::Unauthorized)]
+    pub proposer: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"proposal_stake", proposal.key().as_ref()],
+        bump
+    )]
+    pub proposal_stake_vault: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        constraint = proposer_gov_token.owner == proposal.proposer,
+        constraint = proposer_gov_token.mint == config.governance_token_mint
+    )]
+    pub proposer_gov_token: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        seeds = [b"treasury_vault", config.governance_token_mint.as_ref()],
+        bump
+    )]
+    pub treasury_gov_vault: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(address = config.governance_token_mint)]
+    pub governance_token_mint: InterfaceAccount<'info, Mint>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+#[event]
+pub struct ResolutionStakeReclaimed {
+    pub market: Pubkey,
+    pub proposal: Pubkey,
+    pub proposer: Pubkey,
+    pub stake_returned: u64,
+    pub stake_slashed: u64,
+}
@@ -0,0 +1,99 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{
+    self, CloseAccount, Mint, TokenAccount, TokenInterface, TransferChecked,
+};
+use crate::state::{Config, TreasuryProposal, ProposalStatus};
+use crate::errors::ErrorCode;
+
+pub fn handler(ctx: Context<ReclaimTreasuryStake>) -> Result<()> {
+    let proposal = &ctx.accounts.proposal;
+
+    require!(
+        !ctx.accounts.config.is_paused(Config::PAUSE_CLAIMS),
+        ErrorCode::ProtocolPaused
+    );
+    require!(
+        proposal.status != ProposalStatus::Active,
+        ErrorCode::ProposalNotFinalized
+    );
+
+    let id = proposal.id.to_le_bytes();
+    let seeds = &[b"treasury_proposal".as_ref(), &id, &[proposal.bump]];
+    let signer = &[&seeds[..]];
+
+    token_interface::transfer_checked(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            TransferChecked {
+                from: ctx.accounts.proposal_stake_vault.to_account_info(),
+                mint: ctx.accounts.governance_token_mint.to_account_info(),
+                to: ctx.accounts.proposer_gov_token.to_account_info(),
+                authority: proposal.to_account_info(),
+            },
+            signer,
+        ),
+        proposal.stake,
+        ctx.accounts.governance_token_mint.decimals,
+    )?;
+
+    // Closing the vault makes the stake reclaimable only once
+    token_interface::close_account(CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        CloseAccount {
+            account: ctx.accounts.proposal_stake_vault.to_account_info(),
+            destination: ctx.accounts.proposer.to_account_info(),
+            authority: proposal.to_account_info(),
+        },
+        signer,
+    ))?;
+
+    emit!(TreasuryStakeReclaimed {
+        proposal: proposal.key(),
+        proposer: proposal.proposer,
+        stake: proposal.stake,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct ReclaimTreasuryStake<'info> {
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        seeds = [b"treasury_proposal".as_ref(), &proposal.id.to_le_bytes()],
+        bump = proposal.bump,
+        has_one = proposer @ ErrorCode::Unauthorized
+    )]
+    pub proposal: Account<'info, TreasuryProposal>,
+
+    #[account(mut)]
+    pub proposer: Signer<'info>,
+
+    #[account(
+        mut,
+        constraint = proposer_gov_token.owner == proposer.key(),
+        constraint = proposer_gov_token.mint == config.governance_token_mint
+    )]
+    pub proposer_gov_token: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        seeds = [b"proposal_stake", proposal.key().as_ref()],
+        bump
+    )]
+    pub proposal_stake_vault: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(address = config.governance_token_mint)]
+    pub governance_token_mint: InterfaceAccount<'info, Mint>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+#[event]
+pub struct TreasuryStakeReclaimed {
+    pub proposal: Pubkey,
+    pub proposer: Pubkey,
+    pub stake: u64,
+}
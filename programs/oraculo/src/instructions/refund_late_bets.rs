@@ -0,0 +1,130 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{self, Burn, Mint, TokenAccount, TokenInterface, TransferChecked};
+use crate::state::{Config, Market, MarketStatus};
+use crate::errors::ErrorCode;
+use crate::utils::{outcome_to_collateral, transfer_fee};
+
+pub fn handler(ctx: Context<RefundLateBets>, amount: u64) -> Result<()> {
+    let config = &ctx.accounts.config;
+    let market = &ctx.accounts.market;
+
+    // Validations
+    require!(
+        !config.is_paused(Config::PAUSE_CLAIMS),
+        ErrorCode::ProtocolPaused
+    );
+    require!(!market.halted, ErrorCode::MarketHalted);
+    let late_bets = market.late_bets.as_ref().ok_or(ErrorCode::NoLateBets)?;
+    let outcome = market.outcome.ok_or(ErrorCode::OutcomeNotSet)?;
+
+    // Losing tokens share what the late losing bets paid in
+    let value = late_bets.losing_value(amount, outcome)?;
+    let refund = outcome_to_collateral(value, ctx.accounts.collateral_mint.decimals)?;
+    require!(refund > 0, ErrorCode::NoLateBets);
+
+    token_interface::burn(
+        CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            Burn {
+                mint: ctx.accounts.losing_mint.to_account_info(),
+                from: ctx.accounts.user_outcome_token.to_account_info(),
+                authority: ctx.accounts.user.to_account_info(),
+            },
+        ),
+        amount,
+    )?;
+
+    let seeds = &[
+        b"market".as_ref(),
+        &market.market_id.to_le_bytes(),
+        &[market.bump],
+    ];
+    let signer = &[&seeds[..]];
+
+    token_interface::transfer_checked(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            TransferChecked {
+                from: ctx.accounts.liquidity_vault.to_account_info(),
+                mint: ctx.accounts.collateral_mint.to_account_info(),
+                to: ctx.accounts.user_collateral.to_account_info(),
+                authority: market.to_account_info(),
+            },
+            signer,
+        ),
+        refund,
+        ctx.accounts.collateral_mint.decimals,
+    )?;
+
+    let received = refund
+        .checked_sub(transfer_fee(&ctx.accounts.collateral_mint.to_account_info(), refund)?)
+        .ok_or(ErrorCode::MathUnderflow)?;
+
+    emit!(LateBetsRefunded {
+        market: market.key(),
+        user: ctx.accounts.user.key(),
+        amount,
+        refund,
+        received,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct RefundLateBets<'info> {
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        constraint = market.status == MarketStatus::Resolved
+    )]
+    pub market: Account<'info, Market>,
+
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    #[account(
+        mut,
+        constraint = user_collateral.owner == user.key(),
+        constraint = user_collateral.mint == market.collateral_mint @ ErrorCode::InvalidCollateral
+    )]
+    pub user_collateral: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        seeds = [b"vault", market.key().as_ref()],
+        bump
+    )]
+    pub liquidity_vault: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(address = market.collateral_mint @ ErrorCode::InvalidCollateral)]
+    pub collateral_mint: InterfaceAccount<'info, Mint>,
+
+    /// The outcome token that lost
+    #[account(
+        mut,
+        address = if market.outcome == Some(true) { market.no_mint } else { market.yes_mint }
+            @ ErrorCode::InvalidOutcomeTokenAccount
+    )]
+    pub losing_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        constraint = user_outcome_token.owner == user.key(),
+        constraint = user_outcome_token.mint == losing_mint.key() @ ErrorCode::InvalidOutcomeTokenAccount
+    )]
+    pub user_outcome_token: InterfaceAccount<'info, TokenAccount>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+#[event]
+pub struct LateBetsRefunded {
+    pub market: Pubkey,
+    pub user: Pubkey,
+    /// Losing tokens burned
+    pub amount: u64,
+    pub refund: u64,
+    pub received: u64,
+}
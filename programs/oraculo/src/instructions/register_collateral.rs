@@ -0,0 +1,65 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::Mint;
+use crate::state::{CollateralConfig, Config};
+use crate::errors::ErrorCode;
+
+pub fn handler(
+    ctx: Context<RegisterCollateral>,
+    min_bet: u64,
+    min_liquidity: u64,
+) -> Result<()> {
+    // No pause check: governance-only, and must work while paused
+    require!(min_bet > 0 && min_liquidity > 0, ErrorCode::ZeroAmount);
+
+    let collateral = &mut ctx.accounts.collateral;
+    collateral.mint = ctx.accounts.mint.key();
+    collateral.decimals = ctx.accounts.mint.decimals;
+    collateral.min_bet = min_bet;
+    collateral.min_liquidity = min_liquidity;
+    collateral.enabled = true;
+    collateral.outcome_of = None;
+    collateral.bump = ctx.bumps.collateral;
+
+    emit!(CollateralRegistered {
+        mint: collateral.mint,
+        decimals: collateral.decimals,
+        min_bet,
+        min_liquidity,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct RegisterCollateral<'info> {
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        has_one = authority @ ErrorCode::Unauthorized
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + CollateralConfig::INIT_SPACE,
+        seeds = [b"collateral", mint.key().as_ref()],
+        bump
+    )]
+    pub collateral: Account<'info, CollateralConfig>,
+
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[event]
+pub struct CollateralRegistered {
+    pub mint: Pubkey,
+    pub decimals: u8,
+    pub min_bet: u64,
+    pub min_liquidity: u64,
+}
@@ -0,0 +1,84 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::Mint;
+use crate::state::{CollateralConfig, Config, Market, MarketStatus};
+use crate::errors::ErrorCode;
+use crate::utils::collateral_to_outcome;
+
+pub fn handler(ctx: Context<RegisterOutcomeCollateral>) -> Result<()> {
+    let market = &ctx.accounts.market;
+    let parent_collateral = &ctx.accounts.parent_collateral;
+
+    // Validations
+    require!(
+        !ctx.accounts.config.is_paused(Config::PAUSE_MARKET_CREATION),
+        ErrorCode::ProtocolPaused
+    );
+    require!(!market.halted, ErrorCode::MarketHalted);
+    require!(
+        market.status == MarketStatus::Active,
+        ErrorCode::MarketNotActive
+    );
+
+    // Limits carry over from the market's own collateral at par
+    let collateral = &mut ctx.accounts.collateral;
+    collateral.mint = ctx.accounts.outcome_mint.key();
+    collateral.decimals = ctx.accounts.outcome_mint.decimals;
+    collateral.min_bet = collateral_to_outcome(parent_collateral.min_bet, parent_collateral.decimals)?;
+    collateral.min_liquidity = collateral_to_outcome(parent_collateral.min_liquidity, parent_collateral.decimals)?;
+    collateral.enabled = true;
+    collateral.outcome_of = Some(market.key());
+    collateral.bump = ctx.bumps.collateral;
+
+    emit!(OutcomeCollateralRegistered {
+        market: market.key(),
+        mint: collateral.mint,
+        outcome: collateral.mint == market.yes_mint,
+        min_bet: collateral.min_bet,
+        min_liquidity: collateral.min_liquidity,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct RegisterOutcomeCollateral<'info> {
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, Config>,
+
+    pub market: Account<'info, Market>,
+
+    #[account(
+        constraint = outcome_mint.key() == market.yes_mint
+            || outcome_mint.key() == market.no_mint @ ErrorCode::InvalidParentMarket
+    )]
+    pub outcome_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        seeds = [b"collateral", market.collateral_mint.as_ref()],
+        bump = parent_collateral.bump
+    )]
+    pub parent_collateral: Account<'info, CollateralConfig>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + CollateralConfig::INIT_SPACE,
+        seeds = [b"collateral", outcome_mint.key().as_ref()],
+        bump
+    )]
+    pub collateral: Account<'info, CollateralConfig>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[event]
+pub struct OutcomeCollateralRegistered {
+    pub market: Pubkey,
+    pub mint: Pubkey,
+    pub outcome: bool,
+    pub min_bet: u64,
+    pub min_liquidity: u64,
+}
@@ -0,0 +1,57 @@
+use anchor_lang::prelude::*;
+use crate::state::{Config, Market, MarketStatus, ResolutionMode};
+use crate::errors::ErrorCode;
+use crate::resolution::DesignatedReporter;
+
+pub fn handler(ctx: Context<ReportOutcome>, outcome: bool) -> Result<()> {
+    let config = &ctx.accounts.config;
+    let market = &mut ctx.accounts.market;
+
+    // Validations
+    require!(
+        !config.is_paused(Config::PAUSE_PROPOSALS),
+        ErrorCode::ProtocolPaused
+    );
+    require!(!market.halted, ErrorCode::MarketHalted);
+    require!(
+        market.resolution_mode == ResolutionMode::DesignatedReporter,
+        ErrorCode::InvalidResolutionMode
+    );
+
+    let clock = Clock::get()?;
+    DesignatedReporter::check_report(market, &ctx.accounts.reporter.key(), clock.unix_timestamp)?;
+
+    market.reported_outcome = Some(outcome);
+    market.reported_at = Some(clock.unix_timestamp);
+
+    emit!(OutcomeReported {
+        market: market.key(),
+        reporter: ctx.accounts.reporter.key(),
+        outcome,
+        challenge_ends_at: clock.unix_timestamp + market.voting_period,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct ReportOutcome<'info> {
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        mut,
+        constraint = market.status == MarketStatus::Active @ ErrorCode::MarketNotActive
+    )]
+    pub market: Account<'info, Market>,
+
+    pub reporter: Signer<'info>,
+}
+
+#[event]
+pub struct OutcomeReported {
+    pub market: Pubkey,
+    pub reporter: Pubkey,
+    pub outcome: bool,
+    pub challenge_ends_at: i64,
+}
@@ -0,0 +1,101 @@
+use anchor_lang::prelude::*;
+use crate::state::{Config, Market, MarketStatus, ResolutionMode};
+use crate::errors::ErrorCode;
+use crate::oracle::OraclePrice;
+use crate::utils::price_condition_met;
+
+pub fn handler(ctx: Context<ResolveFromOracle>) -> Result<()> {
+    let config = &ctx.accounts.config;
+    let market = &mut ctx.accounts.market;
+
+    // Validations
+    require!(
+        !config.is_paused(Config::PAUSE_PROPOSALS),
+        ErrorCode::ProtocolPaused
+    );
+    require!(!market.halted, ErrorCode::MarketHalted);
+    require!(
+        market.status == MarketStatus::Active,
+        ErrorCode::MarketNotActive
+    );
+
+    require!(
+        market.resolution_mode == ResolutionMode::PriceOracle,
+        ErrorCode::NotOracleMarket
+    );
+    let condition = market
+        .price_condition
+        .clone()
+        .ok_or(ErrorCode::NotOracleMarket)?;
+    require_keys_eq!(
+        ctx.accounts.price_feed.key(),
+        condition.feed,
+        ErrorCode::InvalidOracleAccount
+    );
+
+    let clock = Clock::get()?;
+    require!(
+        clock.unix_timestamp > market.end_time,
+        ErrorCode::MarketNotEnded
+    );
+
+    // Only a fresh price published after the market closed can settle it
+    let price = OraclePrice::load(&ctx.accounts.price_feed)?;
+    require!(
+        price.publish_time >= market.end_time
+            && clock.unix_timestamp.saturating_sub(price.publish_time)
+                <= condition.max_staleness as i64,
+        ErrorCode::StaleOraclePrice
+    );
+    require!(
+        price.confidence_within(condition.max_conf_bps),
+        ErrorCode::OracleConfidenceTooWide
+    );
+
+    let outcome = price_condition_met(
+        price.price,
+        price.expo,
+        condition.threshold,
+        condition.expo,
+        condition.comparison,
+    )?;
+
+    market.status = MarketStatus::Resolved;
+    market.outcome = Some(outcome);
+    market.resolved_at = Some(clock.unix_timestamp);
+
+    emit!(MarketResolvedFromOracle {
+        market: market.key(),
+        feed: condition.feed,
+        price: price.price,
+        conf: price.conf,
+        expo: price.expo,
+        publish_time: price.publish_time,
+        outcome,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct ResolveFromOracle<'info> {
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, Config>,
+
+    #[account(mut)]
+    pub market: Account<'info, Market>,
+
+    /// CHECK: Owner and layout are validated when the price is loaded
+    pub price_feed: UncheckedAccount<'info>,
+}
+
+#[event]
+pub struct MarketResolvedFromOracle {
+    pub market: Pubkey,
+    pub feed: Pubkey,
+    pub price: i64,
+    pub conf: u64,
+    pub expo: i32,
+    pub publish_time: i64,
+    pub outcome: bool,
+}
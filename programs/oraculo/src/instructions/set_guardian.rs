@@ -0,0 +1,39 @@
+use anchor_lang::prelude::*;
+use crate::state::Config;
+use crate::errors::ErrorCode;
+
+pub fn handler(
+    ctx: Context<SetGuardian>,
+    new_guardian: Pubkey,
+) -> Result<()> {
+    // No pause check: the guardian role must stay reassignable while paused
+    let config = &mut ctx.accounts.config;
+    let previous_guardian = config.guardian;
+    config.guardian = new_guardian;
+
+    emit!(GuardianUpdated {
+        previous_guardian,
+        new_guardian,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct SetGuardian<'info> {
+    #[account(
+        mut,
+        seeds = [b"config"],
+        bump = config.bump,
+        has_one = authority @ ErrorCode::Unauthorized
+    )]
+    pub config: Account<'info, Config>,
+
+    pub authority: Signer<'info>,
+}
+
+#[event]
+pub struct GuardianUpdated {
+    pub previous_guardian: Pubkey,
+    pub new_guardian: Pubkey,
+}
@@ -0,0 +1,44 @@
+use anchor_lang::prelude::*;
+use crate::state::{Config, Market};
+use crate::errors::ErrorCode;
+
+pub fn handler(
+    ctx: Context<SetMarketHalted>,
+    halted: bool,
+) -> Result<()> {
+    // No pause or halt check: this is how halts are lifted
+    let market = &mut ctx.accounts.market;
+    market.halted = halted;
+
+    emit!(MarketHaltUpdated {
+        market: market.key(),
+        guardian: ctx.accounts.guardian.key(),
+        halted,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct SetMarketHalted<'info> {
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        has_one = guardian @ ErrorCode::Unauthorized
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(mut)]
+    pub market: Account<'info, Market>,
+
+    pub guardian: Signer<'info>,
+}
+
+#[event]
+pub struct MarketHaltUpdated {
+    pub market: Pubkey,
+    pub guardian: Pubkey,
+    pub halted: bool,
+    pub timestamp: i64,
+}
@@ -0,0 +1,43 @@
+use anchor_lang::prelude::*;
+use crate::state::Config;
+use crate::errors::ErrorCode;
+
+pub fn handler(
+    ctx: Context<SetPauseFlags>,
+    paused: u8,
+) -> Result<()> {
+    // No pause check: this is how pauses are lifted
+    require!(paused & !Config::PAUSE_ALL == 0, ErrorCode::InvalidPauseFlags);
+
+    let config = &mut ctx.accounts.config;
+    let previous = config.paused;
+    config.paused = paused;
+
+    emit!(ProtocolPauseUpdated {
+        guardian: ctx.accounts.guardian.key(),
+        previous,
+        paused,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct SetPauseFlags<'info> {
+    #[account(
+        mut,
+        seeds = [b"config"],
+        bump = config.bump,
+        has_one = guardian @ ErrorCode::Unauthorized
+    )]
+    pub config: Account<'info, Config>,
+
+    pub guardian: Signer<'info>,
+}
+
+#[event]
+pub struct ProtocolPauseUpdated {
+    pub guardian: Pubkey,
+    pub previous: u8,
+    pub paused: u8,
+}
@@ -0,0 +1,77 @@
+use anchor_lang::prelude::*;
+use crate::state::{validate_period_bounds, Config, ResolutionFallback};
+use crate::errors::ErrorCode;
+
+/// Bounds and defaults for market voting periods and resolution grace periods
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct ResolutionPeriodsParams {
+    pub min_voting_period: i64,
+    pub max_voting_period: i64,
+    pub default_voting_period: i64,
+    pub min_resolution_grace: i64,
+    pub max_resolution_grace: i64,
+    pub default_resolution_grace: i64,
+    pub unresolved_fallback: ResolutionFallback,
+}
+
+pub fn handler(ctx: Context<SetResolutionPeriods>, params: ResolutionPeriodsParams) -> Result<()> {
+    // No pause check: governance-only, and must work while paused
+    let ResolutionPeriodsParams {
+        min_voting_period,
+        max_voting_period,
+        default_voting_period,
+        min_resolution_grace,
+        max_resolution_grace,
+        default_resolution_grace,
+        unresolved_fallback,
+    } = params;
+
+    validate_period_bounds(min_voting_period, max_voting_period, default_voting_period)?;
+    validate_period_bounds(min_resolution_grace, max_resolution_grace, default_resolution_grace)?;
+
+    // Only applies to markets created from now on
+    let config = &mut ctx.accounts.config;
+    config.min_voting_period = min_voting_period;
+    config.max_voting_period = max_voting_period;
+    config.default_voting_period = default_voting_period;
+    config.min_resolution_grace = min_resolution_grace;
+    config.max_resolution_grace = max_resolution_grace;
+    config.default_resolution_grace = default_resolution_grace;
+    config.unresolved_fallback = unresolved_fallback;
+
+    emit!(ResolutionPeriodsUpdated {
+        min_voting_period,
+        max_voting_period,
+        default_voting_period,
+        min_resolution_grace,
+        max_resolution_grace,
+        default_resolution_grace,
+        unresolved_fallback,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct SetResolutionPeriods<'info> {
+    #[account(
+        mut,
+        seeds = [b"config"],
+        bump = config.bump,
+        has_one = authority @ ErrorCode::Unauthorized
+    )]
+    pub config: Account<'info, Config>,
+
+    pub authority: Signer<'info>,
+}
+
+#[event]
+pub struct ResolutionPeriodsUpdated {
+    pub min_voting_period: i64,
+    pub max_voting_period: i64,
+    pub default_voting_period: i64,
+    pub min_resolution_grace: i64,
+    pub max_resolution_grace: i64,
+    pub default_resolution_grace: i64,
+    pub unresolved_fallback: ResolutionFallback,
+}
@@ -0,0 +1,34 @@
+use anchor_lang::prelude::*;
+use crate::state::MarketTemplate;
+use crate::errors::ErrorCode;
+
+pub fn handler(ctx: Context<SetTemplateActive>, active: bool) -> Result<()> {
+    // No pause check: it only toggles whether the series spawns, and spawns
+    // are checked themselves
+    let template = &mut ctx.accounts.template;
+    template.active = active;
+
+    emit!(TemplateActiveSet {
+        template: template.key(),
+        active,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct SetTemplateActive<'info> {
+    #[account(
+        mut,
+        has_one = creator @ ErrorCode::Unauthorized
+    )]
+    pub template: Account<'info, MarketTemplate>,
+
+    pub creator: Signer<'info>,
+}
+
+#[event]
+pub struct TemplateActiveSet {
+    pub template: Pubkey,
+    pub active: bool,
+}
@@ -0,0 +1,168 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{self, Mint, TokenAccount, TokenInterface, TransferChecked};
+use crate::state::{Config, Market, OpenOrders};
+use crate::errors::ErrorCode;
+use crate::utils::lot_size;
+
+pub fn handler(ctx: Context<SettleFunds>) -> Result<()> {
+    let config = &ctx.accounts.config;
+    let market = &ctx.accounts.market;
+
+    // Validations
+    require!(
+        !config.is_paused(Config::PAUSE_CLAIMS),
+        ErrorCode::ProtocolPaused
+    );
+    require!(!market.halted, ErrorCode::MarketHalted);
+    let open_orders = &mut ctx.accounts.open_orders;
+    let owner = open_orders.owner;
+
+    let collateral = open_orders.collateral_free;
+    let yes = open_orders.yes_free;
+    let no = open_orders.no_free;
+    require!(collateral > 0 || yes > 0 || no > 0, ErrorCode::NothingToClaim);
+
+    let lot = lot_size(ctx.accounts.collateral_mint.decimals)?;
+    let seeds = &[
+        b"market".as_ref(),
+        &market.market_id.to_le_bytes(),
+        &[market.bump],
+    ];
+    let signer = &[&seeds[..]];
+
+    if collateral > 0 {
+        token_interface::transfer_checked(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                TransferChecked {
+                    from: ctx.accounts.book_collateral_vault.to_account_info(),
+                    mint: ctx.accounts.collateral_mint.to_account_info(),
+                    to: ctx.accounts.owner_collateral.to_account_info(),
+                    authority: market.to_account_info(),
+                },
+                signer,
+            ),
+            collateral,
+            ctx.accounts.collateral_mint.decimals,
+        )?;
+    }
+
+    // Anyone may settle on the owner's behalf; tokens only go to the owner
+    for (lots, mint, from, to) in [
+        (yes, &ctx.accounts.yes_token_mint, &ctx.accounts.book_yes_vault, &ctx.accounts.owner_yes_token),
+        (no, &ctx.accounts.no_token_mint, &ctx.accounts.book_no_vault, &ctx.accounts.owner_no_token),
+    ] {
+        if lots == 0 {
+            continue;
+        }
+        let to = to.as_ref().ok_or(ErrorCode::InvalidOutcomeTokenAccount)?;
+        require!(
+            to.mint == mint.key() && to.owner == owner,
+            ErrorCode::InvalidOutcomeTokenAccount
+        );
+
+        token_interface::transfer_checked(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                TransferChecked {
+                    from: from.to_account_info(),
+                    mint: mint.to_account_info(),
+                    to: to.to_account_info(),
+                    authority: market.to_account_info(),
+                },
+                signer,
+            ),
+            lots.checked_mul(lot).ok_or(ErrorCode::MathOverflow)?,
+            mint.decimals,
+        )?;
+    }
+
+    open_orders.collateral_free = 0;
+    open_orders.yes_free = 0;
+    open_orders.no_free = 0;
+
+    emit!(FundsSettled {
+        market: market.key(),
+        owner,
+        collateral,
+        yes_lots: yes,
+        no_lots: no,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct SettleFunds<'info> {
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, Config>,
+
+    pub market: Account<'info, Market>,
+
+    #[account(
+        mut,
+        seeds = [b"open_orders", market.key().as_ref(), open_orders.owner.as_ref()],
+        bump = open_orders.bump
+    )]
+    pub open_orders: Account<'info, OpenOrders>,
+
+    #[account(
+        mut,
+        constraint = owner_collateral.owner == open_orders.owner,
+        constraint = owner_collateral.mint == market.collateral_mint @ ErrorCode::InvalidCollateral
+    )]
+    pub owner_collateral: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(mut)]
+    pub owner_yes_token: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(mut)]
+    pub owner_no_token: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        seeds = [b"book_collateral", market.key().as_ref()],
+        bump
+    )]
+    pub book_collateral_vault: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        seeds = [b"book_yes", market.key().as_ref()],
+        bump
+    )]
+    pub book_yes_vault: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        seeds = [b"book_no", market.key().as_ref()],
+        bump
+    )]
+    pub book_no_vault: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(address = market.collateral_mint @ ErrorCode::InvalidCollateral)]
+    pub collateral_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        seeds = [b"yes_mint", market.key().as_ref()],
+        bump
+    )]
+    pub yes_token_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        seeds = [b"no_mint", market.key().as_ref()],
+        bump
+    )]
+    pub no_token_mint: InterfaceAccount<'info, Mint>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+#[event]
+pub struct FundsSettled {
+    pub market: Pubkey,
+    pub owner: Pubkey,
+    pub collateral: u64,
+    pub yes_lots: u64,
+    pub no_lots: u64,
+}
@@ -0,0 +1,336 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    metadata::{
+        create_metadata_accounts_v3, mpl_token_metadata::types::DataV2,
+        CreateMetadataAccountsV3, Metadata,
+    },
+    token_interface::{self, Mint, TokenAccount, TokenInterface, TransferChecked},
+};
+use crate::state::{
+    Category, CollateralConfig, Config, CreatorIndex, CreatorMarket, Market, MarketStatus, MarketTemplate, PriceHistory,
+};
+use crate::errors::ErrorCode;
+use crate::utils::{implied_yes_price, outcome_token_name, outcome_token_symbol, transfer_fee, OUTCOME_DECIMALS};
+
+pub fn handler(ctx: Context<SpawnFromTemplate>) -> Result<()> {
+    let config = &mut ctx.accounts.config;
+    let template = &mut ctx.accounts.template;
+
+    // Validations
+    require!(
+        !config.is_paused(Config::PAUSE_MARKET_CREATION),
+        ErrorCode::ProtocolPaused
+    );
+    require!(template.active, ErrorCode::TemplateInactive);
+
+    let clock = Clock::get()?;
+    let (skipped, start) = template
+        .due_slot(clock.unix_timestamp)?
+        .ok_or(ErrorCode::SpawnNotDue)?;
+    let series_index = template.series_index + skipped;
+    let end_time = start + template.duration;
+
+    let question = template.question(series_index, end_time);
+    require!(question.len() <= 200, ErrorCode::QuestionTooLong);
+
+    // The category may have been disabled or tightened since the template was made
+    let market_category = Category::load(
+        &ctx.accounts.category_account,
+        ctx.accounts.parent_category_account.as_ref().map(|parent| parent.as_ref()),
+    )?;
+    let deposited = template
+        .liquidity
+        .checked_sub(transfer_fee(&ctx.accounts.collateral_mint.to_account_info(), template.liquidity)?)
+        .ok_or(ErrorCode::MathUnderflow)?;
+    require!(
+        deposited >= market_category.min_liquidity_for(&ctx.accounts.collateral)?,
+        ErrorCode::InsufficientLiquidity
+    );
+
+    // Seed the market from the template's vault
+    let creator = template.creator;
+    let template_id = template.template_id.to_le_bytes();
+    let template_bump = [template.bump];
+    let template_seeds = &[b"template".as_ref(), creator.as_ref(), &template_id, &template_bump];
+
+    token_interface::transfer_checked(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            TransferChecked {
+                from: ctx.accounts.template_vault.to_account_info(),
+                mint: ctx.accounts.collateral_mint.to_account_info(),
+                to: ctx.accounts.liquidity_vault.to_account_info(),
+                authority: template.to_account_info(),
+            },
+            &[&template_seeds[..]],
+        ),
+        template.liquidity,
+        ctx.accounts.collateral_mint.decimals,
+    )?;
+
+    let market = &mut ctx.accounts.market;
+    market.market_id = config.total_markets;
+    market.creator = creator;
+    market.collateral_mint = template.collateral_mint;
+    market.question = question.clone();
+    market.description = template.description.clone();
+    market.category = template.category;
+    market.tags = Vec::new();
+    market.fee_bps = market_category.fee_bps;
+    market.created_at = clock.unix_timestamp;
+    market.end_time = end_time;
+    market.resolution_time = end_time + template.resolution_grace;
+    market.voting_period = template.voting_period;
+    market.resolution_grace = template.resolution_grace;
+    market.resolution_source = template.resolution_source.clone();
+    market.metadata = None;
+    market.edits = 0;
+    market.has_trades = false;
+    market.price_condition = template.price_condition.clone();
+    market.resolution_mode = template.resolution_mode;
+    market.resolver = template.resolver;
+    market.parent = None;
+    market.reporter_bond = 0;
+    market.reported_outcome = None;
+    market.reported_at = None;
+    market.report_disputed = false;
+    market.late_bets = None;
+    market.open_proposals = 0;
+    market.status = MarketStatus::Active;
+    market.total_liquidity = deposited;
+    market.creator_liquidity = deposited;
+    market.yes_pool = deposited / 2;
+    market.no_pool = deposited / 2;
+    market.yes_mint = ctx.accounts.yes_token_mint.key();
+    market.no_mint = ctx.accounts.no_token_mint.key();
+    market.volume = 0;
+    market.unique_bettors = 0;
+    market.halted = false;
+    market.bump = ctx.bumps.market;
+
+    config.total_markets = config.total_markets.checked_add(1).ok_or(ErrorCode::MathOverflow)?;
+
+    // Index the market under the template's creator
+    let creator_index = &mut ctx.accounts.creator_index;
+    creator_index.creator = creator;
+    creator_index.bump = ctx.bumps.creator_index;
+    let creator_market = &mut ctx.accounts.creator_market;
+    creator_market.creator = creator;
+    creator_market.index = creator_index.next_index()?;
+    creator_market.market = market.key();
+    creator_market.market_id = market.market_id;
+    creator_market.bump = ctx.bumps.creator_market;
+
+    ctx.accounts.price_history.init(
+        market.key(),
+        clock.unix_timestamp,
+        implied_yes_price(market.yes_pool, market.no_pool)?,
+        ctx.bumps.price_history,
+    );
+
+    template.series_index = series_index + 1;
+    template.next_spawn_at = start + template.interval;
+    template.last_market = Some(market.key());
+
+    // Name the outcome tokens after the question so wallets can tell them apart
+    let market_id = market.market_id.to_le_bytes();
+    let seeds = &[b"market".as_ref(), &market_id, &[market.bump]];
+    let signer = &[&seeds[..]];
+
+    for (outcome, mint, metadata) in [
+        (true, &ctx.accounts.yes_token_mint, &ctx.accounts.yes_metadata),
+        (false, &ctx.accounts.no_token_mint, &ctx.accounts.no_metadata),
+    ] {
+        create_metadata_accounts_v3(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_metadata_program.to_account_info(),
+                CreateMetadataAccountsV3 {
+                    metadata: metadata.to_account_info(),
+                    mint: mint.to_account_info(),
+                    mint_authority: market.to_account_info(),
+                    payer: ctx.accounts.payer.to_account_info(),
+                    update_authority: market.to_account_info(),
+                    system_program: ctx.accounts.system_program.to_account_info(),
+                    rent: ctx.accounts.rent.to_account_info(),
+                },
+                signer,
+            ),
+            DataV2 {
+                name: outcome_token_name(&question, outcome),
+                symbol: outcome_token_symbol(outcome),
+                uri: String::new(),
+                seller_fee_basis_points: 0,
+                creators: None,
+                collection: None,
+                uses: None,
+            },
+            true,
+            true,
+            None,
+        )?;
+    }
+
+    emit!(MarketSpawned {
+        template: template.key(),
+        market: market.key(),
+        market_id: market.market_id,
+        series_index,
+        skipped,
+        question,
+        end_time,
+        initial_liquidity: deposited,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct SpawnFromTemplate<'info> {
+    #[account(mut, seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        mut,
+        seeds = [b"template", template.creator.as_ref(), &template.template_id.to_le_bytes()],
+        bump = template.bump
+    )]
+    pub template: Account<'info, MarketTemplate>,
+
+    #[account(
+        mut,
+        seeds = [b"template_vault", template.key().as_ref()],
+        bump
+    )]
+    pub template_vault: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + Market::INIT_SPACE,
+        seeds = [b"market".as_ref(), &config.total_markets.to_le_bytes()],
+        bump
+    )]
+    pub market: Account<'info, Market>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + CreatorIndex::INIT_SPACE,
+        seeds = [b"creator_index", template.creator.as_ref()],
+        bump
+    )]
+    pub creator_index: Account<'info, CreatorIndex>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + CreatorMarket::INIT_SPACE,
+        seeds = [b"creator_market", template.creator.as_ref(), &creator_index.market_count.to_le_bytes()],
+        bump
+    )]
+    pub creator_market: Account<'info, CreatorMarket>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + PriceHistory::INIT_SPACE,
+        seeds = [b"price_history", market.key().as_ref()],
+        bump
+    )]
+    pub price_history: Account<'info, PriceHistory>,
+
+    /// Anyone can spawn a due market, paying its account rent
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    #[account(
+        init,
+        payer = payer,
+        token::mint = collateral_mint,
+        token::authority = market,
+        token::token_program = token_program,
+        seeds = [b"vault", market.key().as_ref()],
+        bump
+    )]
+    pub liquidity_vault: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        init,
+        payer = payer,
+        mint::decimals = OUTCOME_DECIMALS,
+        mint::authority = market,
+        mint::token_program = token_program,
+        seeds = [b"yes_mint", market.key().as_ref()],
+        bump
+    )]
+    pub yes_token_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        init,
+        payer = payer,
+        mint::decimals = OUTCOME_DECIMALS,
+        mint::authority = market,
+        mint::token_program = token_program,
+        seeds = [b"no_mint", market.key().as_ref()],
+        bump
+    )]
+    pub no_token_mint: InterfaceAccount<'info, Mint>,
+
+    /// CHECK: Created and validated by the token metadata program
+    #[account(
+        mut,
+        seeds = [b"metadata", token_metadata_program.key().as_ref(), yes_token_mint.key().as_ref()],
+        bump,
+        seeds::program = token_metadata_program.key()
+    )]
+    pub yes_metadata: UncheckedAccount<'info>,
+
+    /// CHECK: Created and validated by the token metadata program
+    #[account(
+        mut,
+        seeds = [b"metadata", token_metadata_program.key().as_ref(), no_token_mint.key().as_ref()],
+        bump,
+        seeds::program = token_metadata_program.key()
+    )]
+    pub no_metadata: UncheckedAccount<'info>,
+
+    #[account(
+        seeds = [b"collateral", collateral_mint.key().as_ref()],
+        bump = collateral.bump,
+        constraint = collateral.enabled @ ErrorCode::CollateralNotEnabled
+    )]
+    pub collateral: Account<'info, CollateralConfig>,
+
+    /// CHECK: The template category's PDA, loaded in the handler
+    #[account(
+        seeds = [b"category".as_ref(), &template.category.to_le_bytes()],
+        bump
+    )]
+    pub category_account: UncheckedAccount<'info>,
+
+    /// CHECK: The category's parent, for subcategories; checked by `Category::load`
+    pub parent_category_account: Option<UncheckedAccount<'info>>,
+
+    #[account(
+        address = template.collateral_mint @ ErrorCode::InvalidCollateral,
+        mint::token_program = token_program
+    )]
+    pub collateral_mint: InterfaceAccount<'info, Mint>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub token_metadata_program: Program<'info, Metadata>,
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+#[event]
+pub struct MarketSpawned {
+    pub template: Pubkey,
+    pub market: Pubkey,
+    pub market_id: u64,
+    pub series_index: u64,
+    /// Scheduled markets missed since the last spawn
+    pub skipped: u64,
+    pub question: String,
+    pub end_time: i64,
+    pub initial_liquidity: u64,
+}
@@ -0,0 +1,129 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{self, Mint, TokenAccount, TokenInterface, TransferChecked};
+use crate::state::{
+    Config, Treasury, TreasuryProposal, ProposalStatus, TreasuryMovement, TreasuryMovementKind,
+};
+use crate::errors::ErrorCode;
+
+pub fn handler(ctx: Context<TreasurySpend>) -> Result<()> {
+    let config = &ctx.accounts.config;
+    let proposal = &mut ctx.accounts.proposal;
+
+    let clock = Clock::get()?;
+
+    // Validations
+    require!(
+        !config.is_paused(Config::PAUSE_PROPOSALS),
+        ErrorCode::ProtocolPaused
+    );
+    require!(
+        proposal.status == ProposalStatus::Active,
+        ErrorCode::ProposalNotActive
+    );
+    require!(
+        clock.unix_timestamp >= proposal.voting_ends_at,
+        ErrorCode::VotingNotEnded
+    );
+
+    // A spend that failed its vote can be closed out before the timelock
+    let total_votes = proposal.votes_for.checked_add(proposal.votes_against).unwrap();
+    let supermajority_threshold = total_votes
+        .checked_mul(config.supermajority_percent as u64).unwrap()
+        .checked_div(100).unwrap();
+    let passed = total_votes >= config.quorum
+        && proposal.votes_for > proposal.votes_against
+        && proposal.votes_for >= supermajority_threshold;
+
+    if !passed {
+        proposal.status = ProposalStatus::Rejected;
+
+        emit!(TreasurySpendRejected {
+            proposal: proposal.key(),
+            votes_for: proposal.votes_for,
+            votes_against: proposal.votes_against,
+        });
+
+        return Ok(());
+    }
+
+    require!(
+        clock.unix_timestamp >= proposal.executable_at,
+        ErrorCode::TimelockNotExpired
+    );
+    require!(
+        ctx.accounts.treasury_vault.amount >= proposal.amount,
+        ErrorCode::InsufficientTreasuryBalance
+    );
+
+    let seeds = &[b"treasury".as_ref(), &[ctx.accounts.treasury.bump]];
+    let signer = &[&seeds[..]];
+
+    token_interface::transfer_checked(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            TransferChecked {
+                from: ctx.accounts.treasury_vault.to_account_info(),
+                mint: ctx.accounts.mint.to_account_info(),
+                to: ctx.accounts.recipient.to_account_info(),
+                authority: ctx.accounts.treasury.to_account_info(),
+            },
+            signer,
+        ),
+        proposal.amount,
+        ctx.accounts.mint.decimals,
+    )?;
+
+    proposal.status = ProposalStatus::Executed;
+
+    emit!(TreasuryMovement {
+        kind: TreasuryMovementKind::Spend,
+        mint: proposal.mint,
+        amount: proposal.amount,
+        counterparty: proposal.recipient,
+        reference: proposal.key(),
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct TreasurySpend<'info> {
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, Config>,
+
+    #[account(seeds = [b"treasury"], bump = treasury.bump)]
+    pub treasury: Account<'info, Treasury>,
+
+    #[account(
+        mut,
+        seeds = [b"treasury_proposal".as_ref(), &proposal.id.to_le_bytes()],
+        bump = proposal.bump
+    )]
+    pub proposal: Account<'info, TreasuryProposal>,
+
+    #[account(
+        mut,
+        seeds = [b"treasury_vault", proposal.mint.as_ref()],
+        bump
+    )]
+    pub treasury_vault: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        address = proposal.recipient
+    )]
+    pub recipient: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(address = proposal.mint)]
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+#[event]
+pub struct TreasurySpendRejected {
+    pub proposal: Pubkey,
+    pub votes_for: u64,
+    pub votes_against: u64,
+}
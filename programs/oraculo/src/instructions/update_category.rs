@@ -0,0 +1,62 @@
+use anchor_lang::prelude::*;
+use crate::state::{Category, Config};
+use crate::errors::ErrorCode;
+
+pub fn handler(
+    ctx: Context<UpdateCategory>,
+    name: String,
+    min_liquidity: u64,
+    fee_bps: Option<u16>,
+    enabled: bool,
+) -> Result<()> {
+    // No pause check: governance-only, and must work while paused
+    Category::check_name(ctx.accounts.category.id, &name)?;
+    if let Some(fee_bps) = fee_bps {
+        require!(fee_bps <= Config::MAX_PROTOCOL_FEE_BPS, ErrorCode::InvalidProtocolFee);
+    }
+
+    // Changes apply to new markets; existing ones keep their fee
+    let category = &mut ctx.accounts.category;
+    category.name = name.clone();
+    category.min_liquidity = min_liquidity;
+    category.fee_bps = fee_bps;
+    category.enabled = enabled;
+
+    emit!(CategoryUpdated {
+        id: category.id,
+        name,
+        min_liquidity,
+        fee_bps,
+        enabled,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct UpdateCategory<'info> {
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        has_one = authority @ ErrorCode::Unauthorized
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        mut,
+        seeds = [b"category".as_ref(), &category.id.to_le_bytes()],
+        bump = category.bump
+    )]
+    pub category: Account<'info, Category>,
+
+    pub authority: Signer<'info>,
+}
+
+#[event]
+pub struct CategoryUpdated {
+    pub id: u16,
+    pub name: String,
+    pub min_liquidity: u64,
+    pub fee_bps: Option<u16>,
+    pub enabled: bool,
+}
@@ -0,0 +1,55 @@
+use anchor_lang::prelude::*;
+use crate::state::{CollateralConfig, Config};
+use crate::errors::ErrorCode;
+
+pub fn handler(
+    ctx: Context<UpdateCollateral>,
+    min_bet: u64,
+    min_liquidity: u64,
+    enabled: bool,
+) -> Result<()> {
+    // No pause check: governance-only, and must work while paused
+    require!(min_bet > 0 && min_liquidity > 0, ErrorCode::ZeroAmount);
+
+    // Disabling only blocks new markets; existing ones keep settling
+    let collateral = &mut ctx.accounts.collateral;
+    collateral.min_bet = min_bet;
+    collateral.min_liquidity = min_liquidity;
+    collateral.enabled = enabled;
+
+    emit!(CollateralUpdated {
+        mint: collateral.mint,
+        min_bet,
+        min_liquidity,
+        enabled,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct UpdateCollateral<'info> {
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        has_one = authority @ ErrorCode::Unauthorized
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        mut,
+        seeds = [b"collateral", collateral.mint.as_ref()],
+        bump = collateral.bump
+    )]
+    pub collateral: Account<'info, CollateralConfig>,
+
+    pub authority: Signer<'info>,
+}
+
+#[event]
+pub struct CollateralUpdated {
+    pub mint: Pubkey,
+    pub min_bet: u64,
+    pub min_liquidity: u64,
+    pub enabled: bool,
+}
@@ -0,0 +1,60 @@
+use anchor_lang::prelude::*;
+use crate::state::{Config, Gauge, Market};
+use crate::errors::ErrorCode;
+
+pub fn handler(
+    ctx: Context<UpdateGauge>,
+    emission_rate: u64,
+    lp_weight_bps: u16,
+    end_time: i64,
+) -> Result<()> {
+    // No pause or halt check: governance-only, and must work while paused
+    let gauge = &mut ctx.accounts.gauge;
+    require!(
+        lp_weight_bps <= 10_000 && end_time > gauge.start_time,
+        ErrorCode::InvalidGaugeParams
+    );
+
+    // Settle emissions at the old rate before changing it
+    let clock = Clock::get()?;
+    gauge.update(&ctx.accounts.market, clock.unix_timestamp)?;
+
+    gauge.emission_rate = emission_rate;
+    gauge.lp_weight_bps = lp_weight_bps;
+    gauge.end_time = end_time;
+
+    emit!(GaugeUpdated {
+        gauge: gauge.key(),
+        emission_rate,
+        lp_weight_bps,
+        end_time,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct UpdateGauge<'info> {
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        has_one = authority @ ErrorCode::Unauthorized
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(mut)]
+    pub gauge: Account<'info, Gauge>,
+
+    #[account(address = gauge.market)]
+    pub market: Account<'info, Market>,
+
+    pub authority: Signer<'info>,
+}
+
+#[event]
+pub struct GaugeUpdated {
+    pub gauge: Pubkey,
+    pub emission_rate: u64,
+    pub lp_weight_bps: u16,
+    pub end_time: i64,
+}
@@ -1,20 +1,28 @@
 use anchor_lang::prelude::*;
-use anchor_spl::token::{self, Token, TokenAccount, Transfer};
-use crate::state::{Config, Proposal, ProposalStatus, VoteRecord};
+use anchor_spl::token_interface::{self, Mint, TokenAccount, TokenInterface, TransferChecked};
+use crate::state::{Config, Market, Proposal, ProposalStatus, VoteRecord};
 use crate::errors::ErrorCode;
+use crate::resolution::resolver_for;
 
 pub fn handler(
     ctx: Context<VoteResolution>,
     vote_weight: u64,
     support: bool,
 ) -> Result<()> {
+    let config = &ctx.accounts.config;
     let proposal = &mut ctx.accounts.proposal;
     
     // Validations
+    require!(
+        !config.is_paused(Config::PAUSE_PROPOSALS),
+        ErrorCode::ProtocolPaused
+    );
+    require!(!ctx.accounts.market.halted, ErrorCode::MarketHalted);
     require!(
         proposal.status == ProposalStatus::Active,
         ErrorCode::ProposalNotActive
     );
+    resolver_for(ctx.accounts.market.resolution_mode).check_vote(&ctx.accounts.market)?;
     
     let clock = Clock::get()?;
     require!(
@@ -26,16 +34,18 @@ pub fn handler(
     require!(!vote_record.voted_at != 0, ErrorCode::AlreadyVoted);
 
     // Lock voter tokens during voting period
-    token::transfer(
+    token_interface::transfer_checked(
         CpiContext::new(
             ctx.accounts.token_program.to_account_info(),
-            Transfer {
+            TransferChecked {
                 from: ctx.accounts.voter_gov_token.to_account_info(),
+                mint: ctx.accounts.governance_token_mint.to_account_info(),
                 to: ctx.accounts.vote_escrow.to_account_info(),
                 authority: ctx.accounts.voter.to_account_info(),
             },
         ),
         vote_weight,
+        ctx.accounts.governance_token_mint.decimals,
     )?;
 
     // Record vote
@@ -70,6 +80,9 @@ pub struct VoteResolution<'info> {
     #[account(seeds = [b"config"], bump = config.bump)]
     pub config: Account<'info, Config>,
 
+    #[account(address = proposal.market)]
+    pub market: Account<'info, Market>,
+
     #[account(mut)]
     pub proposal: Account<'info, Proposal>,
 
@@ -94,19 +107,22 @@ pub struct VoteResolution<'info> {
         constraint = voter_gov_token.owner == voter.key(),
         constraint = voter_gov_token.mint == config.governance_token_mint
     )]
-    pub voter_gov_token: Account<'info, TokenAccount>,
+    pub voter_gov_token: InterfaceAccount<'info, TokenAccount>,
 
     #[account(
         init_if_needed,
         payer = voter,
-        token::mint = config.governance_token_mint,
+        token::mint = governance_token_mint,
         token::authority = proposal,
         seeds = [b"vote_escrow", proposal.key().as_ref()],
         bump
     )]
-    pub vote_escrow: Account<'info, TokenAccount>,
+    pub vote_escrow: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(address = config.governance_token_mint)]
+    pub governance_token_mint: InterfaceAccount<'info, Mint>,
 
-    pub token_program: Program<'info, Token>,
+    pub token_program: Interface<'info, TokenInterface>,
     pub system_program: Program<'info, System>,
     pub rent: Sysvar<'info, Rent>,
 }
@@ -0,0 +1,128 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{self, Mint, TokenAccount, TokenInterface, TransferChecked};
+use crate::state::{Config, TreasuryProposal, ProposalStatus, VoteRecord};
+use crate::errors::ErrorCode;
+
+pub fn handler(
+    ctx: Context<VoteTreasurySpend>,
+    vote_weight: u64,
+    support: bool,
+) -> Result<()> {
+    let config = &ctx.accounts.config;
+    let proposal = &mut ctx.accounts.proposal;
+
+    // Validations
+    require!(
+        !config.is_paused(Config::PAUSE_PROPOSALS),
+        ErrorCode::ProtocolPaused
+    );
+    require!(
+        proposal.status == ProposalStatus::Active,
+        ErrorCode::ProposalNotActive
+    );
+
+    let clock = Clock::get()?;
+    require!(
+        clock.unix_timestamp < proposal.voting_ends_at,
+        ErrorCode::VotingEnded
+    );
+
+    // Lock voter tokens behind the proposal
+    token_interface::transfer_checked(
+        CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            TransferChecked {
+                from: ctx.accounts.voter_gov_token.to_account_info(),
+                mint: ctx.accounts.governance_token_mint.to_account_info(),
+                to: ctx.accounts.vote_escrow.to_account_info(),
+                authority: ctx.accounts.voter.to_account_info(),
+            },
+        ),
+        vote_weight,
+        ctx.accounts.governance_token_mint.decimals,
+    )?;
+
+    let vote_record = &mut ctx.accounts.vote_record;
+    vote_record.voter = ctx.accounts.voter.key();
+    vote_record.proposal = proposal.key();
+    vote_record.weight = vote_weight;
+    vote_record.support = support;
+    vote_record.voted_at = clock.unix_timestamp;
+    vote_record.bump = ctx.bumps.vote_record;
+
+    if support {
+        proposal.votes_for = proposal.votes_for.checked_add(vote_weight).unwrap();
+    } else {
+        proposal.votes_against = proposal.votes_against.checked_add(vote_weight).unwrap();
+    }
+
+    emit!(TreasuryVoteCast {
+        proposal: proposal.key(),
+        voter: ctx.accounts.voter.key(),
+        weight: vote_weight,
+        support,
+        votes_for: proposal.votes_for,
+        votes_against: proposal.votes_against,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct VoteTreasurySpend<'info> {
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, Config>,
+
+    #[account(mut)]
+    pub proposal: Account<'info, TreasuryProposal>,
+
+    #[account(
+        init,
+        payer = voter,
+        space = 8 + VoteRecord::INIT_SPACE,
+        seeds = [
+            b"vote",
+            proposal.key().as_ref(),
+            voter.key().as_ref()
+        ],
+        bump
+    )]
+    pub vote_record: Account<'info, VoteRecord>,
+
+    #[account(mut)]
+    pub voter: Signer<'info>,
+
+    #[account(
+        mut,
+        constraint = voter_gov_token.owner == voter.key(),
+        constraint = voter_gov_token.mint == config.governance_token_mint
+    )]
+    pub voter_gov_token: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        init_if_needed,
+        payer = voter,
+        token::mint = governance_token_mint,
+        token::authority = proposal,
+        seeds = [b"vote_escrow", proposal.key().as_ref()],
+        bump
+    )]
+    pub vote_escrow: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(address = config.governance_token_mint)]
+    pub governance_token_mint: InterfaceAccount<'info, Mint>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+#[event]
+pub struct TreasuryVoteCast {
+    pub proposal: Pubkey,
+    pub voter: Pubkey,
+    pub weight: u64,
+    pub support: bool,
+    pub votes_for: u64,
+    pub votes_against: u64,
+}
@@ -0,0 +1,147 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{self, Mint, TokenAccount, TokenInterface, TransferChecked};
+use crate::state::{Config, Gauge, Market, MarketStatus, RewardPosition};
+use crate::errors::ErrorCode;
+use crate::utils::{refund_value, transfer_fee};
+
+pub fn handler(ctx: Context<WithdrawCreatorLiquidity>) -> Result<()> {
+    let config = &ctx.accounts.config;
+    let market = &mut ctx.accounts.market;
+
+    // Validations
+    require!(
+        !config.is_paused(Config::PAUSE_CLAIMS),
+        ErrorCode::ProtocolPaused
+    );
+    require!(!market.halted, ErrorCode::MarketHalted);
+    let yes_price = market.refund_price.ok_or(ErrorCode::MarketNotCancelled)?;
+
+    // Every outstanding token stays refundable; the creator gets what is left
+    // of the vault after that, up to the liquidity they put in
+    let decimals = ctx.accounts.collateral_mint.decimals;
+    let reserved = refund_value(ctx.accounts.yes_token_mint.supply, decimals, yes_price, true)?
+        .checked_add(refund_value(ctx.accounts.no_token_mint.supply, decimals, yes_price, false)?)
+        .ok_or(ErrorCode::MathOverflow)?;
+    let amount = ctx
+        .accounts
+        .liquidity_vault
+        .amount
+        .saturating_sub(reserved)
+        .min(market.creator_liquidity);
+    require!(amount > 0, ErrorCode::ZeroAmount);
+
+    let market_id = market.market_id.to_le_bytes();
+    let bump = [market.bump];
+    let seeds = &[b"market".as_ref(), &market_id, &bump];
+    let signer = &[&seeds[..]];
+
+    token_interface::transfer_checked(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            TransferChecked {
+                from: ctx.accounts.liquidity_vault.to_account_info(),
+                mint: ctx.accounts.collateral_mint.to_account_info(),
+                to: ctx.accounts.creator_collateral.to_account_info(),
+                authority: market.to_account_info(),
+            },
+            signer,
+        ),
+        amount,
+        decimals,
+    )?;
+
+    market.creator_liquidity -= amount;
+
+    // Withdrawn liquidity stops earning LP emissions
+    if let (Some(gauge), Some(position)) = (
+        ctx.accounts.gauge.as_mut(),
+        ctx.accounts.reward_position.as_mut(),
+    ) {
+        require!(gauge.market == market.key(), ErrorCode::InvalidRewardPosition);
+        require!(
+            position.gauge == gauge.key() && position.owner == market.creator,
+            ErrorCode::InvalidRewardPosition
+        );
+
+        gauge.update(market, Clock::get()?.unix_timestamp)?;
+        position.settle(gauge)?;
+        position.sync_lp_shares(gauge, market)?;
+    }
+
+    let received = amount
+        .checked_sub(transfer_fee(&ctx.accounts.collateral_mint.to_account_info(), amount)?)
+        .ok_or(ErrorCode::MathUnderflow)?;
+
+    emit!(CreatorLiquidityWithdrawn {
+        market: market.key(),
+        creator: market.creator,
+        amount,
+        received,
+        remaining: market.creator_liquidity,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct WithdrawCreatorLiquidity<'info> {
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        mut,
+        has_one = creator @ ErrorCode::Unauthorized,
+        constraint = market.status == MarketStatus::Cancelled @ ErrorCode::MarketNotCancelled
+    )]
+    pub market: Account<'info, Market>,
+
+    pub creator: Signer<'info>,
+
+    #[account(
+        mut,
+        constraint = creator_collateral.owner == creator.key(),
+        constraint = creator_collateral.mint == market.collateral_mint @ ErrorCode::InvalidCollateral
+    )]
+    pub creator_collateral: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        seeds = [b"vault", market.key().as_ref()],
+        bump
+    )]
+    pub liquidity_vault: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(address = market.collateral_mint @ ErrorCode::InvalidCollateral)]
+    pub collateral_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        seeds = [b"yes_mint", market.key().as_ref()],
+        bump
+    )]
+    pub yes_token_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        seeds = [b"no_mint", market.key().as_ref()],
+        bump
+    )]
+    pub no_token_mint: InterfaceAccount<'info, Mint>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+
+    /// Optional: the creator's reward position, re-synced to the remaining liquidity
+    #[account(mut)]
+    pub gauge: Option<Account<'info, Gauge>>,
+
+    #[account(mut)]
+    pub reward_position: Option<Account<'info, RewardPosition>>,
+}
+
+#[event]
+pub struct CreatorLiquidityWithdrawn {
+    pub market: Pubkey,
+    pub creator: Pubkey,
+    pub amount: u64,
+    pub received: u64,
+    /// Creator liquidity still in the vault
+    pub remaining: u64,
+}
@@ -0,0 +1,99 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{self, Mint, TokenAccount, TokenInterface, TransferChecked};
+use crate::state::{Config, TreasuryProposal, ProposalStatus, VoteRecord};
+use crate::errors::ErrorCode;
+
+pub fn handler(ctx: Context<WithdrawTreasuryVote>) -> Result<()> {
+    let proposal = &ctx.accounts.proposal;
+
+    require!(
+        !ctx.accounts.config.is_paused(Config::PAUSE_CLAIMS),
+        ErrorCode::ProtocolPaused
+    );
+    require!(
+        proposal.status != ProposalStatus::Active,
+        ErrorCode::ProposalNotFinalized
+    );
+
+    let id = proposal.id.to_le_bytes();
+    let seeds = &[b"treasury_proposal".as_ref(), &id, &[proposal.bump]];
+    let signer = &[&seeds[..]];
+
+    let weight = ctx.accounts.vote_record.weight;
+    token_interface::transfer_checked(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            TransferChecked {
+                from: ctx.accounts.vote_escrow.to_account_info(),
+                mint: ctx.accounts.governance_token_mint.to_account_info(),
+                to: ctx.accounts.voter_gov_token.to_account_info(),
+                authority: proposal.to_account_info(),
+            },
+            signer,
+        ),
+        weight,
+        ctx.accounts.governance_token_mint.decimals,
+    )?;
+
+    emit!(TreasuryVoteWithdrawn {
+        proposal: proposal.key(),
+        voter: ctx.accounts.voter.key(),
+        weight,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct WithdrawTreasuryVote<'info> {
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        seeds = [b"treasury_proposal".as_ref(), &proposal.id.to_le_bytes()],
+        bump = proposal.bump
+    )]
+    pub proposal: Account<'info, TreasuryProposal>,
+
+    /// Closed on withdrawal so the weight can only be returned once
+    #[account(
+        mut,
+        close = voter,
+        seeds = [
+            b"vote",
+            proposal.key().as_ref(),
+            voter.key().as_ref()
+        ],
+        bump = vote_record.bump
+    )]
+    pub vote_record: Account<'info, VoteRecord>,
+
+    #[account(mut)]
+    pub voter: Signer<'info>,
+
+    #[account(
+        mut,
+        constraint = voter_gov_token.owner == voter.key(),
+        constraint = voter_gov_token.mint == config.governance_token_mint
+    )]
+    pub voter_gov_token: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        seeds = [b"vote_escrow", proposal.key().as_ref()],
+        bump
+    )]
+    pub vote_escrow: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(address = config.governance_token_mint)]
+    pub governance_token_mint: InterfaceAccount<'info, Mint>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+#[event]
+pub struct TreasuryVoteWithdrawn {
+    pub proposal: Pubkey,
+    pub voter: Pubkey,
+    pub weight: u64,
+}
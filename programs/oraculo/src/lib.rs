@@ -6,8 +6,11 @@ pub mod state;
 pub mod instructions;
 pub mod errors;
 pub mod utils;
+pub mod oracle;
+pub mod resolution;
 
 use instructions::*;
+use state::{OffChainMetadata, OrderKind, OrderType};
 
 #[program]
 pub mod oraculo {
@@ -20,6 +23,8 @@ pub mod oraculo {
         proposal_stake: u64,
         quorum: u64,
         supermajority_percent: u8,
+        guardian: Pubkey,
+        protocol_fee_bps: u16,
     ) -> Result<()> {
         instructions::initialize::handler(
             ctx,
@@ -27,28 +32,17 @@ pub mod oraculo {
             proposal_stake,
             quorum,
             supermajority_percent,
+            guardian,
+            protocol_fee_bps,
         )
     }
 
     /// Create prediction market with proof of liquidity
     pub fn create_market(
         ctx: Context<CreateMarket>,
-        question: String,
-        description: String,
-        category: u8,
-        end_time: i64,
-        resolution_source: String,
-        initial_liquidity: u64,
+        params: CreateMarketParams,
     ) -> Result<()> {
-        instructions::create_market::handler(
-            ctx,
-            question,
-            description,
-            category,
-            end_time,
-            resolution_source,
-            initial_liquidity,
-        )
+        instructions::create_market::handler(ctx, params)
     }
 
     /// Place bet using bonding curve
@@ -56,8 +50,9 @@ pub mod oraculo {
         ctx: Context<PlaceBet>,
         amount: u64,
         bet_on_yes: bool,
+        max_cost: u64,
     ) -> Result<()> {
-        instructions::place_bet::handler(ctx, amount, bet_on_yes)
+        instructions::place_bet::handler(ctx, amount, bet_on_yes, max_cost)
     }
 
     /// DAO proposes resolution with stake
@@ -92,4 +87,368 @@ pub mod oraculo {
     ) -> Result<()> {
         instructions::claim_winnings::handler(ctx, amount)
     }
+
+    /// Rotate the guardian allowed to pause the protocol
+    pub fn set_guardian(
+        ctx: Context<SetGuardian>,
+        new_guardian: Pubkey,
+    ) -> Result<()> {
+        instructions::set_guardian::handler(ctx, new_guardian)
+    }
+
+    /// Guardian sets the protocol pause bitmap
+    pub fn set_pause_flags(
+        ctx: Context<SetPauseFlags>,
+        paused: u8,
+    ) -> Result<()> {
+        instructions::set_pause_flags::handler(ctx, paused)
+    }
+
+    /// Guardian halts or resumes a single market
+    pub fn set_market_halted(
+        ctx: Context<SetMarketHalted>,
+        halted: bool,
+    ) -> Result<()> {
+        instructions::set_market_halted::handler(ctx, halted)
+    }
+
+    /// Open the treasury vault for an SPL mint
+    pub fn open_treasury_vault(
+        ctx: Context<OpenTreasuryVault>,
+    ) -> Result<()> {
+        instructions::open_treasury_vault::handler(ctx)
+    }
+
+    /// Propose spending from a treasury vault
+    pub fn propose_treasury_spend(
+        ctx: Context<ProposeTreasurySpend>,
+        amount: u64,
+        memo: String,
+    ) -> Result<()> {
+        instructions::propose_treasury_spend::handler(ctx, amount, memo)
+    }
+
+    /// DAO members vote on a treasury spend
+    pub fn vote_treasury_spend(
+        ctx: Context<VoteTreasurySpend>,
+        vote_weight: u64,
+        support: bool,
+    ) -> Result<()> {
+        instructions::vote_treasury_spend::handler(ctx, vote_weight, support)
+    }
+
+    /// Execute a passed treasury spend once its timelock has expired
+    pub fn treasury_spend(
+        ctx: Context<TreasurySpend>,
+    ) -> Result<()> {
+        instructions::treasury_spend::handler(ctx)
+    }
+
+    /// Return a voter's escrowed tokens once a treasury spend is finalized
+    pub fn withdraw_treasury_vote(
+        ctx: Context<WithdrawTreasuryVote>,
+    ) -> Result<()> {
+        instructions::withdraw_treasury_vote::handler(ctx)
+    }
+
+    /// Return the proposer's stake once a treasury spend is finalized
+    pub fn reclaim_treasury_stake(
+        ctx: Context<ReclaimTreasuryStake>,
+    ) -> Result<()> {
+        instructions::reclaim_treasury_stake::handler(ctx)
+    }
+
+    /// Governance starts liquidity mining emissions on a market
+    pub fn create_gauge(
+        ctx: Context<CreateGauge>,
+        emission_bucket: u8,
+        emission_rate: u64,
+        lp_weight_bps: u16,
+        start_time: i64,
+        end_time: i64,
+    ) -> Result<()> {
+        instructions::create_gauge::handler(
+            ctx,
+            emission_bucket,
+            emission_rate,
+            lp_weight_bps,
+            start_time,
+            end_time,
+        )
+    }
+
+    /// Governance changes a gauge's emission rate, split or end time
+    pub fn update_gauge(
+        ctx: Context<UpdateGauge>,
+        emission_rate: u64,
+        lp_weight_bps: u16,
+        end_time: i64,
+    ) -> Result<()> {
+        instructions::update_gauge::handler(ctx, emission_rate, lp_weight_bps, end_time)
+    }
+
+    /// Open a reward position on a market's gauge
+    pub fn open_reward_position(
+        ctx: Context<OpenRewardPosition>,
+    ) -> Result<()> {
+        instructions::open_reward_position::handler(ctx)
+    }
+
+    /// Claim accrued liquidity mining rewards as governance tokens
+    pub fn claim_rewards(
+        ctx: Context<ClaimRewards>,
+    ) -> Result<()> {
+        instructions::claim_rewards::handler(ctx)
+    }
+
+    /// Governance allows a mint to be used as market collateral
+    pub fn register_collateral(
+        ctx: Context<RegisterCollateral>,
+        min_bet: u64,
+        min_liquidity: u64,
+    ) -> Result<()> {
+        instructions::register_collateral::handler(ctx, min_bet, min_liquidity)
+    }
+
+    /// Governance changes a collateral's limits or disables it for new markets
+    pub fn update_collateral(
+        ctx: Context<UpdateCollateral>,
+        min_bet: u64,
+        min_liquidity: u64,
+        enabled: bool,
+    ) -> Result<()> {
+        instructions::update_collateral::handler(ctx, min_bet, min_liquidity, enabled)
+    }
+
+    /// Governance registers a market category or subcategory
+    pub fn create_category(
+        ctx: Context<CreateCategory>,
+        id: u16,
+        name: String,
+        parent: Option<u16>,
+        min_liquidity: u64,
+        fee_bps: Option<u16>,
+    ) -> Result<()> {
+        instructions::create_category::handler(ctx, id, name, parent, min_liquidity, fee_bps)
+    }
+
+    /// Governance renames a category, changes its limits or disables it for new markets
+    pub fn update_category(
+        ctx: Context<UpdateCategory>,
+        name: String,
+        min_liquidity: u64,
+        fee_bps: Option<u16>,
+        enabled: bool,
+    ) -> Result<()> {
+        instructions::update_category::handler(ctx, name, min_liquidity, fee_bps, enabled)
+    }
+
+    /// Resolve a price threshold market from its oracle feed (permissionless)
+    pub fn resolve_from_oracle(
+        ctx: Context<ResolveFromOracle>,
+    ) -> Result<()> {
+        instructions::resolve_from_oracle::handler(ctx)
+    }
+
+    /// Designated reporter bonds governance tokens ahead of reporting
+    pub fn post_reporter_bond(
+        ctx: Context<PostReporterBond>,
+    ) -> Result<()> {
+        instructions::post_reporter_bond::handler(ctx)
+    }
+
+    /// Designated reporter reports the outcome during its exclusive window
+    pub fn report_outcome(
+        ctx: Context<ReportOutcome>,
+        outcome: bool,
+    ) -> Result<()> {
+        instructions::report_outcome::handler(ctx, outcome)
+    }
+
+    /// Settle an unchallenged report and return the reporter's bond
+    pub fn finalize_report(
+        ctx: Context<FinalizeReport>,
+    ) -> Result<()> {
+        instructions::finalize_report::handler(ctx)
+    }
+
+    /// Hand a missed or disputed report to a DAO vote, forfeiting the bond
+    pub fn fall_back_to_dao(
+        ctx: Context<FallBackToDao>,
+    ) -> Result<()> {
+        instructions::fall_back_to_dao::handler(ctx)
+    }
+
+    /// Propose resolving before end_time, halting trading while the DAO votes
+    pub fn propose_early_resolution(
+        ctx: Context<ProposeEarlyResolution>,
+        outcome: bool,
+        evidence: String,
+        event_time: i64,
+        refund_late_bets: bool,
+    ) -> Result<()> {
+        instructions::propose_early_resolution::handler(ctx, outcome, evidence, event_time, refund_late_bets)
+    }
+
+    /// Redeem losing tokens for their share of bets placed after an early-resolved event
+    pub fn refund_late_bets(
+        ctx: Context<RefundLateBets>,
+        amount: u64,
+    ) -> Result<()> {
+        instructions::refund_late_bets::handler(ctx, amount)
+    }
+
+    /// Governance sets resolution period bounds and the unresolved-market fallback
+    pub fn set_resolution_periods(
+        ctx: Context<SetResolutionPeriods>,
+        params: ResolutionPeriodsParams,
+    ) -> Result<()> {
+        instructions::set_resolution_periods::handler(ctx, params)
+    }
+
+    /// Extend or cancel a market left unresolved past its resolution time (permissionless).
+    /// Takes each open proposal, its stake vault and the proposer's governance
+    /// token account as remaining accounts; their stakes are returned.
+    pub fn expire_resolution<'info>(
+        ctx: Context<'_, '_, 'info, 'info, ExpireResolution<'info>>,
+    ) -> Result<()> {
+        instructions::expire_resolution::handler(ctx)
+    }
+
+    /// Return or slash the stake of a proposal left open when its market settled (permissionless)
+    pub fn reclaim_resolution_stake(
+        ctx: Context<ReclaimResolutionStake>,
+    ) -> Result<()> {
+        instructions::reclaim_resolution_stake::handler(ctx)
+    }
+
+    /// Open a market's order book and its escrow vaults (permissionless)
+    pub fn create_order_book(
+        ctx: Context<CreateOrderBook>,
+    ) -> Result<()> {
+        instructions::create_order_book::handler(ctx)
+    }
+
+    /// Place a limit, post-only or immediate-or-cancel order on the book
+    pub fn place_order(
+        ctx: Context<PlaceOrder>,
+        kind: OrderKind,
+        order_type: OrderType,
+        price: u16,
+        lots: u64,
+    ) -> Result<()> {
+        instructions::place_order::handler(ctx, kind, order_type, price, lots)
+    }
+
+    /// Cancel a resting order, releasing its escrow for settlement
+    pub fn cancel_order(
+        ctx: Context<CancelOrder>,
+        order_id: u64,
+    ) -> Result<()> {
+        instructions::cancel_order::handler(ctx, order_id)
+    }
+
+    /// Crank queued maker fills into the makers' open orders accounts
+    pub fn consume_fills<'info>(
+        ctx: Context<'_, '_, 'info, 'info, ConsumeFills<'info>>,
+        limit: u8,
+    ) -> Result<()> {
+        instructions::consume_fills::handler(ctx, limit)
+    }
+
+    /// Pay out an open orders account's free balances to its owner (permissionless)
+    pub fn settle_funds(
+        ctx: Context<SettleFunds>,
+    ) -> Result<()> {
+        instructions::settle_funds::handler(ctx)
+    }
+
+    /// Place bet split across the order book and AMM at the lowest total cost
+    pub fn place_bet_routed(
+        ctx: Context<PlaceBetRouted>,
+        amount: u64,
+        bet_on_yes: bool,
+        max_cost: u64,
+    ) -> Result<()> {
+        instructions::place_bet_routed::handler(ctx, amount, bet_on_yes, max_cost)
+    }
+
+    /// Return a market's implied probabilities, TWAP and pool state (read-only, for CPI)
+    pub fn get_market_price(
+        ctx: Context<GetMarketPrice>,
+        twap_window: Option<i64>,
+    ) -> Result<MarketPrice> {
+        instructions::get_market_price::handler(ctx, twap_window)
+    }
+
+    /// Register a market's outcome token as collateral for conditional markets (permissionless)
+    pub fn register_outcome_collateral(
+        ctx: Context<RegisterOutcomeCollateral>,
+    ) -> Result<()> {
+        instructions::register_outcome_collateral::handler(ctx)
+    }
+
+    /// Cancel a conditional market whose parent resolved against its condition (permissionless)
+    pub fn cancel_conditional_market(
+        ctx: Context<CancelConditionalMarket>,
+    ) -> Result<()> {
+        instructions::cancel_conditional_market::handler(ctx)
+    }
+
+    /// Refund outcome tokens of a cancelled market at its TWAP when cancelled
+    pub fn claim_refund(
+        ctx: Context<ClaimRefund>,
+        amount: u64,
+        outcome: bool,
+    ) -> Result<()> {
+        instructions::claim_refund::handler(ctx, amount, outcome)
+    }
+
+    /// Creator withdraws their liquidity from a cancelled market, less what its tokens can still claim
+    pub fn withdraw_creator_liquidity(
+        ctx: Context<WithdrawCreatorLiquidity>,
+    ) -> Result<()> {
+        instructions::withdraw_creator_liquidity::handler(ctx)
+    }
+
+    /// Create a template for a recurring market series, funded from its vault
+    pub fn create_market_template(
+        ctx: Context<CreateMarketTemplate>,
+        params: CreateMarketTemplateParams,
+    ) -> Result<()> {
+        instructions::create_market_template::handler(ctx, params)
+    }
+
+    /// Create the next due market in a template's series (permissionless)
+    pub fn spawn_from_template(
+        ctx: Context<SpawnFromTemplate>,
+    ) -> Result<()> {
+        instructions::spawn_from_template::handler(ctx)
+    }
+
+    /// Pause or resume spawning from a template
+    pub fn set_template_active(
+        ctx: Context<SetTemplateActive>,
+        active: bool,
+    ) -> Result<()> {
+        instructions::set_template_active::handler(ctx, active)
+    }
+
+    /// Close a paused template, returning its vault's collateral to the creator
+    pub fn close_template(
+        ctx: Context<CloseTemplate>,
+    ) -> Result<()> {
+        instructions::close_template::handler(ctx)
+    }
+
+    /// Creator fixes a market's text or metadata before the first bet
+    pub fn edit_market(
+        ctx: Context<EditMarket>,
+        question: Option<String>,
+        description: Option<String>,
+        resolution_source: Option<String>,
+        metadata: Option<OffChainMetadata>,
+    ) -> Result<()> {
+        instructions::edit_market::handler(ctx, question, description, resolution_source, metadata)
+    }
 }
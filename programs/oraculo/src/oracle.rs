@@ -0,0 +1,137 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::pubkey;
+use crate::errors::ErrorCode;
+
+/// Pyth oracle programs (mainnet-beta, devnet) allowed to own price feeds
+pub const PYTH_PROGRAM_IDS: [Pubkey; 2] = [
+    pubkey!("FsJ3A3u2vn5cTVofAjvy6y5kwABJAqYWpe4975bi2epH"),
+    pubkey!("gSbePebfvPy7tRqimPoVecS2UsBvYv46ynrzWocc92s"),
+];
+
+const MAGIC: u32 = 0xa1b2_c3d4;
+const VERSION: u32 = 2;
+const ACCOUNT_TYPE_PRICE: u32 = 3;
+const STATUS_TRADING: u32 = 1;
+
+// Byte offsets into a Pyth v2 price account
+const OFFSET_MAGIC: usize = 0;
+const OFFSET_VERSION: usize = 4;
+const OFFSET_ACCOUNT_TYPE: usize = 8;
+const OFFSET_EXPO: usize = 20;
+const OFFSET_TIMESTAMP: usize = 96;
+const OFFSET_AGG_PRICE: usize = 208;
+const OFFSET_AGG_CONF: usize = 216;
+const OFFSET_AGG_STATUS: usize = 224;
+
+/// Size of the price account header through the aggregate price
+pub const PRICE_ACCOUNT_MIN_LEN: usize = 240;
+
+/// Aggregate price read from a Pyth-format price account
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct OraclePrice {
+    pub price: i64,
+    pub conf: u64,
+    pub expo: i32,
+    pub publish_time: i64,
+}
+
+impl OraclePrice {
+    /// Parse the aggregate price, rejecting anything not currently trading
+    pub fn parse(data: &[u8]) -> Result<Self> {
+        require!(data.len() >= PRICE_ACCOUNT_MIN_LEN, ErrorCode::InvalidOracleAccount);
+        require!(
+            read_u32(data, OFFSET_MAGIC) == MAGIC
+                && read_u32(data, OFFSET_VERSION) == VERSION
+                && read_u32(data, OFFSET_ACCOUNT_TYPE) == ACCOUNT_TYPE_PRICE,
+            ErrorCode::InvalidOracleAccount
+        );
+        require!(
+            read_u32(data, OFFSET_AGG_STATUS) == STATUS_TRADING,
+            ErrorCode::OraclePriceUnavailable
+        );
+
+        Ok(Self {
+            price: read_u64(data, OFFSET_AGG_PRICE) as i64,
+            conf: read_u64(data, OFFSET_AGG_CONF),
+            expo: read_u32(data, OFFSET_EXPO) as i32,
+            publish_time: read_u64(data, OFFSET_TIMESTAMP) as i64,
+        })
+    }
+
+    /// Load a price from an account owned by a Pyth program
+    pub fn load(account: &AccountInfo) -> Result<Self> {
+        require!(
+            PYTH_PROGRAM_IDS.contains(account.owner),
+            ErrorCode::InvalidOracleAccount
+        );
+        Self::parse(&account.try_borrow_data()?)
+    }
+
+    /// Whether the confidence interval is within `max_conf_bps` of the price
+    pub fn confidence_within(&self, max_conf_bps: u16) -> bool {
+        (self.conf as u128) * 10_000 <= (self.price.unsigned_abs() as u128) * max_conf_bps as u128
+    }
+}
+
+fn read_u32(data: &[u8], offset: usize) -> u32 {
+    u32::from_le_bytes(data[offset..offset + 4].try_into().unwrap())
+}
+
+fn read_u64(data: &[u8], offset: usize) -> u64 {
+    u64::from_le_bytes(data[offset..offset + 8].try_into().unwrap())
+}
+
+#[cfg(test)]
+pub(crate) fn mock_price_account(price: i64, conf: u64, expo: i32, publish_time: i64) -> Vec<u8> {
+    let mut data = vec![0u8; PRICE_ACCOUNT_MIN_LEN];
+    data[OFFSET_MAGIC..OFFSET_MAGIC + 4].copy_from_slice(&MAGIC.to_le_bytes());
+    data[OFFSET_VERSION..OFFSET_VERSION + 4].copy_from_slice(&VERSION.to_le_bytes());
+    data[OFFSET_ACCOUNT_TYPE..OFFSET_ACCOUNT_TYPE + 4]
+        .copy_from_slice(&ACCOUNT_TYPE_PRICE.to_le_bytes());
+    data[OFFSET_EXPO..OFFSET_EXPO + 4].copy_from_slice(&expo.to_le_bytes());
+    data[OFFSET_TIMESTAMP..OFFSET_TIMESTAMP + 8].copy_from_slice(&publish_time.to_le_bytes());
+    data[OFFSET_AGG_PRICE..OFFSET_AGG_PRICE + 8].copy_from_slice(&price.to_le_bytes());
+    data[OFFSET_AGG_CONF..OFFSET_AGG_CONF + 8].copy_from_slice(&conf.to_le_bytes());
+    data[OFFSET_AGG_STATUS..OFFSET_AGG_STATUS + 4].copy_from_slice(&STATUS_TRADING.to_le_bytes());
+    data
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_price_account() {
+        // BTC at $101,234.56 with expo -8
+        let data = mock_price_account(10_123_456_000_000, 5_000_000_000, -8, 1_767_225_600);
+        let price = OraclePrice::parse(&data).unwrap();
+        assert_eq!(price.price, 10_123_456_000_000);
+        assert_eq!(price.conf, 5_000_000_000);
+        assert_eq!(price.expo, -8);
+        assert_eq!(price.publish_time, 1_767_225_600);
+
+        // $50 confidence on ~$101K is about 5 bps
+        assert!(price.confidence_within(5));
+        assert!(!price.confidence_within(4));
+    }
+
+    #[test]
+    fn test_parse_rejects_bad_accounts() {
+        let good = mock_price_account(100, 1, -2, 0);
+
+        assert!(OraclePrice::parse(&good[..PRICE_ACCOUNT_MIN_LEN - 1]).is_err());
+
+        let mut bad_magic = good.clone();
+        bad_magic[OFFSET_MAGIC] ^= 0xff;
+        assert!(OraclePrice::parse(&bad_magic).is_err());
+
+        let mut not_price = good.clone();
+        not_price[OFFSET_ACCOUNT_TYPE..OFFSET_ACCOUNT_TYPE + 4].copy_from_slice(&2u32.to_le_bytes());
+        assert!(OraclePrice::parse(&not_price).is_err());
+
+        // Halted / unknown status
+        let mut halted = good.clone();
+        halted[OFFSET_AGG_STATUS..OFFSET_AGG_STATUS + 4].copy_from_slice(&2u32.to_le_bytes());
+        assert!(OraclePrice::parse(&halted).is_err());
+    }
+}
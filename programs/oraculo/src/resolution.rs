@@ -0,0 +1,538 @@
+use anchor_lang::prelude::*;
+use crate::errors::ErrorCode;
+use crate::state::{Config, Market, Proposal, ResolutionMode};
+
+/// Share of votes an early-resolution proposal needs, if above the config's
+pub const EARLY_SUPERMAJORITY_PERCENT: u8 = 80;
+
+/// Exclusive window after `end_time` for a designated reporter to report
+pub const REPORTING_WINDOW: i64 = 24 * 60 * 60;
+
+/// Propose/vote/finalise rules for one resolution mode
+pub trait Resolver {
+    /// Seconds a new proposal stays open for votes before it can be finalised
+    fn voting_period(&self, market: &Market) -> i64;
+
+    /// Check that `proposer` may propose an outcome for `market` at `now`
+    fn check_propose(&self, market: &Market, proposer: &Pubkey, now: i64) -> Result<()>;
+
+    /// Record any market state the new proposal changes
+    fn on_propose(&self, _market: &mut Market) {}
+
+    /// Check that token holders may vote on proposals for `market`
+    fn check_vote(&self, market: &Market) -> Result<()>;
+
+    /// Final outcome of `proposal`, or an error if it cannot be finalised yet
+    fn finalise(&self, config: &Config, market: &Market, proposal: &Proposal, now: i64) -> Result<bool>;
+}
+
+pub fn resolver_for(mode: ResolutionMode) -> &'static dyn Resolver {
+    match mode {
+        ResolutionMode::DaoVote => &DaoVote,
+        ResolutionMode::Optimistic => &Optimistic,
+        ResolutionMode::PriceOracle => &PriceOracle,
+        ResolutionMode::DesignatedReporter => &DesignatedReporter,
+        ResolutionMode::MultisigCommittee => &MultisigCommittee,
+    }
+}
+
+fn check_market_ended(market: &Market, now: i64) -> Result<()> {
+    require!(now > market.end_time, ErrorCode::MarketNotEnded);
+    Ok(())
+}
+
+fn check_named_resolver(market: &Market, proposer: &Pubkey) -> Result<()> {
+    require!(
+        market.resolver.as_ref() == Some(proposer),
+        ErrorCode::Unauthorized
+    );
+    Ok(())
+}
+
+/// Outcome of an early-resolution vote, or `None` if it was rejected
+pub fn early_resolution_outcome(config: &Config, proposal: &Proposal, now: i64) -> Result<Option<bool>> {
+    require!(now >= proposal.voting_ends_at, ErrorCode::VotingNotEnded);
+
+    let total_votes = proposal
+        .votes_for
+        .checked_add(proposal.votes_against)
+        .ok_or(ErrorCode::MathOverflow)?;
+    let supporting = if proposal.outcome {
+        proposal.votes_for
+    } else {
+        proposal.votes_against
+    };
+    let threshold = (total_votes as u128)
+        .checked_mul(EARLY_SUPERMAJORITY_PERCENT.max(config.supermajority_percent) as u128)
+        .ok_or(ErrorCode::MathOverflow)?
+        / 100;
+
+    let passed = total_votes > 0
+        && total_votes >= config.quorum
+        && supporting as u128 >= threshold;
+    Ok(passed.then_some(proposal.outcome))
+}
+
+/// Whether a non-early `proposal` could be executed at `now`. A designated
+/// reporter's proposals count as they would after falling back to a DAO vote.
+pub fn proposal_passes(config: &Config, market: &Market, proposal: &Proposal, now: i64) -> bool {
+    let mode = match market.resolution_mode {
+        ResolutionMode::DesignatedReporter => ResolutionMode::DaoVote,
+        mode => mode,
+    };
+    resolver_for(mode).finalise(config, market, proposal, now).is_ok()
+}
+
+/// Token-weighted vote with quorum and supermajority
+pub struct DaoVote;
+
+impl Resolver for DaoVote {
+    fn voting_period(&self, market: &Market) -> i64 {
+        market.voting_period
+    }
+
+    fn check_propose(&self, market: &Market, _proposer: &Pubkey, now: i64) -> Result<()> {
+        check_market_ended(market, now)
+    }
+
+    fn check_vote(&self, _market: &Market) -> Result<()> {
+        Ok(())
+    }
+
+    fn finalise(&self, config: &Config, _market: &Market, proposal: &Proposal, now: i64) -> Result<bool> {
+        require!(now >= proposal.voting_ends_at, ErrorCode::VotingNotEnded);
+
+        // Check quorum
+        let total_votes = proposal
+            .votes_for
+            .checked_add(proposal.votes_against)
+            .ok_or(ErrorCode::MathOverflow)?;
+        require!(total_votes >= config.quorum, ErrorCode::QuorumNotReached);
+
+        // Check supermajority
+        let winner_votes = proposal.votes_for.max(proposal.votes_against);
+        let supermajority_threshold = (total_votes as u128)
+            .checked_mul(config.supermajority_percent as u128)
+            .ok_or(ErrorCode::MathOverflow)?
+            / 100;
+        require!(
+            winner_votes as u128 >= supermajority_threshold,
+            ErrorCode::NoSupermajority
+        );
+
+        Ok(proposal.votes_for > proposal.votes_against)
+    }
+}
+
+/// Proposal stands unless challenged, disputes go to a DAO vote
+pub struct Optimistic;
+
+impl Resolver for Optimistic {
+    fn voting_period(&self, market: &Market) -> i64 {
+        market.voting_period
+    }
+
+    fn check_propose(&self, market: &Market, _proposer: &Pubkey, now: i64) -> Result<()> {
+        check_market_ended(market, now)
+    }
+
+    fn check_vote(&self, _market: &Market) -> Result<()> {
+        Ok(())
+    }
+
+    fn finalise(&self, config: &Config, market: &Market, proposal: &Proposal, now: i64) -> Result<bool> {
+        require!(now >= proposal.voting_ends_at, ErrorCode::VotingNotEnded);
+
+        if proposal.votes_against == 0 {
+            return Ok(proposal.outcome);
+        }
+        DaoVote.finalise(config, market, proposal, now)
+    }
+}
+
+/// Settled by `resolve_from_oracle`, never by proposals
+pub struct PriceOracle;
+
+impl Resolver for PriceOracle {
+    fn voting_period(&self, _market: &Market) -> i64 {
+        0
+    }
+
+    fn check_propose(&self, _market: &Market, _proposer: &Pubkey, _now: i64) -> Result<()> {
+        err!(ErrorCode::ResolvedByOracle)
+    }
+
+    fn check_vote(&self, _market: &Market) -> Result<()> {
+        err!(ErrorCode::ResolvedByOracle)
+    }
+
+    fn finalise(&self, _config: &Config, _market: &Market, _proposal: &Proposal, _now: i64) -> Result<bool> {
+        err!(ErrorCode::ResolvedByOracle)
+    }
+}
+
+/// The named reporter settles via `report_outcome` during an exclusive window.
+/// Proposals here are disputes of the report, or follow a missed window; either
+/// way the market must fall back to a DAO vote before they can be executed.
+pub struct DesignatedReporter;
+
+impl Resolver for DesignatedReporter {
+    fn voting_period(&self, market: &Market) -> i64 {
+        market.voting_period
+    }
+
+    fn check_propose(&self, market: &Market, _proposer: &Pubkey, now: i64) -> Result<()> {
+        check_market_ended(market, now)?;
+        match market.reported_at {
+            Some(reported_at) => require!(
+                now < reported_at + market.voting_period,
+                ErrorCode::ChallengeWindowClosed
+            ),
+            None => require!(
+                now > market.end_time + REPORTING_WINDOW,
+                ErrorCode::ReporterWindowOpen
+            ),
+        }
+        Ok(())
+    }
+
+    fn on_propose(&self, market: &mut Market) {
+        if market.reported_at.is_some() {
+            market.report_disputed = true;
+        }
+    }
+
+    fn check_vote(&self, _market: &Market) -> Result<()> {
+        Ok(())
+    }
+
+    fn finalise(&self, _config: &Config, _market: &Market, _proposal: &Proposal, _now: i64) -> Result<bool> {
+        err!(ErrorCode::FallbackRequired)
+    }
+}
+
+impl DesignatedReporter {
+    /// Whether the reporter may report at `now`
+    pub fn check_report(market: &Market, reporter: &Pubkey, now: i64) -> Result<()> {
+        check_market_ended(market, now)?;
+        check_named_resolver(market, reporter)?;
+        require!(market.reporter_bond > 0, ErrorCode::ReporterBondMissing);
+        require!(market.reported_at.is_none(), ErrorCode::AlreadyReported);
+        require!(
+            now <= market.end_time + REPORTING_WINDOW,
+            ErrorCode::ReporterWindowClosed
+        );
+        Ok(())
+    }
+
+    /// Outcome of an unchallenged report once its challenge window has passed
+    pub fn check_finalise_report(market: &Market, now: i64) -> Result<bool> {
+        let reported_at = market.reported_at.ok_or(ErrorCode::OutcomeNotSet)?;
+        require!(!market.report_disputed, ErrorCode::ReportDisputed);
+        require!(now >= reported_at + market.voting_period, ErrorCode::ChallengeWindowOpen);
+        market.reported_outcome.ok_or(ErrorCode::OutcomeNotSet.into())
+    }
+
+    /// Whether the market should hand resolution to a DAO vote at `now`
+    pub fn check_fallback(market: &Market, now: i64) -> Result<()> {
+        let missed_window = market.reported_at.is_none() && now > market.end_time + REPORTING_WINDOW;
+        require!(
+            market.report_disputed || missed_window,
+            ErrorCode::FallbackNotAllowed
+        );
+        Ok(())
+    }
+}
+
+/// The market's resolver is an M-of-N multisig; its proposal is final
+pub struct MultisigCommittee;
+
+impl Resolver for MultisigCommittee {
+    fn voting_period(&self, _market: &Market) -> i64 {
+        0
+    }
+
+    fn check_propose(&self, market: &Market, proposer: &Pubkey, now: i64) -> Result<()> {
+        check_market_ended(market, now)?;
+        check_named_resolver(market, proposer)
+    }
+
+    fn check_vote(&self, _market: &Market) -> Result<()> {
+        err!(ErrorCode::VotingNotAllowed)
+    }
+
+    fn finalise(&self, _config: &Config, _market: &Market, proposal: &Proposal, _now: i64) -> Result<bool> {
+        Ok(proposal.outcome)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::state::{validate_period_bounds, MarketStatus, ProposalStatus, ResolutionFallback};
+
+    fn config() -> Config {
+        Config {
+            authority: Pubkey::default(),
+            governance_token_mint: Pubkey::default(),
+            min_liquidity: 0,
+            proposal_stake: 0,
+            quorum: 1_000,
+            supermajority_percent: 66,
+            treasury: Pubkey::default(),
+            total_markets: 0,
+            total_volume: 0,
+            guardian: Pubkey::default(),
+            paused: 0,
+            protocol_fee_bps: 0,
+            min_voting_period: Config::MIN_VOTING_PERIOD,
+            max_voting_period: Config::MAX_VOTING_PERIOD,
+            default_voting_period: Config::DEFAULT_VOTING_PERIOD,
+            min_resolution_grace: Config::MIN_RESOLUTION_GRACE,
+            max_resolution_grace: Config::MAX_RESOLUTION_GRACE,
+            default_resolution_grace: Config::DEFAULT_RESOLUTION_GRACE,
+            unresolved_fallback: ResolutionFallback::ExtendRound,
+            bump: 0,
+        }
+    }
+
+    fn market(mode: ResolutionMode, resolver: Option<Pubkey>) -> Market {
+        Market {
+            market_id: 0,
+            creator: Pubkey::default(),
+            collateral_mint: Pubkey::default(),
+            question: String::new(),
+            description: String::new(),
+            category: 5,
+            tags: vec![],
+            fee_bps: None,
+            created_at: 0,
+            end_time: 100,
+            resolution_time: 0,
+            voting_period: Config::DEFAULT_VOTING_PERIOD,
+            resolution_grace: Config::DEFAULT_RESOLUTION_GRACE,
+            resolution_source: String::new(),
+            metadata: None,
+            edits: 0,
+            has_trades: false,
+            price_condition: None,
+            resolution_mode: mode,
+            resolver,
+            parent: None,
+            reporter_bond: 0,
+            reported_outcome: None,
+            reported_at: None,
+            report_disputed: false,
+            late_bets: None,
+            open_proposals: 0,
+            status: MarketStatus::Active,
+            outcome: None,
+            resolved_at: None,
+            refund_price: None,
+            total_liquidity: 0,
+            creator_liquidity: 0,
+            yes_pool: 0,
+            no_pool: 0,
+            yes_mint: Pubkey::default(),
+            no_mint: Pubkey::default(),
+            volume: 0,
+            unique_bettors: 0,
+            halted: false,
+            bump: 0,
+        }
+    }
+
+    fn proposal(outcome: bool, votes_for: u64, votes_against: u64) -> Proposal {
+        Proposal {
+            market: Pubkey::default(),
+            proposer: Pubkey::default(),
+            outcome,
+            evidence: String::new(),
+            proposed_at: 200,
+            voting_ends_at: 300,
+            votes_for,
+            votes_against,
+            status: ProposalStatus::Active,
+            early: false,
+            event_time: 0,
+            late_bets: None,
+            bump: 0,
+        }
+    }
+
+    #[test]
+    fn test_dao_vote() {
+        let config = config();
+        let market = market(ResolutionMode::DaoVote, None);
+        let resolver = resolver_for(ResolutionMode::DaoVote);
+
+        assert!(resolver.check_propose(&market, &Pubkey::new_unique(), 100).is_err());
+        assert!(resolver.check_propose(&market, &Pubkey::new_unique(), 101).is_ok());
+
+        // Voting still open
+        assert!(resolver.finalise(&config, &market, &proposal(true, 900, 100), 299).is_err());
+        // Quorum not reached
+        assert!(resolver.finalise(&config, &market, &proposal(true, 500, 0), 300).is_err());
+        // No supermajority
+        assert!(resolver.finalise(&config, &market, &proposal(true, 600, 400), 300).is_err());
+
+        assert!(resolver.finalise(&config, &market, &proposal(true, 900, 100), 300).unwrap());
+        assert!(!resolver.finalise(&config, &market, &proposal(true, 100, 900), 300).unwrap());
+    }
+
+    #[test]
+    fn test_proposal_passes() {
+        let config = config();
+        let dao = market(ResolutionMode::DaoVote, None);
+
+        assert!(proposal_passes(&config, &dao, &proposal(true, 900, 100), 300));
+        assert!(!proposal_passes(&config, &dao, &proposal(true, 900, 100), 299));
+        assert!(!proposal_passes(&config, &dao, &proposal(true, 500, 0), 300));
+
+        // A reporter dispute could still pass once it falls back to the DAO
+        let reporter = market(ResolutionMode::DesignatedReporter, Some(Pubkey::new_unique()));
+        assert!(proposal_passes(&config, &reporter, &proposal(false, 100, 900), 300));
+        assert!(!proposal_passes(&config, &reporter, &proposal(false, 500, 0), 300));
+    }
+
+    #[test]
+    fn test_resolution_periods() {
+        let config = config();
+
+        assert_eq!(config.voting_period(None).unwrap(), Config::DEFAULT_VOTING_PERIOD);
+        assert_eq!(config.voting_period(Some(3 * 60 * 60)).unwrap(), 3 * 60 * 60);
+        assert!(config.voting_period(Some(Config::MIN_VOTING_PERIOD - 1)).is_err());
+        assert!(config.voting_period(Some(Config::MAX_VOTING_PERIOD + 1)).is_err());
+
+        assert_eq!(config.resolution_grace(None).unwrap(), Config::DEFAULT_RESOLUTION_GRACE);
+        assert!(config.resolution_grace(Some(Config::MAX_RESOLUTION_GRACE + 1)).is_err());
+
+        assert!(validate_period_bounds(60, 120, 90).is_ok());
+        assert!(validate_period_bounds(0, 120, 90).is_err());
+        assert!(validate_period_bounds(60, 120, 150).is_err());
+        assert!(validate_period_bounds(100, 90, 95).is_err());
+    }
+
+    #[test]
+    fn test_early_resolution_outcome() {
+        let config = config();
+
+        // Needs 80% behind the proposed outcome, not just the config's 66%
+        assert_eq!(early_resolution_outcome(&config, &proposal(true, 800, 200), 300).unwrap(), Some(true));
+        assert_eq!(early_resolution_outcome(&config, &proposal(true, 700, 300), 300).unwrap(), None);
+        assert_eq!(early_resolution_outcome(&config, &proposal(false, 100, 900), 300).unwrap(), Some(false));
+
+        // A supermajority against the proposal rejects it
+        assert_eq!(early_resolution_outcome(&config, &proposal(true, 0, 1_000), 300).unwrap(), None);
+
+        // Quorum still applies, and voting must be over
+        assert_eq!(early_resolution_outcome(&config, &proposal(true, 500, 0), 300).unwrap(), None);
+        assert!(early_resolution_outcome(&config, &proposal(true, 900, 0), 299).is_err());
+
+        // A config supermajority above the early one wins
+        let mut strict = config.clone();
+        strict.supermajority_percent = 90;
+        assert_eq!(early_resolution_outcome(&strict, &proposal(true, 850, 150), 300).unwrap(), None);
+    }
+
+    #[test]
+    fn test_optimistic() {
+        let config = config();
+        let market = market(ResolutionMode::Optimistic, None);
+        let resolver = resolver_for(ResolutionMode::Optimistic);
+
+        // Unchallenged proposals stand without quorum
+        assert!(resolver.finalise(&config, &market, &proposal(false, 0, 0), 300).is_ok_and(|o| !o));
+        assert!(resolver.finalise(&config, &market, &proposal(true, 10, 0), 300).unwrap());
+        assert!(resolver.finalise(&config, &market, &proposal(true, 0, 0), 299).is_err());
+
+        // Challenged proposals need a full DAO vote
+        assert!(resolver.finalise(&config, &market, &proposal(true, 10, 1), 300).is_err());
+        assert!(!resolver.finalise(&config, &market, &proposal(true, 100, 900), 300).unwrap());
+    }
+
+    #[test]
+    fn test_multisig_committee() {
+        let config = config();
+        let committee = Pubkey::new_unique();
+        let market = market(ResolutionMode::MultisigCommittee, Some(committee));
+        let resolver = resolver_for(ResolutionMode::MultisigCommittee);
+
+        assert!(resolver.check_propose(&market, &committee, 101).is_ok());
+        assert!(resolver.check_propose(&market, &committee, 100).is_err());
+        assert!(resolver.check_propose(&market, &Pubkey::new_unique(), 101).is_err());
+
+        assert_eq!(resolver.voting_period(&market), 0);
+        assert!(resolver.check_vote(&market).is_err());
+        assert!(!resolver.finalise(&config, &market, &proposal(false, 0, 0), 200).unwrap());
+    }
+
+    #[test]
+    fn test_designated_reporter_report() {
+        let reporter = Pubkey::new_unique();
+        let mut market = market(ResolutionMode::DesignatedReporter, Some(reporter));
+        let window_end = market.end_time + REPORTING_WINDOW;
+
+        // Needs a bond, the reporter's key and the exclusive window
+        assert!(DesignatedReporter::check_report(&market, &reporter, 101).is_err());
+        market.reporter_bond = 1_000;
+        assert!(DesignatedReporter::check_report(&market, &reporter, 100).is_err());
+        assert!(DesignatedReporter::check_report(&market, &Pubkey::new_unique(), 101).is_err());
+        assert!(DesignatedReporter::check_report(&market, &reporter, window_end + 1).is_err());
+        assert!(DesignatedReporter::check_report(&market, &reporter, window_end).is_ok());
+
+        // Nobody else may propose while the window is open
+        let resolver = resolver_for(ResolutionMode::DesignatedReporter);
+        assert!(resolver.check_propose(&market, &Pubkey::new_unique(), window_end).is_err());
+        assert!(DesignatedReporter::check_fallback(&market, window_end).is_err());
+
+        market.reported_outcome = Some(true);
+        market.reported_at = Some(200);
+        assert!(DesignatedReporter::check_report(&market, &reporter, 201).is_err());
+
+        // Unchallenged report settles after the challenge window
+        assert!(DesignatedReporter::check_finalise_report(&market, 200 + market.voting_period - 1).is_err());
+        assert!(DesignatedReporter::check_finalise_report(&market, 200 + market.voting_period).unwrap());
+    }
+
+    #[test]
+    fn test_designated_reporter_fallback() {
+        let config = config();
+        let reporter = Pubkey::new_unique();
+        let resolver = resolver_for(ResolutionMode::DesignatedReporter);
+
+        // Missed window: anyone may propose, and the market may fall back
+        let mut missed = market(ResolutionMode::DesignatedReporter, Some(reporter));
+        let after_window = missed.end_time + REPORTING_WINDOW + 1;
+        assert!(resolver.check_propose(&missed, &Pubkey::new_unique(), after_window).is_ok());
+        assert!(DesignatedReporter::check_fallback(&missed, after_window).is_ok());
+        resolver.on_propose(&mut missed);
+        assert!(!missed.report_disputed);
+
+        // Disputed report: the proposal marks the dispute and blocks settlement
+        let mut disputed = market(ResolutionMode::DesignatedReporter, Some(reporter));
+        disputed.reporter_bond = 1_000;
+        disputed.reported_outcome = Some(true);
+        disputed.reported_at = Some(200);
+        assert!(resolver.check_propose(&disputed, &Pubkey::new_unique(), 200 + disputed.voting_period).is_err());
+        assert!(resolver.check_propose(&disputed, &Pubkey::new_unique(), 201).is_ok());
+        assert!(DesignatedReporter::check_fallback(&disputed, 201).is_err());
+        resolver.on_propose(&mut disputed);
+        assert!(DesignatedReporter::check_fallback(&disputed, 201).is_ok());
+        assert!(DesignatedReporter::check_finalise_report(&disputed, 200 + disputed.voting_period).is_err());
+
+        // Proposals only execute once the market has fallen back to a DAO vote
+        assert!(resolver.finalise(&config, &disputed, &proposal(false, 900, 0), 300).is_err());
+    }
+
+    #[test]
+    fn test_price_oracle_rejects_proposals() {
+        let config = config();
+        let market = market(ResolutionMode::PriceOracle, None);
+        let resolver = resolver_for(ResolutionMode::PriceOracle);
+
+        assert!(resolver.check_propose(&market, &Pubkey::new_unique(), 101).is_err());
+        assert!(resolver.check_vote(&market).is_err());
+        assert!(resolver.finalise(&config, &market, &proposal(true, 900, 0), 300).is_err());
+    }
+}
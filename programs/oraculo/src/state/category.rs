@@ -0,0 +1,180 @@
+use anchor_lang::prelude::*;
+use crate::errors::ErrorCode;
+use crate::state::CollateralConfig;
+
+/// Governance-registered market category at `[b"category", id]`.
+/// Ids 0-5 are reserved for the original buckets in `RESERVED`. Subcategories
+/// are one level deep and follow their parent: a disabled parent disables
+/// them, the parent's minimum liquidity applies to them too, and they use
+/// the parent's fee unless they set their own.
+#[account]
+#[derive(InitSpace)]
+pub struct Category {
+    pub id: u16,
+    #[max_len(32)]
+    pub name: String,
+    /// Parent category id for subcategories
+    pub parent: Option<u16>,
+    pub enabled: bool,
+    /// Minimum creator liquidity in whole collateral units, raising the
+    /// collateral's own minimum
+    pub min_liquidity: u64,
+    /// Trading fee for markets in this category instead of the protocol fee
+    pub fee_bps: Option<u16>,
+    pub bump: u8,
+}
+
+impl Category {
+    pub const MAX_NAME_LEN: usize = 32;
+    pub const MAX_TAGS: usize = 4;
+    pub const MAX_TAG_LEN: usize = 32;
+    /// Names of the original buckets, which keep ids 0-5
+    pub const RESERVED: [&'static str; 6] = [
+        "Crypto",
+        "Sports",
+        "Politics",
+        "Entertainment",
+        "Technology",
+        "Other",
+    ];
+
+    /// Check a category name, which must be the reserved one for ids 0-5
+    pub fn check_name(id: u16, name: &str) -> Result<()> {
+        require!(
+            !name.is_empty() && name.len() <= Self::MAX_NAME_LEN,
+            ErrorCode::InvalidCategory
+        );
+        if let Some(reserved) = Self::RESERVED.get(id as usize) {
+            require!(name == *reserved, ErrorCode::InvalidCategory);
+        }
+        Ok(())
+    }
+
+    /// Load an enabled category from its PDA, which is empty if the id was
+    /// never registered. A subcategory needs its parent's account, and
+    /// inherits its enabled flag, minimum liquidity and fee.
+    pub fn load(info: &AccountInfo, parent: Option<&AccountInfo>) -> Result<Self> {
+        require!(!info.data_is_empty(), ErrorCode::UnknownCategory);
+        let mut category = Self::try_deserialize(&mut &info.try_borrow_data()?[..])?;
+        require!(category.enabled, ErrorCode::CategoryDisabled);
+
+        if let Some(parent_id) = category.parent {
+            let parent = parent.ok_or(ErrorCode::InvalidCategory)?;
+            // Categories are only created at their id's PDA, so one owned by
+            // this program with the right id is the parent
+            require!(parent.owner == &crate::ID, ErrorCode::InvalidCategory);
+            let parent = Self::try_deserialize(&mut &parent.try_borrow_data()?[..])?;
+            require!(parent.id == parent_id, ErrorCode::InvalidCategory);
+            category.inherit(&parent)?;
+        }
+        Ok(category)
+    }
+
+    /// Apply a parent category's enabled flag, minimum liquidity and fee
+    fn inherit(&mut self, parent: &Category) -> Result<()> {
+        require!(parent.enabled, ErrorCode::CategoryDisabled);
+        self.min_liquidity = self.min_liquidity.max(parent.min_liquidity);
+        self.fee_bps = self.fee_bps.or(parent.fee_bps);
+        Ok(())
+    }
+
+    /// Minimum creator liquidity for a market in `collateral`, in its base units
+    pub fn min_liquidity_for(&self, collateral: &CollateralConfig) -> Result<u64> {
+        let category_min = 10u64
+            .checked_pow(collateral.decimals as u32)
+            .and_then(|unit| unit.checked_mul(self.min_liquidity))
+            .ok_or(ErrorCode::MathOverflow)?;
+        Ok(category_min.max(collateral.min_liquidity))
+    }
+}
+
+/// Check a market's tags: at most `MAX_TAGS`, each non-empty and short
+pub fn validate_tags(tags: &[String]) -> Result<()> {
+    require!(tags.len() <= Category::MAX_TAGS, ErrorCode::InvalidTags);
+    for tag in tags {
+        require!(
+            !tag.is_empty() && tag.len() <= Category::MAX_TAG_LEN,
+            ErrorCode::InvalidTags
+        );
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn category(id: u16, parent: Option<u16>, min_liquidity: u64) -> Category {
+        Category {
+            id,
+            name: String::from("Test"),
+            parent,
+            enabled: true,
+            min_liquidity,
+            fee_bps: None,
+            bump: 0,
+        }
+    }
+
+    fn collateral(decimals: u8, min_liquidity: u64) -> CollateralConfig {
+        CollateralConfig {
+            mint: Pubkey::default(),
+            decimals,
+            min_bet: 0,
+            min_liquidity,
+            enabled: true,
+            outcome_of: None,
+            bump: 0,
+        }
+    }
+
+    #[test]
+    fn test_check_name() {
+        assert!(Category::check_name(0, "Crypto").is_ok());
+        assert!(Category::check_name(5, "Other").is_ok());
+        assert!(Category::check_name(4, "Crypto").is_err());
+        assert!(Category::check_name(6, "Crypto").is_ok());
+        assert!(Category::check_name(6, "").is_err());
+        assert!(Category::check_name(6, &"x".repeat(Category::MAX_NAME_LEN + 1)).is_err());
+    }
+
+    #[test]
+    fn test_min_liquidity_for() {
+        // Whole units scaled to the collateral's decimals
+        assert_eq!(category(6, None, 500).min_liquidity_for(&collateral(6, 100_000_000)).unwrap(), 500_000_000);
+        // The collateral's own minimum still applies
+        assert_eq!(category(6, None, 50).min_liquidity_for(&collateral(6, 100_000_000)).unwrap(), 100_000_000);
+        assert!(category(6, None, u64::MAX).min_liquidity_for(&collateral(6, 0)).is_err());
+    }
+
+    #[test]
+    fn test_subcategory_inherits_parent() {
+        let mut parent = category(6, None, 500);
+        let mut child = category(7, Some(6), 200);
+        child.inherit(&parent).unwrap();
+        assert_eq!(child.min_liquidity, 500);
+        assert_eq!(child.fee_bps, None);
+
+        // The parent's fee applies unless the subcategory sets its own
+        parent.fee_bps = Some(50);
+        let mut child = category(7, Some(6), 200);
+        child.inherit(&parent).unwrap();
+        assert_eq!(child.fee_bps, Some(50));
+        let mut child = category(7, Some(6), 200);
+        child.fee_bps = Some(10);
+        child.inherit(&parent).unwrap();
+        assert_eq!(child.fee_bps, Some(10));
+
+        parent.enabled = false;
+        assert!(category(7, Some(6), 200).inherit(&parent).is_err());
+    }
+
+    #[test]
+    fn test_validate_tags() {
+        assert!(validate_tags(&[]).is_ok());
+        assert!(validate_tags(&[String::from("bitcoin"), "x".repeat(Category::MAX_TAG_LEN)]).is_ok());
+        assert!(validate_tags(&[String::new()]).is_err());
+        assert!(validate_tags(&["x".repeat(Category::MAX_TAG_LEN + 1)]).is_err());
+        assert!(validate_tags(&vec![String::from("tag"); Category::MAX_TAGS + 1]).is_err());
+    }
+}
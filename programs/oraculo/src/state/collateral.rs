@@ -0,0 +1,17 @@
+use anchor_lang::prelude::*;
+
+/// Governance-approved collateral mint and its market limits
+#[account]
+#[derive(InitSpace)]
+pub struct CollateralConfig {
+    pub mint: Pubkey,
+    pub decimals: u8,
+    /// Minimum bet, in the mint's base units
+    pub min_bet: u64,
+    /// Minimum creator liquidity, in the mint's base units
+    pub min_liquidity: u64,
+    pub enabled: bool,
+    /// Market whose outcome token this is, for conditional market collateral
+    pub outcome_of: Option<Pubkey>,
+    pub bump: u8,
+}
@@ -1,4 +1,5 @@
 use anchor_lang::prelude::*;
+use crate::errors::ErrorCode;
 
 #[account]
 #[derive(InitSpace)]
@@ -12,5 +13,80 @@ pub struct Config {
     pub treasury: Pubkey,
     pub total_markets: u64,
     pub total_volume: u64,
+    pub guardian: Pubkey,
+    pub paused: u8,
+    pub protocol_fee_bps: u16,
+    /// Bounds and default for how long resolution proposals stay open for votes
+    pub min_voting_period: i64,
+    pub max_voting_period: i64,
+    pub default_voting_period: i64,
+    /// Bounds and default for how long after end_time a market has to resolve
+    pub min_resolution_grace: i64,
+    pub max_resolution_grace: i64,
+    pub default_resolution_grace: i64,
+    /// What happens to a market still unresolved at its resolution_time
+    pub unresolved_fallback: ResolutionFallback,
     pub bump: u8,
 }
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace)]
+pub enum ResolutionFallback {
+    /// Push resolution_time out by another grace period
+    ExtendRound,
+    Cancel,
+}
+
+impl Config {
+    pub const PAUSE_TRADING: u8 = 1 << 0;
+    pub const PAUSE_MARKET_CREATION: u8 = 1 << 1;
+    pub const PAUSE_PROPOSALS: u8 = 1 << 2;
+    pub const PAUSE_CLAIMS: u8 = 1 << 3;
+    pub const PAUSE_ALL: u8 = Self::PAUSE_TRADING
+        | Self::PAUSE_MARKET_CREATION
+        | Self::PAUSE_PROPOSALS
+        | Self::PAUSE_CLAIMS;
+
+    /// Upper bound on the protocol fee charged on each bet (10%)
+    pub const MAX_PROTOCOL_FEE_BPS: u16 = 1_000;
+
+    /// Resolution periods a new config starts with
+    pub const MIN_VOTING_PERIOD: i64 = 60 * 60;
+    pub const MAX_VOTING_PERIOD: i64 = 30 * 24 * 60 * 60;
+    pub const DEFAULT_VOTING_PERIOD: i64 = 48 * 60 * 60;
+    pub const MIN_RESOLUTION_GRACE: i64 = 60 * 60;
+    pub const MAX_RESOLUTION_GRACE: i64 = 90 * 24 * 60 * 60;
+    pub const DEFAULT_RESOLUTION_GRACE: i64 = 7 * 24 * 60 * 60;
+
+    pub fn is_paused(&self, flag: u8) -> bool {
+        self.paused & flag != 0
+    }
+
+    /// Market voting period: `requested` if within bounds, else the default
+    pub fn voting_period(&self, requested: Option<i64>) -> Result<i64> {
+        within_bounds(requested, self.min_voting_period, self.max_voting_period, self.default_voting_period)
+    }
+
+    /// Market resolution grace period: `requested` if within bounds, else the default
+    pub fn resolution_grace(&self, requested: Option<i64>) -> Result<i64> {
+        within_bounds(requested, self.min_resolution_grace, self.max_resolution_grace, self.default_resolution_grace)
+    }
+}
+
+/// Check a `(min, max, default)` period triple is usable
+pub fn validate_period_bounds(min: i64, max: i64, default: i64) -> Result<()> {
+    require!(
+        min > 0 && min <= default && default <= max,
+        ErrorCode::InvalidResolutionPeriod
+    );
+    Ok(())
+}
+
+fn within_bounds(requested: Option<i64>, min: i64, max: i64, default: i64) -> Result<i64> {
+    match requested {
+        Some(period) => {
+            require!(period >= min && period <= max, ErrorCode::InvalidResolutionPeriod);
+            Ok(period)
+        }
+        None => Ok(default),
+    }
+}
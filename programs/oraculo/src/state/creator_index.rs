@@ -0,0 +1,32 @@
+use anchor_lang::prelude::*;
+use crate::errors::ErrorCode;
+
+/// Count of markets a creator has made. Their `n`th market is found through
+/// the `CreatorMarket` at `[b"creator_market", creator, n]`.
+#[account]
+#[derive(InitSpace)]
+pub struct CreatorIndex {
+    pub creator: Pubkey,
+    pub market_count: u64,
+    pub bump: u8,
+}
+
+impl CreatorIndex {
+    /// Claim the index of the creator's next market
+    pub fn next_index(&mut self) -> Result<u64> {
+        let index = self.market_count;
+        self.market_count = index.checked_add(1).ok_or(ErrorCode::MathOverflow)?;
+        Ok(index)
+    }
+}
+
+/// One entry of a creator's market index
+#[account]
+#[derive(InitSpace)]
+pub struct CreatorMarket {
+    pub creator: Pubkey,
+    pub index: u64,
+    pub market: Pubkey,
+    pub market_id: u64,
+    pub bump: u8,
+}
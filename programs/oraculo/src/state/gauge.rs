@@ -0,0 +1,116 @@
+use anchor_lang::prelude::*;
+use crate::errors::ErrorCode;
+use crate::state::{Market, MarketStatus};
+use crate::utils::{accumulate_reward_per_share, pending_reward, reward_debt, split_emissions};
+
+/// Governance-approved liquidity mining gauge for one market. Emissions
+/// are split between the market's LP shares and its traders' volume,
+/// MasterChef-style: each side keeps a cumulative reward per share and
+/// positions track a reward debt against it.
+///
+/// Volume is credited by `place_bet` and `place_bet_routed`. Orders placed
+/// directly with `place_order` earn no volume rewards: book fills pay no
+/// protocol fee, so a wallet could farm emissions by trading with itself.
+#[account]
+#[derive(InitSpace)]
+pub struct Gauge {
+    pub market: Pubkey,
+    /// Governance token emission bucket this gauge mints from
+    pub emission_bucket: u8,
+    /// Governance token base units emitted per second
+    pub emission_rate: u64,
+    /// Share of emissions going to LP shares; the rest goes to volume
+    pub lp_weight_bps: u16,
+    pub start_time: i64,
+    pub end_time: i64,
+    pub last_update: i64,
+    pub acc_reward_per_lp_share: u128,
+    pub acc_reward_per_volume: u128,
+    pub total_lp_shares: u64,
+    pub total_volume: u64,
+    pub bump: u8,
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct RewardPosition {
+    pub gauge: Pubkey,
+    pub owner: Pubkey,
+    pub lp_shares: u64,
+    pub volume: u64,
+    pub lp_reward_debt: u128,
+    pub volume_reward_debt: u128,
+    pub pending_rewards: u64,
+    pub total_claimed: u64,
+    pub bump: u8,
+}
+
+impl Gauge {
+    /// Accrue emissions from `last_update` up to `now` (capped at `end_time`).
+    /// Emissions stop once the market leaves `Active`: at the time it settled,
+    /// or at the last update while an early resolution is pending.
+    pub fn update(&mut self, market: &Market, now: i64) -> Result<()> {
+        let mut until = now.min(self.end_time);
+        if market.status != MarketStatus::Active {
+            until = until.min(market.resolved_at.unwrap_or(self.last_update));
+        }
+        let from = self.last_update.max(self.start_time);
+        if until <= from {
+            self.last_update = self.last_update.max(until);
+            return Ok(());
+        }
+
+        let (lp_rewards, volume_rewards) =
+            split_emissions(self.emission_rate, (until - from) as u64, self.lp_weight_bps)?;
+        self.acc_reward_per_lp_share = accumulate_reward_per_share(
+            self.acc_reward_per_lp_share,
+            lp_rewards,
+            self.total_lp_shares,
+        )?;
+        self.acc_reward_per_volume = accumulate_reward_per_share(
+            self.acc_reward_per_volume,
+            volume_rewards,
+            self.total_volume,
+        )?;
+        self.last_update = until;
+
+        Ok(())
+    }
+}
+
+impl RewardPosition {
+    /// Move rewards earned at the gauge's current accumulators into
+    /// `pending_rewards`. Call after `Gauge::update` and before changing
+    /// shares, then `checkpoint` once shares are updated.
+    pub fn settle(&mut self, gauge: &Gauge) -> Result<()> {
+        let lp = pending_reward(self.lp_shares, gauge.acc_reward_per_lp_share, self.lp_reward_debt)?;
+        let volume = pending_reward(self.volume, gauge.acc_reward_per_volume, self.volume_reward_debt)?;
+        self.pending_rewards = self.pending_rewards
+            .checked_add(lp)
+            .and_then(|p| p.checked_add(volume))
+            .ok_or(ErrorCode::MathOverflow)?;
+        self.checkpoint(gauge)
+    }
+
+    /// Re-read the LP share from the market's creator liquidity, so
+    /// withdrawn liquidity stops earning. Call after `settle`.
+    pub fn sync_lp_shares(&mut self, gauge: &mut Gauge, market: &Market) -> Result<()> {
+        let lp_shares = if self.owner == market.creator {
+            market.creator_liquidity
+        } else {
+            0
+        };
+        gauge.total_lp_shares = gauge.total_lp_shares
+            .checked_sub(self.lp_shares)
+            .and_then(|total| total.checked_add(lp_shares))
+            .ok_or(ErrorCode::MathOverflow)?;
+        self.lp_shares = lp_shares;
+        self.checkpoint(gauge)
+    }
+
+    pub fn checkpoint(&mut self, gauge: &Gauge) -> Result<()> {
+        self.lp_reward_debt = reward_debt(self.lp_shares, gauge.acc_reward_per_lp_share)?;
+        self.volume_reward_debt = reward_debt(self.volume, gauge.acc_reward_per_volume)?;
+        Ok(())
+    }
+}
@@ -1,45 +1,143 @@
 use anchor_lang::prelude::*;
+use crate::state::{Config, LateBets};
 
 #[account]
 #[derive(InitSpace)]
 pub struct Market {
+    /// Sequential id from `Config::total_markets`, the market's PDA seed
+    pub market_id: u64,
     pub creator: Pubkey,
+    pub collateral_mint: Pubkey,
     #[max_len(200)]
     pub question: String,
     #[max_len(500)]
     pub description: String,
-    pub category: MarketCategory,
+    /// Id of the market's registered `Category`
+    pub category: u16,
+    #[max_len(4, 32)]
+    pub tags: Vec<String>,
+    /// The category's trading fee, if it overrides the protocol fee
+    pub fee_bps: Option<u16>,
     pub created_at: i64,
     pub end_time: i64,
     pub resolution_time: i64,
+    /// Seconds resolution proposals stay open for votes
+    pub voting_period: i64,
+    /// Seconds added to resolution_time when an unresolved round is extended
+    pub resolution_grace: i64,
     #[max_len(200)]
     pub resolution_source: String,
+    /// Off-chain document with the full rules, images and resolution criteria
+    pub metadata: Option<OffChainMetadata>,
+    /// Number of creator edits made before the first bet
+    pub edits: u16,
+    /// Set by the first bet or order; the market can no longer be edited
+    pub has_trades: bool,
+    pub price_condition: Option<PriceCondition>,
+    pub resolution_mode: ResolutionMode,
+    /// Reporter or committee multisig for modes with a named resolver
+    pub resolver: Option<Pubkey>,
+    /// Parent outcome this market's collateral is conditional on
+    pub parent: Option<ParentOutcome>,
+    /// Governance tokens the designated reporter has bonded
+    pub reporter_bond: u64,
+    pub reported_outcome: Option<bool>,
+    pub reported_at: Option<i64>,
+    pub report_disputed: bool,
+    /// AMM bets to roll back after an early resolution
+    pub late_bets: Option<LateBets>,
+    /// Resolution proposals not yet executed or expired
+    pub open_proposals: u16,
     pub status: MarketStatus,
     pub outcome: Option<bool>,
+    /// When the market was resolved or cancelled
     pub resolved_at: Option<i64>,
+    /// YES price refunds are paid at, frozen from the TWAP when cancelled
+    pub refund_price: Option<u16>,
     pub total_liquidity: u64,
+    pub creator_liquidity: u64,
     pub yes_pool: u64,
     pub no_pool: u64,
     pub yes_mint: Pubkey,
     pub no_mint: Pubkey,
     pub volume: u64,
     pub unique_bettors: u64,
+    pub halted: bool,
     pub bump: u8,
 }
 
+impl Market {
+    /// Trading fee in basis points: the category's, or else the protocol fee
+    pub fn trading_fee_bps(&self, config: &Config) -> u16 {
+        self.fee_bps.unwrap_or(config.protocol_fee_bps)
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace)]
+pub enum ResolutionMode {
+    DaoVote,
+    Optimistic,
+    PriceOracle,
+    DesignatedReporter,
+    MultisigCommittee,
+}
+
+impl ResolutionMode {
+    /// Whether the mode needs `Market::resolver` set
+    pub fn has_named_resolver(&self) -> bool {
+        matches!(self, Self::DesignatedReporter | Self::MultisigCommittee)
+    }
+}
+
+/// Content-addressed pointer to a market's JSON metadata document
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, InitSpace)]
+pub struct OffChainMetadata {
+    /// e.g. `ar://<tx id>` or `ipfs://<cid>`
+    #[max_len(200)]
+    pub uri: String,
+    /// SHA-256 of the document, so clients can check what they fetched
+    pub hash: [u8; 32],
+}
+
+impl OffChainMetadata {
+    pub const MAX_URI_LEN: usize = 200;
+}
+
+/// Outcome of another market whose outcome token is this market's collateral.
+/// If the parent resolves the other way or is cancelled, this market is cancelled.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace)]
+pub struct ParentOutcome {
+    pub market: Pubkey,
+    pub outcome: bool,
+}
+
+/// Resolves YES when the feed price compares true against the threshold
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, InitSpace)]
-pub enum MarketCategory {
-    Crypto,
-    Sports,
-    Politics,
-    Entertainment,
-    Technology,
-    Other,
+pub struct PriceCondition {
+    pub feed: Pubkey,
+    /// Threshold as `threshold * 10^expo`
+    pub threshold: i64,
+    pub expo: i32,
+    pub comparison: PriceComparison,
+    /// Maximum age of the feed price at resolution, in seconds
+    pub max_staleness: u32,
+    /// Maximum confidence interval as a share of price, in basis points
+    pub max_conf_bps: u16,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace)]
+pub enum PriceComparison {
+    GreaterThan,
+    GreaterOrEqual,
+    LessThan,
+    LessOrEqual,
 }
 
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, InitSpace)]
 pub enum MarketStatus {
     Active,
+    /// Trading halted while an early-resolution vote runs
+    ResolvingEarly,
     Resolved,
     Cancelled,
 }
@@ -1,7 +1,23 @@
 pub mod config;
 pub mod market;
 pub mod proposal;
+pub mod treasury;
+pub mod gauge;
+pub mod collateral;
+pub mod order_book;
+pub mod price_history;
+pub mod template;
+pub mod creator_index;
+pub mod category;
 
 pub use config::*;
 pub use market::*;
 pub use proposal::*;
+pub use treasury::*;
+pub use gauge::*;
+pub use collateral::*;
+pub use order_book::*;
+pub use price_history::*;
+pub use template::*;
+pub use creator_index::*;
+pub use category::*;
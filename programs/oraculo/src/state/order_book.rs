@@ -0,0 +1,478 @@
+use anchor_lang::prelude::*;
+use crate::errors::ErrorCode;
+
+/// Order prices are basis points of one collateral unit per whole outcome token
+pub const PRICE_SCALE_BPS: u16 = 10_000;
+
+/// Per-market central limit order book for YES. NO orders rest on the
+/// complementary side at `PRICE_SCALE_BPS - price`: buying NO at 30% is a
+/// YES ask at 70%. Quantities are in lots; one lot at price `p` costs `p`
+/// collateral base units, so a lot is `PRICE_SCALE_BPS` collateral base
+/// units' worth of outcome tokens.
+#[account]
+#[derive(InitSpace)]
+pub struct OrderBook {
+    pub market: Pubkey,
+    pub next_order_id: u64,
+    /// Resting YES bids, highest price first, oldest first within a price
+    #[max_len(32)]
+    pub bids: Vec<Order>,
+    /// Resting YES asks, lowest price first, oldest first within a price
+    #[max_len(32)]
+    pub asks: Vec<Order>,
+    /// Maker proceeds waiting to be credited by `consume_fills`
+    #[max_len(32)]
+    pub fills: Vec<Fill>,
+    pub bump: u8,
+}
+
+/// A trader's unsettled balances on one market's order book
+#[account]
+#[derive(InitSpace)]
+pub struct OpenOrders {
+    pub market: Pubkey,
+    pub owner: Pubkey,
+    /// Collateral base units
+    pub collateral_free: u64,
+    /// YES lots
+    pub yes_free: u64,
+    /// NO lots
+    pub no_free: u64,
+    pub bump: u8,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace, Debug)]
+pub enum OrderKind {
+    BuyYes,
+    SellYes,
+    BuyNo,
+    SellNo,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace, Debug)]
+pub enum OrderType {
+    /// Match what crosses, rest the remainder
+    Limit,
+    /// Rest only; rejected if it would match
+    PostOnly,
+    /// Match what crosses, cancel the remainder
+    ImmediateOrCancel,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace, Debug)]
+pub struct Order {
+    pub id: u64,
+    pub owner: Pubkey,
+    pub kind: OrderKind,
+    /// YES price in basis points
+    pub price: u16,
+    pub lots: u64,
+    pub placed_at: i64,
+}
+
+/// Collateral base units and outcome lots
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default, PartialEq, Eq, InitSpace, Debug)]
+pub struct Balances {
+    pub collateral: u64,
+    pub yes: u64,
+    pub no: u64,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace, Debug)]
+pub struct Fill {
+    pub maker: Pubkey,
+    pub order_id: u64,
+    pub credit: Balances,
+}
+
+/// What a taker order did against the book
+#[derive(Clone, Copy, Default, PartialEq, Eq, Debug)]
+pub struct TakeResult {
+    /// Proceeds and price improvement from resting orders
+    pub taker_credit: Balances,
+    /// Lots filled against resting orders
+    pub book_lots: u64,
+    /// Lots left to route to the AMM
+    pub amm_lots: u64,
+    /// Lots left unfilled
+    pub remaining_lots: u64,
+    /// Complete sets to mint (buy YES against buy NO)
+    pub sets_minted: u64,
+    /// Collateral the YES side paid for the minted sets, at the fill prices
+    pub sets_yes_cost: u64,
+    /// Complete sets to merge (sell YES against sell NO)
+    pub sets_merged: u64,
+}
+
+impl OrderKind {
+    pub fn is_bid(self) -> bool {
+        matches!(self, OrderKind::BuyYes | OrderKind::SellNo)
+    }
+
+    /// YES price for an order priced in its own outcome
+    pub fn yes_price(self, price: u16) -> u16 {
+        match self {
+            OrderKind::BuyYes | OrderKind::SellYes => price,
+            OrderKind::BuyNo | OrderKind::SellNo => PRICE_SCALE_BPS - price,
+        }
+    }
+
+    /// What an order of `lots` at YES price `price` locks up
+    pub fn escrow(self, price: u16, lots: u64) -> Result<Balances> {
+        let per_lot = match self {
+            OrderKind::BuyYes => price,
+            OrderKind::BuyNo => PRICE_SCALE_BPS - price,
+            OrderKind::SellYes => return Ok(Balances { yes: lots, ..Default::default() }),
+            OrderKind::SellNo => return Ok(Balances { no: lots, ..Default::default() }),
+        };
+        Ok(Balances {
+            collateral: lots.checked_mul(per_lot as u64).ok_or(ErrorCode::MathOverflow)?,
+            ..Default::default()
+        })
+    }
+}
+
+impl Balances {
+    pub fn add(&mut self, other: &Balances) -> Result<()> {
+        self.collateral = self.collateral.checked_add(other.collateral).ok_or(ErrorCode::MathOverflow)?;
+        self.yes = self.yes.checked_add(other.yes).ok_or(ErrorCode::MathOverflow)?;
+        self.no = self.no.checked_add(other.no).ok_or(ErrorCode::MathOverflow)?;
+        Ok(())
+    }
+}
+
+impl OpenOrders {
+    pub fn credit(&mut self, balances: &Balances) -> Result<()> {
+        let mut free = Balances {
+            collateral: self.collateral_free,
+            yes: self.yes_free,
+            no: self.no_free,
+        };
+        free.add(balances)?;
+        self.collateral_free = free.collateral;
+        self.yes_free = free.yes;
+        self.no_free = free.no;
+        Ok(())
+    }
+}
+
+impl OrderBook {
+    pub const MAX_ORDERS: usize = 32;
+    pub const MAX_FILLS: usize = 32;
+    /// Resting orders one owner may have across both sides
+    pub const MAX_ORDERS_PER_OWNER: usize = 4;
+    /// Smallest order that may rest on the book, one whole token
+    pub const MIN_ORDER_LOTS: u64 = 100;
+
+    /// Best resting order `taker` would match at YES price `price`
+    fn best_crossing(&self, taker: OrderKind, price: u16) -> Option<&Order> {
+        if taker.is_bid() {
+            self.asks.first().filter(|ask| ask.price <= price)
+        } else {
+            self.bids.first().filter(|bid| bid.price >= price)
+        }
+    }
+
+    /// Resting orders a `taker` would match, best first, as (cost per lot in
+    /// the taker's own terms, lots, YES price)
+    pub fn levels(&self, taker: OrderKind) -> Result<Vec<(u64, u64, u16)>> {
+        let side = if taker.is_bid() { &self.asks } else { &self.bids };
+        side.iter()
+            .map(|order| Ok((taker.escrow(order.price, 1)?.collateral, order.lots, order.price)))
+            .collect()
+    }
+
+    /// Whether an order at YES price `price` would match immediately
+    pub fn crosses(&self, kind: OrderKind, price: u16) -> bool {
+        self.best_crossing(kind, price).is_some()
+    }
+
+    /// Match a taker order against resting orders, best price first, filling
+    /// at each maker's price. `amm_cost(lots)` quotes buying `lots` from the
+    /// AMM including fees, or `None` if the order cannot use the AMM. The AMM
+    /// curve's marginal price only falls as more is bought, so once it beats
+    /// the best resting price for the whole remainder it takes all of it.
+    pub fn take(
+        &mut self,
+        kind: OrderKind,
+        price: u16,
+        lots: u64,
+        amm_cost: impl Fn(u64) -> Result<Option<u64>>,
+    ) -> Result<TakeResult> {
+        let mut result = TakeResult::default();
+        let mut remaining = lots;
+
+        while remaining > 0 {
+            let best = self.best_crossing(kind, price).copied();
+
+            // Cost to the taker of the remainder at the best resting price, or
+            // at its own limit if nothing rests there
+            let book_cost = kind.escrow(best.map_or(price, |order| order.price), remaining)?.collateral;
+            if let Some(cost) = amm_cost(remaining)? {
+                if cost <= book_cost {
+                    result.amm_lots = remaining;
+                    remaining = 0;
+                    break;
+                }
+            }
+
+            let Some(maker) = best else { break };
+            let filled = remaining.min(maker.lots);
+            let (taker_credit, maker_credit, minted, merged) = settle_pair(kind, price, maker.kind, maker.price, filled)?;
+
+            require!(self.fills.len() < Self::MAX_FILLS, ErrorCode::FillQueueFull);
+            self.fills.push(Fill {
+                maker: maker.owner,
+                order_id: maker.id,
+                credit: maker_credit,
+            });
+
+            let side = if kind.is_bid() { &mut self.asks } else { &mut self.bids };
+            if filled == maker.lots {
+                side.remove(0);
+            } else {
+                side[0].lots -= filled;
+            }
+
+            result.taker_credit.add(&taker_credit)?;
+            result.book_lots += filled;
+            result.sets_minted += minted;
+            result.sets_yes_cost = (maker.price as u64)
+                .checked_mul(minted)
+                .and_then(|cost| cost.checked_add(result.sets_yes_cost))
+                .ok_or(ErrorCode::MathOverflow)?;
+            result.sets_merged += merged;
+            remaining -= filled;
+        }
+
+        result.remaining_lots = remaining;
+        Ok(result)
+    }
+
+    /// Rest an order behind existing orders at the same price
+    pub fn insert(&mut self, order: Order) -> Result<()> {
+        let owned = self
+            .bids
+            .iter()
+            .chain(self.asks.iter())
+            .filter(|resting| resting.owner == order.owner)
+            .count();
+        require!(owned < Self::MAX_ORDERS_PER_OWNER, ErrorCode::TooManyOrders);
+
+        let side = if order.kind.is_bid() { &mut self.bids } else { &mut self.asks };
+        require!(side.len() < Self::MAX_ORDERS, ErrorCode::OrderBookFull);
+
+        let position = if order.kind.is_bid() {
+            side.iter().position(|resting| resting.price < order.price)
+        } else {
+            side.iter().position(|resting| resting.price > order.price)
+        };
+        side.insert(position.unwrap_or(side.len()), order);
+        Ok(())
+    }
+
+    /// Remove `owner`'s order `id` from the book
+    pub fn remove(&mut self, owner: &Pubkey, id: u64) -> Result<Order> {
+        for side in [&mut self.bids, &mut self.asks] {
+            if let Some(index) = side.iter().position(|order| order.id == id) {
+                require!(side[index].owner == *owner, ErrorCode::Unauthorized);
+                return Ok(side.remove(index));
+            }
+        }
+        err!(ErrorCode::OrderNotFound)
+    }
+}
+
+/// Balances a taker and maker receive for `lots` filled at the maker's price,
+/// plus complete sets to mint and merge. The taker is refunded any collateral
+/// it escrowed above the fill price.
+fn settle_pair(
+    taker: OrderKind,
+    taker_price: u16,
+    maker: OrderKind,
+    price: u16,
+    lots: u64,
+) -> Result<(Balances, Balances, u64, u64)> {
+    let (bid, ask) = if taker.is_bid() { (taker, maker) } else { (maker, taker) };
+
+    let bid_credit = match bid {
+        OrderKind::BuyYes => Balances { yes: lots, ..Default::default() },
+        _ => OrderKind::BuyNo.escrow(price, lots)?,
+    };
+    let ask_credit = match ask {
+        OrderKind::SellYes => OrderKind::BuyYes.escrow(price, lots)?,
+        _ => Balances { no: lots, ..Default::default() },
+    };
+    let minted = if bid == OrderKind::BuyYes && ask == OrderKind::BuyNo { lots } else { 0 };
+    let merged = if bid == OrderKind::SellNo && ask == OrderKind::SellYes { lots } else { 0 };
+
+    let (mut taker_credit, maker_credit) = if taker.is_bid() {
+        (bid_credit, ask_credit)
+    } else {
+        (ask_credit, bid_credit)
+    };
+    let improvement = taker
+        .escrow(taker_price, lots)?
+        .collateral
+        .checked_sub(taker.escrow(price, lots)?.collateral)
+        .ok_or(ErrorCode::MathUnderflow)?;
+    taker_credit.collateral = taker_credit
+        .collateral
+        .checked_add(improvement)
+        .ok_or(ErrorCode::MathOverflow)?;
+
+    Ok((taker_credit, maker_credit, minted, merged))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn empty_book() -> OrderBook {
+        OrderBook {
+            market: Pubkey::default(),
+            next_order_id: 0,
+            bids: Vec::new(),
+            asks: Vec::new(),
+            fills: Vec::new(),
+            bump: 0,
+        }
+    }
+
+    fn order(id: u64, kind: OrderKind, price: u16, lots: u64) -> Order {
+        Order {
+            id,
+            owner: Pubkey::new_unique(),
+            kind,
+            price,
+            lots,
+            placed_at: 0,
+        }
+    }
+
+    fn no_amm(_: u64) -> Result<Option<u64>> {
+        Ok(None)
+    }
+
+    #[test]
+    fn test_insert_orders_by_price_then_time() {
+        let mut book = empty_book();
+        book.insert(order(1, OrderKind::BuyYes, 4_000, 10)).unwrap();
+        book.insert(order(2, OrderKind::BuyYes, 4_500, 10)).unwrap();
+        book.insert(order(3, OrderKind::BuyYes, 4_000, 10)).unwrap();
+        // Selling NO at 58% is a YES bid at 42%
+        book.insert(order(4, OrderKind::SellNo, OrderKind::SellNo.yes_price(5_800), 10)).unwrap();
+
+        let ids: Vec<u64> = book.bids.iter().map(|o| o.id).collect();
+        assert_eq!(ids, vec![2, 4, 1, 3]);
+
+        book.insert(order(5, OrderKind::SellYes, 6_000, 10)).unwrap();
+        book.insert(order(6, OrderKind::BuyNo, OrderKind::BuyNo.yes_price(4_500), 10)).unwrap();
+        let ids: Vec<u64> = book.asks.iter().map(|o| o.id).collect();
+        assert_eq!(ids, vec![6, 5]);
+        assert!(book.crosses(OrderKind::BuyYes, 5_500));
+        assert!(!book.crosses(OrderKind::BuyYes, 5_499));
+    }
+
+    #[test]
+    fn test_take_partial_fills_at_maker_price() {
+        let mut book = empty_book();
+        book.insert(order(1, OrderKind::SellYes, 5_000, 10)).unwrap();
+        book.insert(order(2, OrderKind::SellYes, 5_200, 10)).unwrap();
+
+        let result = book.take(OrderKind::BuyYes, 5_500, 15, no_amm).unwrap();
+        assert_eq!(result.book_lots, 15);
+        assert_eq!(result.remaining_lots, 0);
+        // 15 YES, refunded (5500-5000)*10 + (5500-5200)*5
+        assert_eq!(result.taker_credit, Balances { collateral: 6_500, yes: 15, no: 0 });
+
+        // Makers are paid at their own prices
+        assert_eq!(book.fills.len(), 2);
+        assert_eq!(book.fills[0].credit.collateral, 50_000);
+        assert_eq!(book.fills[1].credit.collateral, 26_000);
+        assert_eq!(book.asks.len(), 1);
+        assert_eq!(book.asks[0].lots, 5);
+    }
+
+    #[test]
+    fn test_take_complementary_sides() {
+        // Buy YES at 60% against buy NO at 40% mints complete sets
+        let mut book = empty_book();
+        book.insert(order(1, OrderKind::BuyNo, OrderKind::BuyNo.yes_price(4_000), 10)).unwrap();
+        let result = book.take(OrderKind::BuyYes, 6_000, 10, no_amm).unwrap();
+        assert_eq!(result.sets_minted, 10);
+        // The YES side pays the 60% fill price for its half of each set
+        assert_eq!(result.sets_yes_cost, 60_000);
+        assert_eq!(result.taker_credit, Balances { collateral: 0, yes: 10, no: 0 });
+        assert_eq!(book.fills[0].credit, Balances { collateral: 0, yes: 0, no: 10 });
+
+        // Sell YES at 55% against sell NO at 40% merges them
+        let mut book = empty_book();
+        book.insert(order(1, OrderKind::SellNo, OrderKind::SellNo.yes_price(4_000), 10)).unwrap();
+        let result = book.take(OrderKind::SellYes, 5_500, 10, no_amm).unwrap();
+        assert_eq!(result.sets_merged, 10);
+        assert_eq!(result.taker_credit.collateral, 60_000);
+        assert_eq!(book.fills[0].credit.collateral, 40_000);
+
+        // Buy NO at 45% against sell NO at 40%
+        let mut book = empty_book();
+        book.insert(order(1, OrderKind::SellNo, OrderKind::SellNo.yes_price(4_000), 10)).unwrap();
+        let result = book
+            .take(OrderKind::BuyNo, OrderKind::BuyNo.yes_price(4_500), 10, no_amm)
+            .unwrap();
+        assert_eq!((result.sets_minted, result.sets_merged), (0, 0));
+        assert_eq!(result.taker_credit, Balances { collateral: 5_000, yes: 0, no: 10 });
+        assert_eq!(book.fills[0].credit.collateral, 40_000);
+    }
+
+    #[test]
+    fn test_take_routes_to_cheaper_amm() {
+        let mut book = empty_book();
+        book.insert(order(1, OrderKind::SellYes, 5_000, 10)).unwrap();
+
+        // AMM at 4,000 per lot beats the 5,000 ask for the whole order
+        let result = book
+            .take(OrderKind::BuyYes, 6_000, 20, |lots| Ok(Some(lots * 4_000)))
+            .unwrap();
+        assert_eq!((result.book_lots, result.amm_lots), (0, 20));
+        assert!(book.fills.is_empty());
+
+        // A dearer AMM only takes what the book cannot fill within the limit
+        let result = book
+            .take(OrderKind::BuyYes, 6_000, 20, |lots| Ok(Some(lots * 5_500)))
+            .unwrap();
+        assert_eq!((result.book_lots, result.amm_lots), (10, 10));
+
+        // Nothing fills above the limit
+        let result = book
+            .take(OrderKind::BuyYes, 5_000, 20, |lots| Ok(Some(lots * 5_500)))
+            .unwrap();
+        assert_eq!((result.book_lots, result.amm_lots, result.remaining_lots), (0, 0, 20));
+    }
+
+    #[test]
+    fn test_insert_caps_orders_per_owner() {
+        let mut book = empty_book();
+        let owner = Pubkey::new_unique();
+        for id in 0..OrderBook::MAX_ORDERS_PER_OWNER as u64 {
+            let kind = if id % 2 == 0 { OrderKind::BuyYes } else { OrderKind::SellYes };
+            book.insert(Order { owner, ..order(id, kind, 5_000, 100) }).unwrap();
+        }
+        assert!(book.insert(Order { owner, ..order(9, OrderKind::BuyNo, 5_000, 100) }).is_err());
+
+        // Others can still rest orders
+        book.insert(order(10, OrderKind::BuyYes, 5_000, 100)).unwrap();
+    }
+
+    #[test]
+    fn test_remove_order() {
+        let mut book = empty_book();
+        let resting = order(7, OrderKind::BuyYes, 4_000, 10);
+        book.insert(resting).unwrap();
+
+        assert!(book.remove(&Pubkey::new_unique(), 7).is_err());
+        assert!(book.remove(&resting.owner, 8).is_err());
+        assert_eq!(book.remove(&resting.owner, 7).unwrap(), resting);
+        assert!(book.bids.is_empty());
+    }
+}
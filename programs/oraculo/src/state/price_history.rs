@@ -0,0 +1,449 @@
+use anchor_lang::prelude::*;
+use crate::errors::ErrorCode;
+
+/// Ring buffer of a market's implied YES price, Uniswap v2 style: a running
+/// time-weighted cumulative price is kept on every trade and snapshotted at
+/// most once per `OBSERVATION_INTERVAL`, so a TWAP over a window is the
+/// difference of two cumulatives divided by the time between them. Outcome
+/// tokens minted by AMM bets and book fills are logged with their exact
+/// times, so the bets placed after an early-resolved event can be rolled back.
+#[account]
+#[derive(InitSpace)]
+pub struct PriceHistory {
+    pub market: Pubkey,
+    /// Implied YES price in basis points since `last_update`
+    pub last_price: u16,
+    pub last_update: i64,
+    /// Sum of price (bps) x seconds up to `last_update`
+    pub cumulative: u128,
+    /// Tokens minted since the market opened
+    pub minted: MintTotals,
+    /// Slot the next observation overwrites once the buffer is full
+    pub head: u8,
+    #[max_len(96)]
+    pub observations: Vec<PriceObservation>,
+    /// Slot the next mint record overwrites once the log is full
+    pub mint_head: u8,
+    #[max_len(64)]
+    pub mints: Vec<MintRecord>,
+    pub bump: u8,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace, Debug)]
+pub struct PriceObservation {
+    pub timestamp: i64,
+    /// Implied YES price in basis points from this time
+    pub price: u16,
+    /// Cumulative price at `timestamp`
+    pub cumulative: u128,
+    /// Mints up to and including `timestamp`
+    pub minted: MintTotals,
+}
+
+/// Mint totals after the mints at one timestamp
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace, Debug)]
+pub struct MintRecord {
+    pub timestamp: i64,
+    pub minted: MintTotals,
+}
+
+/// Outcome tokens minted by AMM bets and book fills and the collateral paid
+/// for them, all in outcome base units (one whole token redeems one whole
+/// collateral unit). Book fills that move existing tokens mint nothing.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default, PartialEq, Eq, InitSpace, Debug)]
+pub struct MintTotals {
+    pub yes_tokens: u64,
+    pub no_tokens: u64,
+    pub yes_paid: u64,
+    pub no_paid: u64,
+}
+
+impl MintTotals {
+    /// Tokens minted and collateral paid on one side
+    pub fn side(&self, yes: bool) -> (u64, u64) {
+        if yes {
+            (self.yes_tokens, self.yes_paid)
+        } else {
+            (self.no_tokens, self.no_paid)
+        }
+    }
+
+    fn since(&self, earlier: &MintTotals) -> Result<MintTotals> {
+        let sub = |a: u64, b: u64| a.checked_sub(b).ok_or(ErrorCode::MathUnderflow);
+        Ok(MintTotals {
+            yes_tokens: sub(self.yes_tokens, earlier.yes_tokens)?,
+            no_tokens: sub(self.no_tokens, earlier.no_tokens)?,
+            yes_paid: sub(self.yes_paid, earlier.yes_paid)?,
+            no_paid: sub(self.no_paid, earlier.no_paid)?,
+        })
+    }
+}
+
+/// Bets minted after an early-resolved event, frozen when trading halts.
+/// Tokens are fungible and can change hands, so the rollback is applied to
+/// every payout pro rata instead of to the late bettors: each side as a whole
+/// gets back what its late bets paid in, and no more.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace, Debug)]
+pub struct LateBets {
+    pub cutoff: i64,
+    pub minted: MintTotals,
+    /// Outcome token supplies when trading halted
+    pub yes_supply: u64,
+    pub no_supply: u64,
+}
+
+impl LateBets {
+    fn supply(&self, yes: bool) -> u64 {
+        if yes {
+            self.yes_supply
+        } else {
+            self.no_supply
+        }
+    }
+
+    /// Value of `amount` winning tokens: par, less their share of what the
+    /// late winning tokens were minted for above their price
+    pub fn winning_value(&self, amount: u64, outcome: bool) -> Result<u64> {
+        let (tokens, paid) = self.minted.side(outcome);
+        let windfall = tokens.saturating_sub(paid) as u128;
+        let haircut = (amount as u128)
+            .checked_mul(windfall)
+            .ok_or(ErrorCode::MathOverflow)?
+            .checked_div(self.supply(outcome) as u128)
+            .unwrap_or(0);
+        Ok(amount - haircut.min(amount as u128) as u64)
+    }
+
+    /// Value of `amount` losing tokens: their share of what the late losing
+    /// bets paid in
+    pub fn losing_value(&self, amount: u64, outcome: bool) -> Result<u64> {
+        let (_, paid) = self.minted.side(!outcome);
+        let value = (amount as u128)
+            .checked_mul(paid as u128)
+            .ok_or(ErrorCode::MathOverflow)?
+            .checked_div(self.supply(!outcome) as u128)
+            .unwrap_or(0);
+        Ok(value as u64)
+    }
+}
+
+impl PriceHistory {
+    pub const MAX_OBSERVATIONS: usize = 96;
+    /// 96 observations 15 minutes apart cover the last 24 hours
+    pub const OBSERVATION_INTERVAL: i64 = 15 * 60;
+    pub const MAX_MINT_RECORDS: usize = 64;
+
+    /// Start the history at `price` when the market opens
+    pub fn init(&mut self, market: Pubkey, now: i64, price: u16, bump: u8) {
+        self.market = market;
+        self.last_price = price;
+        self.last_update = now;
+        self.cumulative = 0;
+        self.minted = MintTotals::default();
+        self.head = 0;
+        self.observations = vec![PriceObservation {
+            timestamp: now,
+            price,
+            cumulative: 0,
+            minted: MintTotals::default(),
+        }];
+        self.mint_head = 0;
+        self.mints = Vec::new();
+        self.bump = bump;
+    }
+
+    /// Cumulative price at `now`, extending the last price to it
+    pub fn cumulative_at(&self, now: i64) -> Result<u128> {
+        let elapsed = now.checked_sub(self.last_update).ok_or(ErrorCode::MathUnderflow)?;
+        require!(elapsed >= 0, ErrorCode::InvalidTwapWindow);
+        (self.last_price as u128)
+            .checked_mul(elapsed as u128)
+            .and_then(|delta| delta.checked_add(self.cumulative))
+            .ok_or(ErrorCode::MathOverflow.into())
+    }
+
+    /// Count outcome tokens minted at `now`, before `record`ing the trade.
+    /// `paid` is the collateral that reached the vault, in outcome units.
+    pub fn record_mint(&mut self, now: i64, yes: bool, tokens: u64, paid: u64) -> Result<()> {
+        let (minted, paid_total) = if yes {
+            (&mut self.minted.yes_tokens, &mut self.minted.yes_paid)
+        } else {
+            (&mut self.minted.no_tokens, &mut self.minted.no_paid)
+        };
+        *minted = minted.checked_add(tokens).ok_or(ErrorCode::MathOverflow)?;
+        *paid_total = paid_total.checked_add(paid).ok_or(ErrorCode::MathOverflow)?;
+
+        // Mints at the same time share a record
+        let record = MintRecord {
+            timestamp: now,
+            minted: self.minted,
+        };
+        let newest = self.mint_records().last().map(|newest| newest.timestamp);
+        if newest == Some(now) {
+            let newest = (self.mint_head as usize + self.mints.len() - 1) % self.mints.len();
+            self.mints[newest] = record;
+        } else if self.mints.len() < Self::MAX_MINT_RECORDS {
+            self.mints.push(record);
+        } else {
+            self.mints[self.mint_head as usize] = record;
+            self.mint_head = ((self.mint_head as usize + 1) % Self::MAX_MINT_RECORDS) as u8;
+        }
+        Ok(())
+    }
+
+    /// Count complete sets minted by book fills at `now`: `tokens` of each
+    /// outcome, for which the YES side paid `yes_paid` and the NO side the rest
+    pub fn record_set_mint(&mut self, now: i64, tokens: u64, yes_paid: u64) -> Result<()> {
+        let no_paid = tokens.checked_sub(yes_paid).ok_or(ErrorCode::MathUnderflow)?;
+        self.record_mint(now, true, tokens, yes_paid)?;
+        self.record_mint(now, false, tokens, no_paid)
+    }
+
+    /// Mint records from oldest to newest
+    fn mint_records(&self) -> impl Iterator<Item = &MintRecord> {
+        let (newer, older) = self.mints.split_at(self.mint_head as usize);
+        older.iter().chain(newer)
+    }
+
+    /// Record the price after a trade at `now`
+    pub fn record(&mut self, now: i64, price: u16) -> Result<()> {
+        self.cumulative = self.cumulative_at(now)?;
+        self.last_price = price;
+        self.last_update = now;
+
+        let newest = self.newest().ok_or(ErrorCode::InvalidTwapWindow)?;
+        if now - newest.timestamp < Self::OBSERVATION_INTERVAL {
+            return Ok(());
+        }
+
+        let observation = PriceObservation {
+            timestamp: now,
+            price,
+            cumulative: self.cumulative,
+            minted: self.minted,
+        };
+        if self.observations.len() < Self::MAX_OBSERVATIONS {
+            self.observations.push(observation);
+        } else {
+            self.observations[self.head as usize] = observation;
+            self.head = ((self.head as usize + 1) % Self::MAX_OBSERVATIONS) as u8;
+        }
+        Ok(())
+    }
+
+    fn newest(&self) -> Option<&PriceObservation> {
+        if self.observations.len() < Self::MAX_OBSERVATIONS {
+            self.observations.last()
+        } else {
+            let newest = (self.head as usize + Self::MAX_OBSERVATIONS - 1) % Self::MAX_OBSERVATIONS;
+            self.observations.get(newest)
+        }
+    }
+
+    /// Mints at or after `cutoff`, exact while the mint log reaches back to
+    /// it. After more than `MAX_MINT_RECORDS` mints since then, counts from
+    /// the last observation before it, so bets up to one interval early may
+    /// be included. Fails if the history no longer reaches back that far.
+    pub fn minted_since(&self, cutoff: i64) -> Result<MintTotals> {
+        if let Some(before) = self
+            .mint_records()
+            .filter(|record| record.timestamp < cutoff)
+            .last()
+        {
+            return self.minted.since(&before.minted);
+        }
+        // Every mint since the market opened is logged and at or after it
+        if self.mints.len() < Self::MAX_MINT_RECORDS {
+            return Ok(self.minted);
+        }
+
+        let from = self
+            .observations
+            .iter()
+            .filter(|observation| observation.timestamp < cutoff)
+            .max_by_key(|observation| observation.timestamp);
+        match from {
+            Some(from) => self.minted.since(&from.minted),
+            // Everything since the market opened, unless it was overwritten
+            None if self.observations.len() < Self::MAX_OBSERVATIONS => Ok(self.minted),
+            None => err!(ErrorCode::InsufficientPriceHistory),
+        }
+    }
+
+    /// TWAP up to `end` over all the history kept, or the last price if
+    /// there is none before `end`. Cancelled markets refund at this price.
+    pub fn settlement_price(&self, end: i64) -> Result<u16> {
+        let oldest = self
+            .observations
+            .iter()
+            .map(|observation| observation.timestamp)
+            .min()
+            .ok_or(ErrorCode::InsufficientPriceHistory)?;
+        if end <= oldest {
+            return Ok(self.last_price);
+        }
+        self.twap(end, end - oldest)
+    }
+
+    /// Time-weighted average YES price (bps) from the latest observation at
+    /// or before `now - window` up to `now`. Fails if the history does not
+    /// reach back that far.
+    pub fn twap(&self, now: i64, window: i64) -> Result<u16> {
+        require!(window > 0, ErrorCode::InvalidTwapWindow);
+        let start = now.checked_sub(window).ok_or(ErrorCode::MathUnderflow)?;
+
+        let from = self
+            .observations
+            .iter()
+            .filter(|observation| observation.timestamp <= start)
+            .max_by_key(|observation| observation.timestamp)
+            .ok_or(ErrorCode::InsufficientPriceHistory)?;
+
+        let elapsed = (now - from.timestamp) as u128;
+        let average = self
+            .cumulative_at(now)?
+            .checked_sub(from.cumulative)
+            .ok_or(ErrorCode::MathUnderflow)?
+            / elapsed;
+        Ok(average as u16)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const INTERVAL: i64 = PriceHistory::OBSERVATION_INTERVAL;
+
+    fn history(now: i64, price: u16) -> PriceHistory {
+        let mut history = PriceHistory {
+            market: Pubkey::default(),
+            last_price: 0,
+            last_update: 0,
+            cumulative: 0,
+            minted: MintTotals::default(),
+            head: 0,
+            observations: Vec::new(),
+            mint_head: 0,
+            mints: Vec::new(),
+            bump: 0,
+        };
+        history.init(Pubkey::default(), now, price, 0);
+        history
+    }
+
+    #[test]
+    fn test_twap() {
+        let mut history = history(0, 5_000);
+        history.record(INTERVAL, 6_000).unwrap();
+        history.record(2 * INTERVAL, 8_000).unwrap();
+
+        // 5,000 then 6,000 for one interval each
+        assert_eq!(history.twap(2 * INTERVAL, 2 * INTERVAL).unwrap(), 5_500);
+        // Last interval entirely at 8,000
+        assert_eq!(history.twap(3 * INTERVAL, INTERVAL).unwrap(), 8_000);
+        // Window reaching before the first observation
+        assert!(history.twap(2 * INTERVAL, 2 * INTERVAL + 1).is_err());
+        assert!(history.twap(INTERVAL, 0).is_err());
+    }
+
+    #[test]
+    fn test_single_trade_barely_moves_twap() {
+        let mut history = history(0, 5_000);
+        history.record(4 * INTERVAL, 5_000).unwrap();
+
+        // A spike one second before reading shifts a 1h TWAP by ~1 bps
+        history.record(8 * INTERVAL - 1, 9_000).unwrap();
+        let twap = history.twap(8 * INTERVAL, 4 * INTERVAL).unwrap();
+        assert_eq!(twap, 5_001);
+    }
+
+    #[test]
+    fn test_ring_buffer_wraps() {
+        let mut history = history(0, 5_000);
+        let trades = PriceHistory::MAX_OBSERVATIONS as i64 + 10;
+        for i in 1..=trades {
+            history.record(i * INTERVAL, 5_000).unwrap();
+            // Trades within the interval update the price but add no observation
+            history.record(i * INTERVAL + 1, 5_000).unwrap();
+        }
+
+        assert_eq!(history.observations.len(), PriceHistory::MAX_OBSERVATIONS);
+        assert_eq!(history.newest().unwrap().timestamp, trades * INTERVAL);
+        let now = trades * INTERVAL + 1;
+        assert_eq!(history.twap(now, 50 * INTERVAL).unwrap(), 5_000);
+        // The oldest observations were overwritten
+        assert!(history.twap(now, 100 * INTERVAL).is_err());
+    }
+
+    #[test]
+    fn test_settlement_price() {
+        let mut history = history(0, 5_000);
+        assert_eq!(history.settlement_price(0).unwrap(), 5_000);
+
+        // One late spike barely moves the average over the whole history
+        history.record(INTERVAL * 4 - 60, 9_000).unwrap();
+        assert_eq!(history.settlement_price(INTERVAL * 4).unwrap(), 5_066);
+    }
+
+    #[test]
+    fn test_minted_since_cutoff() {
+        let mut history = history(0, 5_000);
+        history.record_mint(10, true, 100, 50).unwrap();
+        history.record(10, 5_500).unwrap();
+        history.record_mint(INTERVAL, false, 40, 20).unwrap();
+        history.record(INTERVAL, 5_200).unwrap();
+        history.record_mint(INTERVAL + 60, true, 10, 9).unwrap();
+        history.record(INTERVAL + 60, 6_000).unwrap();
+
+        // Exactly the mints at or after the cutoff, even within one interval
+        let late = history.minted_since(INTERVAL + 30).unwrap();
+        assert_eq!(late, MintTotals { yes_tokens: 10, no_tokens: 0, yes_paid: 9, no_paid: 0 });
+        let late = history.minted_since(INTERVAL).unwrap();
+        assert_eq!(late, MintTotals { yes_tokens: 10, no_tokens: 40, yes_paid: 9, no_paid: 20 });
+        assert_eq!(history.minted_since(INTERVAL + 61).unwrap(), MintTotals::default());
+        // Before the first mint, everything is late
+        assert_eq!(history.minted_since(0).unwrap(), history.minted);
+    }
+
+    #[test]
+    fn test_mint_log_wraps() {
+        let mut history = history(0, 5_000);
+        // Mints at the same time share a record
+        history.record_mint(1, true, 5, 2).unwrap();
+        history.record_mint(1, false, 5, 3).unwrap();
+        assert_eq!(history.mints.len(), 1);
+
+        let mints = PriceHistory::MAX_MINT_RECORDS as i64 + 10;
+        for i in 2..=mints {
+            history.record_mint(i * 10, true, 1, 1).unwrap();
+            history.record(i * 10, 5_000).unwrap();
+        }
+        assert_eq!(history.mints.len(), PriceHistory::MAX_MINT_RECORDS);
+        assert_eq!(history.mint_records().last().unwrap().timestamp, mints * 10);
+
+        // Still exact while the log reaches back to the cutoff
+        let late = history.minted_since(mints * 10 - 5).unwrap();
+        assert_eq!(late, MintTotals { yes_tokens: 1, no_tokens: 0, yes_paid: 1, no_paid: 0 });
+        // Beyond it, counted from the observation before the cutoff
+        let late = history.minted_since(15).unwrap();
+        assert_eq!(late, history.minted);
+    }
+
+    #[test]
+    fn test_late_bets_rolled_back_pro_rata() {
+        let late = LateBets {
+            cutoff: 0,
+            minted: MintTotals { yes_tokens: 200, no_tokens: 50, yes_paid: 180, no_paid: 10 },
+            yes_supply: 1_000,
+            no_supply: 500,
+        };
+
+        // YES won: the 20 late YES tokens minted above their price come out of every YES token
+        assert_eq!(late.winning_value(1_000, true).unwrap(), 980);
+        assert_eq!(late.winning_value(100, true).unwrap(), 98);
+        // NO lost: the 10 paid in by late NO bets go back to NO holders
+        assert_eq!(late.losing_value(500, true).unwrap(), 10);
+        assert_eq!(late.losing_value(250, true).unwrap(), 5);
+    }
+}
@@ -1,4 +1,5 @@
 use anchor_lang::prelude::*;
+use super::LateBets;
 
 #[account]
 #[derive(InitSpace)]
@@ -13,6 +14,12 @@ pub struct Proposal {
     pub votes_for: u64,
     pub votes_against: u64,
     pub status: ProposalStatus,
+    /// Proposed before `end_time`; needs the early supermajority
+    pub early: bool,
+    /// When the proposer says the event concluded (early proposals only)
+    pub event_time: i64,
+    /// AMM bets after `event_time` to roll back if the proposal passes
+    pub late_bets: Option<LateBets>,
     pub bump: u8,
 }
 
@@ -0,0 +1,149 @@
+use anchor_lang::prelude::*;
+use crate::errors::ErrorCode;
+use crate::state::{PriceCondition, ResolutionMode};
+
+/// Recurring market series. Anyone can spawn the next market once it is due;
+/// its liquidity comes from the template's funded vault.
+#[account]
+#[derive(InitSpace)]
+pub struct MarketTemplate {
+    pub creator: Pubkey,
+    pub template_id: u64,
+    pub collateral_mint: Pubkey,
+    /// Question with `{index}` and `{date}` (end date, UTC) placeholders
+    #[max_len(200)]
+    pub question_pattern: String,
+    #[max_len(500)]
+    pub description: String,
+    /// Registered category id, checked again at each spawn
+    pub category: u16,
+    #[max_len(200)]
+    pub resolution_source: String,
+    pub price_condition: Option<PriceCondition>,
+    pub resolution_mode: ResolutionMode,
+    pub resolver: Option<Pubkey>,
+    pub voting_period: i64,
+    pub resolution_grace: i64,
+    /// Seconds from a market's scheduled start to its end time
+    pub duration: i64,
+    /// Seconds between scheduled markets
+    pub interval: i64,
+    /// Collateral each spawned market is seeded with
+    pub liquidity: u64,
+    /// Index of the next market in the series
+    pub series_index: u64,
+    pub next_spawn_at: i64,
+    pub last_market: Option<Pubkey>,
+    pub active: bool,
+    pub bump: u8,
+}
+
+impl MarketTemplate {
+    /// Scheduled start of the next market at `now` and the number of slots
+    /// missed before it. Missed slots whose markets would already have
+    /// ended are skipped. `None` if the next market is not due yet.
+    pub fn due_slot(&self, now: i64) -> Result<Option<(u64, i64)>> {
+        if now < self.next_spawn_at {
+            return Ok(None);
+        }
+
+        let overdue = now - self.next_spawn_at;
+        let skipped = if overdue >= self.duration {
+            (overdue - self.duration) / self.interval + 1
+        } else {
+            0
+        };
+        let slot = skipped
+            .checked_mul(self.interval)
+            .and_then(|delay| delay.checked_add(self.next_spawn_at))
+            .ok_or(ErrorCode::MathOverflow)?;
+        if slot > now {
+            return Ok(None);
+        }
+        Ok(Some((skipped as u64, slot)))
+    }
+
+    /// Question for the market at `index` ending at `end_time`
+    pub fn question(&self, index: u64, end_time: i64) -> String {
+        self.question_pattern
+            .replace("{index}", &index.to_string())
+            .replace("{date}", &utc_date(end_time))
+    }
+}
+
+/// `YYYY-MM-DD` for a unix timestamp
+pub fn utc_date(timestamp: i64) -> String {
+    // Days to civil date, from Howard Hinnant's date algorithms
+    let z = timestamp.div_euclid(86_400) + 719_468;
+    let era = z.div_euclid(146_097);
+    let day_of_era = z - era * 146_097;
+    let year_of_era = (day_of_era - day_of_era / 1_460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let mp = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = year_of_era + era * 400 + i64::from(month <= 2);
+
+    format!("{:04}-{:02}-{:02}", year, month, day)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DAY: i64 = 86_400;
+
+    fn template(next_spawn_at: i64) -> MarketTemplate {
+        MarketTemplate {
+            creator: Pubkey::default(),
+            template_id: 0,
+            collateral_mint: Pubkey::default(),
+            question_pattern: String::from("Will BTC close above 100k on {date}? (#{index})"),
+            description: String::new(),
+            category: 0,
+            resolution_source: String::new(),
+            price_condition: None,
+            resolution_mode: ResolutionMode::DaoVote,
+            resolver: None,
+            voting_period: 0,
+            resolution_grace: 0,
+            duration: DAY,
+            interval: DAY,
+            liquidity: 0,
+            series_index: 0,
+            next_spawn_at,
+            last_market: None,
+            active: true,
+            bump: 0,
+        }
+    }
+
+    #[test]
+    fn test_utc_date() {
+        assert_eq!(utc_date(0), "1970-01-01");
+        assert_eq!(utc_date(951_782_400), "2000-02-29");
+        assert_eq!(utc_date(1_767_225_599), "2025-12-31");
+        assert_eq!(utc_date(1_767_225_600), "2026-01-01");
+    }
+
+    #[test]
+    fn test_question() {
+        let template = template(0);
+        assert_eq!(
+            template.question(7, 1_767_225_600),
+            "Will BTC close above 100k on 2026-01-01? (#7)"
+        );
+    }
+
+    #[test]
+    fn test_due_slot() {
+        let template = template(10 * DAY);
+        assert_eq!(template.due_slot(10 * DAY - 1).unwrap(), None);
+        assert_eq!(template.due_slot(10 * DAY).unwrap(), Some((0, 10 * DAY)));
+        // Late, but the scheduled market has not ended yet
+        assert_eq!(template.due_slot(11 * DAY - 1).unwrap(), Some((0, 10 * DAY)));
+        // Missed slots whose markets would have ended are skipped
+        assert_eq!(template.due_slot(11 * DAY).unwrap(), Some((1, 11 * DAY)));
+        assert_eq!(template.due_slot(13 * DAY + 5).unwrap(), Some((3, 13 * DAY)));
+    }
+}
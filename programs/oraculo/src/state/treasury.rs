@@ -0,0 +1,58 @@
+use anchor_lang::prelude::*;
+use super::ProposalStatus;
+
+/// Program-owned treasury. Token balances live in per-mint vaults
+/// seeded with `[b"treasury_vault", mint]` and owned by this PDA.
+#[account]
+#[derive(InitSpace)]
+pub struct Treasury {
+    pub config: Pubkey,
+    pub spend_proposal_count: u64,
+    pub bump: u8,
+}
+
+impl Treasury {
+    /// Delay between a spend passing its vote and becoming executable
+    pub const TIMELOCK: i64 = 2 * 24 * 60 * 60;
+    /// Voting window for spend proposals
+    pub const VOTING_PERIOD: i64 = 48 * 60 * 60;
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct TreasuryProposal {
+    pub id: u64,
+    pub proposer: Pubkey,
+    pub mint: Pubkey,
+    pub recipient: Pubkey,
+    pub amount: u64,
+    #[max_len(200)]
+    pub memo: String,
+    pub proposed_at: i64,
+    pub voting_ends_at: i64,
+    pub executable_at: i64,
+    pub votes_for: u64,
+    pub votes_against: u64,
+    /// Governance tokens locked by the proposer until the spend is finalized
+    pub stake: u64,
+    pub status: ProposalStatus,
+    pub bump: u8,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq)]
+pub enum TreasuryMovementKind {
+    ProtocolFee,
+    SlashedStake,
+    Spend,
+}
+
+/// Emitted for every token movement into or out of the treasury
+#[event]
+pub struct TreasuryMovement {
+    pub kind: TreasuryMovementKind,
+    pub mint: Pubkey,
+    pub amount: u64,
+    pub counterparty: Pubkey,
+    pub reference: Pubkey,
+    pub timestamp: i64,
+}
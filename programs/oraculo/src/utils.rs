@@ -1,5 +1,48 @@
 use anchor_lang::prelude::*;
+use anchor_spl::token_2022::spl_token_2022::{
+    self,
+    extension::{transfer_fee::TransferFeeConfig, BaseStateWithExtensions, StateWithExtensions},
+};
 use crate::errors::ErrorCode;
+use crate::state::{PriceComparison, PRICE_SCALE_BPS};
+
+/// Fee a Token-2022 transfer-fee mint withholds when `amount` is transferred.
+/// Zero for legacy SPL mints and Token-2022 mints without the extension.
+pub fn transfer_fee(mint: &AccountInfo, amount: u64) -> Result<u64> {
+    if *mint.owner != spl_token_2022::ID {
+        return Ok(0);
+    }
+
+    let data = mint.try_borrow_data()?;
+    let mint = StateWithExtensions::<spl_token_2022::state::Mint>::unpack(&data)?;
+    let fee = match mint.get_extension::<TransferFeeConfig>() {
+        Ok(config) => config
+            .calculate_epoch_fee(Clock::get()?.epoch, amount)
+            .ok_or(ErrorCode::MathOverflow)?,
+        Err(_) => 0,
+    };
+
+    Ok(fee)
+}
+
+/// Fee to add to `amount` so that `amount` arrives after a Token-2022
+/// transfer-fee mint withholds its fee. Zero where `transfer_fee` is zero.
+pub fn inverse_transfer_fee(mint: &AccountInfo, amount: u64) -> Result<u64> {
+    if *mint.owner != spl_token_2022::ID {
+        return Ok(0);
+    }
+
+    let data = mint.try_borrow_data()?;
+    let mint = StateWithExtensions::<spl_token_2022::state::Mint>::unpack(&data)?;
+    let fee = match mint.get_extension::<TransferFeeConfig>() {
+        Ok(config) => config
+            .calculate_inverse_epoch_fee(Clock::get()?.epoch, amount)
+            .ok_or(ErrorCode::MathOverflow)?,
+        Err(_) => 0,
+    };
+
+    Ok(fee)
+}
 
 /// Constant product bonding curve: x * y = k
 /// Returns (cost_in_usdc, tokens_out)
@@ -44,6 +87,237 @@ pub fn calculate_bonding_curve(
     }
 }
 
+/// Implied YES probability in basis points; pools grow with the side bet on
+pub fn implied_yes_price(yes_pool: u64, no_pool: u64) -> Result<u16> {
+    let total = (yes_pool as u128)
+        .checked_add(no_pool as u128)
+        .ok_or(ErrorCode::MathOverflow)?;
+    require!(total > 0, ErrorCode::DivisionByZero);
+
+    Ok(((yes_pool as u128) * PRICE_SCALE_BPS as u128 / total) as u16)
+}
+
+/// Collateral refunded for `amount` outcome base units of a cancelled market,
+/// valued at YES price `yes_price`
+pub fn refund_value(amount: u64, collateral_decimals: u8, yes_price: u16, outcome: bool) -> Result<u64> {
+    let price = if outcome {
+        yes_price
+    } else {
+        PRICE_SCALE_BPS.checked_sub(yes_price).ok_or(ErrorCode::MathUnderflow)?
+    };
+    let par = outcome_to_collateral(amount, collateral_decimals)? as u128;
+
+    Ok((par * price as u128 / PRICE_SCALE_BPS as u128) as u64)
+}
+
+/// Split of an order between resting book orders and the AMM
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Route {
+    /// Order book lots taken, cheapest first
+    pub book_lots: u64,
+    pub book_cost: u64,
+    /// Shares bought from the AMM
+    pub amm_shares: u64,
+    /// AMM cost including fees
+    pub amm_cost: u64,
+}
+
+impl Route {
+    pub fn total_cost(&self) -> Result<u64> {
+        Ok(self.book_cost.checked_add(self.amm_cost).ok_or(ErrorCode::MathOverflow)?)
+    }
+}
+
+/// Cheapest way to buy `shares` from resting orders `levels` (cost per lot,
+/// lots; best first) and the AMM, where `amm_cost(shares)` includes fees.
+/// Book cost is linear within each resting order and the curve's cost is
+/// concave, so the cheapest split lies on a resting order boundary and only
+/// those points are compared.
+pub fn route_order(
+    levels: &[(u64, u64)],
+    shares: u64,
+    amm_cost: impl Fn(u64) -> Result<u64>,
+) -> Result<Route> {
+    let lot = PRICE_SCALE_BPS as u64;
+    let max_lots = shares / lot;
+
+    let candidate = |book_lots: u64, book_cost: u64| -> Result<Route> {
+        let amm_shares = shares - book_lots * lot;
+        let amm_cost = if amm_shares > 0 { amm_cost(amm_shares)? } else { 0 };
+        Ok(Route { book_lots, book_cost, amm_shares, amm_cost })
+    };
+
+    let mut best = candidate(0, 0)?;
+    let (mut book_lots, mut book_cost) = (0u64, 0u64);
+    for &(price, lots) in levels {
+        if book_lots == max_lots {
+            break;
+        }
+        let taken = lots.min(max_lots - book_lots);
+        book_lots += taken;
+        book_cost = taken
+            .checked_mul(price)
+            .and_then(|cost| cost.checked_add(book_cost))
+            .ok_or(ErrorCode::MathOverflow)?;
+
+        let route = candidate(book_lots, book_cost)?;
+        if route.total_cost()? < best.total_cost()? {
+            best = route;
+        }
+    }
+
+    Ok(best)
+}
+
+/// Protocol fee on `amount` in basis points, rounded down
+pub fn calculate_fee(amount: u64, fee_bps: u16) -> Result<u64> {
+    let fee = (amount as u128)
+        .checked_mul(fee_bps as u128)
+        .ok_or(ErrorCode::MathOverflow)?
+        .checked_div(10_000)
+        .ok_or(ErrorCode::DivisionByZero)?;
+
+    Ok(fee as u64)
+}
+
+/// Compare `price * 10^price_expo` against `threshold * 10^threshold_expo`
+pub fn price_condition_met(
+    price: i64,
+    price_expo: i32,
+    threshold: i64,
+    threshold_expo: i32,
+    comparison: PriceComparison,
+) -> Result<bool> {
+    // Rescale both sides to the smaller exponent
+    let rescale = |value: i64, expo: i32| -> Result<i128> {
+        let factor = 10i128
+            .checked_pow(expo.abs_diff(price_expo.min(threshold_expo)))
+            .ok_or(ErrorCode::MathOverflow)?;
+        (value as i128)
+            .checked_mul(factor)
+            .ok_or(ErrorCode::MathOverflow.into())
+    };
+    let price = rescale(price, price_expo)?;
+    let threshold = rescale(threshold, threshold_expo)?;
+
+    Ok(match comparison {
+        PriceComparison::GreaterThan => price > threshold,
+        PriceComparison::GreaterOrEqual => price >= threshold,
+        PriceComparison::LessThan => price < threshold,
+        PriceComparison::LessOrEqual => price <= threshold,
+    })
+}
+
+/// Decimals of every YES/NO outcome mint
+pub const OUTCOME_DECIMALS: u8 = 9;
+
+/// Rescale `amount` between mints with different decimals, rounding down
+pub fn scale_amount(amount: u64, from_decimals: u8, to_decimals: u8) -> Result<u64> {
+    let scaled = if to_decimals >= from_decimals {
+        let factor = 10u128
+            .checked_pow((to_decimals - from_decimals) as u32)
+            .ok_or(ErrorCode::MathOverflow)?;
+        (amount as u128)
+            .checked_mul(factor)
+            .ok_or(ErrorCode::MathOverflow)?
+    } else {
+        let factor = 10u128
+            .checked_pow((from_decimals - to_decimals) as u32)
+            .ok_or(ErrorCode::MathOverflow)?;
+        (amount as u128) / factor
+    };
+
+    u64::try_from(scaled).map_err(|_| ErrorCode::MathOverflow.into())
+}
+
+/// Outcome base units for `amount` collateral base units.
+/// One whole outcome token is backed by one whole collateral unit.
+pub fn collateral_to_outcome(amount: u64, collateral_decimals: u8) -> Result<u64> {
+    scale_amount(amount, collateral_decimals, OUTCOME_DECIMALS)
+}
+
+/// Collateral base units redeemable for `amount` outcome base units
+pub fn outcome_to_collateral(amount: u64, collateral_decimals: u8) -> Result<u64> {
+    scale_amount(amount, OUTCOME_DECIMALS, collateral_decimals)
+}
+
+/// Outcome base units in one order book lot, worth `PRICE_SCALE_BPS`
+/// collateral base units at par
+pub fn lot_size(collateral_decimals: u8) -> Result<u64> {
+    collateral_to_outcome(PRICE_SCALE_BPS as u64, collateral_decimals)
+}
+
+/// Token Metadata limits on name and symbol length (bytes)
+pub const METADATA_MAX_NAME_LEN: usize = 32;
+
+/// Wallet-facing name for an outcome token, e.g. "YES: Will BTC hit $100K...".
+/// Truncated on a char boundary to fit the Token Metadata name limit.
+pub fn outcome_token_name(question: &str, outcome: bool) -> String {
+    let mut name = String::from(if outcome { "YES: " } else { "NO: " });
+    for c in question.chars() {
+        if name.len() + c.len_utf8() > METADATA_MAX_NAME_LEN {
+            break;
+        }
+        name.push(c);
+    }
+    name
+}
+
+pub fn outcome_token_symbol(outcome: bool) -> String {
+    String::from(if outcome { "YES" } else { "NO" })
+}
+
+/// Fixed-point scale for cumulative reward-per-share accumulators
+pub const REWARD_PRECISION: u128 = 1_000_000_000_000;
+
+/// Emissions for `elapsed` seconds split into (lp_rewards, volume_rewards)
+pub fn split_emissions(emission_rate: u64, elapsed: u64, lp_weight_bps: u16) -> Result<(u64, u64)> {
+    let total = emission_rate
+        .checked_mul(elapsed)
+        .ok_or(ErrorCode::MathOverflow)?;
+    let lp = (total as u128)
+        .checked_mul(lp_weight_bps as u128)
+        .ok_or(ErrorCode::MathOverflow)?
+        .checked_div(10_000)
+        .ok_or(ErrorCode::DivisionByZero)? as u64;
+
+    Ok((lp, total - lp))
+}
+
+/// New accumulator value after distributing `reward` over `total_shares`.
+/// With no shares outstanding the reward is not distributed.
+pub fn accumulate_reward_per_share(acc: u128, reward: u64, total_shares: u64) -> Result<u128> {
+    if total_shares == 0 {
+        return Ok(acc);
+    }
+
+    let delta = (reward as u128)
+        .checked_mul(REWARD_PRECISION)
+        .ok_or(ErrorCode::MathOverflow)?
+        .checked_div(total_shares as u128)
+        .ok_or(ErrorCode::DivisionByZero)?;
+
+    Ok(acc.checked_add(delta).ok_or(ErrorCode::MathOverflow)?)
+}
+
+/// `shares * acc`, the reward debt for a position at accumulator `acc`
+pub fn reward_debt(shares: u64, acc: u128) -> Result<u128> {
+    Ok((shares as u128)
+        .checked_mul(acc)
+        .ok_or(ErrorCode::MathOverflow)?)
+}
+
+/// Rewards earned by `shares` since `debt` was recorded at the old accumulator
+pub fn pending_reward(shares: u64, acc: u128, debt: u128) -> Result<u64> {
+    let earned = reward_debt(shares, acc)?
+        .checked_sub(debt)
+        .ok_or(ErrorCode::MathUnderflow)?
+        .checked_div(REWARD_PRECISION)
+        .ok_or(ErrorCode::DivisionByZero)?;
+
+    Ok(earned as u64)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -60,4 +334,162 @@ mod tests {
         assert!(cost > 0);
         assert!(cost < amount); // Should cost less than 1:1
     }
+
+    #[test]
+    fn test_implied_yes_price() {
+        assert_eq!(implied_yes_price(100, 100).unwrap(), 5_000);
+        assert_eq!(implied_yes_price(300, 100).unwrap(), 7_500);
+        assert_eq!(implied_yes_price(0, 100).unwrap(), 0);
+        assert_eq!(implied_yes_price(u64::MAX, u64::MAX).unwrap(), 5_000);
+        assert!(implied_yes_price(0, 0).is_err());
+    }
+
+    #[test]
+    fn test_refund_value() {
+        let whole = 1_000_000_000;
+        // One whole token of a 6-decimal collateral at 30% / 70%
+        assert_eq!(refund_value(whole, 6, 3_000, true).unwrap(), 300_000);
+        assert_eq!(refund_value(whole, 6, 3_000, false).unwrap(), 700_000);
+        // A complete set refunds one collateral unit
+        assert_eq!(
+            refund_value(whole, 6, 3_000, true).unwrap() + refund_value(whole, 6, 3_000, false).unwrap(),
+            1_000_000
+        );
+    }
+
+    #[test]
+    fn test_route_order() {
+        let lot = PRICE_SCALE_BPS as u64;
+        let flat_amm = |price: u64| move |shares: u64| Ok(shares * price / lot);
+
+        // Book at 40% then 70% against an AMM at 55%: take only the cheap level
+        let levels = [(4_000, 5), (7_000, 100)];
+        let route = route_order(&levels, 20 * lot, flat_amm(5_500)).unwrap();
+        assert_eq!(route, Route { book_lots: 5, book_cost: 20_000, amm_shares: 15 * lot, amm_cost: 82_500 });
+
+        // An AMM cheaper than every level takes everything
+        let route = route_order(&levels, 20 * lot, flat_amm(3_000)).unwrap();
+        assert_eq!((route.book_lots, route.amm_shares), (0, 20 * lot));
+
+        // A dear AMM only fills what the book cannot, including odd shares
+        let route = route_order(&levels, 20 * lot + 7, flat_amm(9_000)).unwrap();
+        assert_eq!((route.book_lots, route.book_cost, route.amm_shares), (20, 125_000, 7));
+
+        // Concave AMM cost: skipping a slightly cheaper level can still win
+        let bulk_amm = |shares: u64| Ok(if shares >= 20 * lot { shares * 4_000 / lot } else { shares * 6_000 / lot });
+        let route = route_order(&[(5_000, 5)], 20 * lot, bulk_amm).unwrap();
+        assert_eq!((route.book_lots, route.amm_cost), (0, 80_000));
+    }
+
+    #[test]
+    fn test_calculate_fee() {
+        assert_eq!(calculate_fee(1_000_000, 0).unwrap(), 0);
+        assert_eq!(calculate_fee(1_000_000, 100).unwrap(), 10_000); // 1%
+        assert_eq!(calculate_fee(999, 100).unwrap(), 9); // rounds down
+        assert_eq!(calculate_fee(u64::MAX, 1_000).unwrap(), u64::MAX / 10);
+    }
+
+    #[test]
+    fn test_price_condition_met() {
+        // BTC $101,234.56 (expo -8) against $100,000 (expo 0)
+        let price = 10_123_456_000_000;
+        assert!(price_condition_met(price, -8, 100_000, 0, PriceComparison::GreaterThan).unwrap());
+        assert!(price_condition_met(price, -8, 100_000, 0, PriceComparison::GreaterOrEqual).unwrap());
+        assert!(!price_condition_met(price, -8, 100_000, 0, PriceComparison::LessThan).unwrap());
+        assert!(!price_condition_met(price, -8, 100_000, 0, PriceComparison::LessOrEqual).unwrap());
+
+        // Equal prices at different exponents
+        assert!(price_condition_met(500_000, -2, 5_000, 0, PriceComparison::GreaterOrEqual).unwrap());
+        assert!(price_condition_met(500_000, -2, 5_000, 0, PriceComparison::LessOrEqual).unwrap());
+        assert!(!price_condition_met(500_000, -2, 5_000, 0, PriceComparison::GreaterThan).unwrap());
+        assert!(!price_condition_met(500_000, -2, 5_000, 0, PriceComparison::LessThan).unwrap());
+
+        // Threshold finer than the feed
+        assert!(price_condition_met(5_000, 0, 499_999, -2, PriceComparison::GreaterThan).unwrap());
+        assert!(!price_condition_met(4_999, 0, 499_999, -2, PriceComparison::GreaterThan).unwrap());
+
+        // Negative prices compare correctly
+        assert!(price_condition_met(-5, 0, 0, 0, PriceComparison::LessThan).unwrap());
+
+        // Exponent gap too wide to rescale
+        assert!(price_condition_met(1, 0, 1, -60, PriceComparison::GreaterThan).is_err());
+    }
+
+    #[test]
+    fn test_outcome_collateral_scaling() {
+        // 1 whole outcome token <-> 1 whole collateral unit for common mints
+        for decimals in [0u8, 2, 6, 8, 9] {
+            let one_collateral = 10u64.pow(decimals as u32);
+            let one_outcome = 10u64.pow(OUTCOME_DECIMALS as u32);
+            assert_eq!(collateral_to_outcome(one_collateral, decimals).unwrap(), one_outcome);
+            assert_eq!(outcome_to_collateral(one_outcome, decimals).unwrap(), one_collateral);
+        }
+
+        // USDC: 1.5 USDC buys 1.5 YES, not 0.0015
+        assert_eq!(collateral_to_outcome(1_500_000, 6).unwrap(), 1_500_000_000);
+        assert_eq!(outcome_to_collateral(1_500_000_000, 6).unwrap(), 1_500_000);
+
+        // Collateral with more decimals than outcome tokens
+        assert_eq!(collateral_to_outcome(10u64.pow(12), 12).unwrap(), 10u64.pow(9));
+        assert_eq!(outcome_to_collateral(10u64.pow(9), 12).unwrap(), 10u64.pow(12));
+        assert_eq!(collateral_to_outcome(999, 12).unwrap(), 0);
+
+        // Redemption rounds down, never paying out more than was deposited
+        assert_eq!(outcome_to_collateral(999, 6).unwrap(), 0);
+        assert_eq!(outcome_to_collateral(1_999, 6).unwrap(), 1);
+        for decimals in [0u8, 6, 9, 12] {
+            for amount in [0u64, 1, 7, 1_000, 123_456_789] {
+                let outcome = collateral_to_outcome(amount, decimals).unwrap();
+                assert!(outcome_to_collateral(outcome, decimals).unwrap() <= amount);
+            }
+        }
+
+        // Same decimals is the identity
+        assert_eq!(scale_amount(42, 9, 9).unwrap(), 42);
+
+        // Scaling up can overflow u64
+        assert!(collateral_to_outcome(u64::MAX, 6).is_err());
+        assert!(scale_amount(1, 0, 30).is_err());
+        assert_eq!(scale_amount(u64::MAX, 30, 0).unwrap(), 0);
+    }
+
+    #[test]
+    fn test_outcome_token_name() {
+        assert_eq!(outcome_token_name("Rain tomorrow?", true), "YES: Rain tomorrow?");
+        assert_eq!(outcome_token_name("Rain tomorrow?", false), "NO: Rain tomorrow?");
+
+        let long = outcome_token_name("Will BTC hit $100K by end of 2025?", true);
+        assert_eq!(long, "YES: Will BTC hit $100K by end o");
+        assert_eq!(long.len(), METADATA_MAX_NAME_LEN);
+
+        // Never splits a multi-byte character
+        let accented = outcome_token_name("¿Ganará el candidato en las elecciones?", false);
+        assert!(accented.len() <= METADATA_MAX_NAME_LEN);
+        assert!(accented.starts_with("NO: ¿Ganará"));
+    }
+
+    #[test]
+    fn test_split_emissions() {
+        assert_eq!(split_emissions(100, 10, 7_000).unwrap(), (700, 300));
+        assert_eq!(split_emissions(100, 10, 10_000).unwrap(), (1_000, 0));
+        assert_eq!(split_emissions(100, 10, 0).unwrap(), (0, 1_000));
+        assert!(split_emissions(u64::MAX, 2, 5_000).is_err());
+    }
+
+    #[test]
+    fn test_reward_accumulator() {
+        // Alice holds 300 of 400 shares when 1_000 tokens are emitted
+        let acc = accumulate_reward_per_share(0, 1_000, 400).unwrap();
+        assert_eq!(pending_reward(300, acc, 0).unwrap(), 750);
+        assert_eq!(pending_reward(100, acc, 0).unwrap(), 250);
+
+        // Bob joins with 400 shares; his debt excludes the first round
+        let bob_debt = reward_debt(400, acc).unwrap();
+        let acc = accumulate_reward_per_share(acc, 800, 800).unwrap();
+        assert_eq!(pending_reward(400, acc, bob_debt).unwrap(), 400);
+        assert_eq!(pending_reward(300, acc, 0).unwrap(), 1_050);
+
+        // No shares: nothing distributed
+        assert_eq!(accumulate_reward_per_share(acc, 500, 0).unwrap(), acc);
+    }
 }
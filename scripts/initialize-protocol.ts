@@ -25,7 +25,7 @@ async function initializeProtocol() {
 
   try {
     await governanceProgram.methods
-      .initialize(9)
+      .initialize(9, new anchor.BN("1000000000000000000")) // 1B tokens max supply
       .accounts({
         config: govConfigPDA,
         mint: govMintPDA,
@@ -56,7 +56,9 @@ async function initializeProtocol() {
         new anchor.BN(100_000_000), // min 100 USDC
         new anchor.BN(1000_000_000_000), // 1000 tokens stake
         new anchor.BN(10_000_000_000_000), // 10K quorum
-        66 // 66% supermajority
+        66, // 66% supermajority
+        provider.wallet.publicKey, // guardian
+        100 // 1% protocol fee
       )
       .accounts({
         config: configPDA,
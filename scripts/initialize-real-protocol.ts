@@ -25,7 +25,7 @@ async function initializeRealProtocol() {
 
   try {
     await governanceProgram.methods
-      .initialize(9)
+      .initialize(9, new anchor.BN("1000000000000000000")) // 1B tokens max supply
       .accounts({
         config: govConfigPDA,
         mint: govMintPDA,
@@ -56,7 +56,9 @@ async function initializeRealProtocol() {
         new anchor.BN(100_000_000), // min 100 USDC
         new anchor.BN(1000_000_000_000), // 1000 tokens stake
         new anchor.BN(10_000_000_000_000), // 10K quorum
-        66 // 66% supermajority
+        66, // 66% supermajority
+        provider.wallet.publicKey, // guardian
+        100 // 1% protocol fee
       )
       .accounts({
         config: configPDA,
@@ -71,6 +73,51 @@ async function initializeRealProtocol() {
     console.log("Protocolo ya inicializado");
   }
 
+  // Register collateral mints (min bet, min liquidity in base units)
+  const collaterals = [
+    {
+      name: "USDC",
+      mint: new PublicKey("EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v"),
+      minBet: new anchor.BN(1_000_000), // 1 USDC
+      minLiquidity: new anchor.BN(100_000_000), // 100 USDC
+    },
+    {
+      name: "USDT",
+      mint: new PublicKey("Es9vMFrzaCERmJfrF4H2FYD4KCoNkY11McCe8BenwNYB"),
+      minBet: new anchor.BN(1_000_000), // 1 USDT
+      minLiquidity: new anchor.BN(100_000_000), // 100 USDT
+    },
+    {
+      name: "wSOL",
+      mint: new PublicKey("So11111111111111111111111111111111111111112"),
+      minBet: new anchor.BN(10_000_000), // 0.01 SOL
+      minLiquidity: new anchor.BN(1_000_000_000), // 1 SOL
+    },
+  ];
+
+  for (const collateral of collaterals) {
+    const [collateralPDA] = PublicKey.findProgramAddressSync(
+      [Buffer.from("collateral"), collateral.mint.toBuffer()],
+      program.programId
+    );
+
+    try {
+      await program.methods
+        .registerCollateral(collateral.minBet, collateral.minLiquidity)
+        .accounts({
+          config: configPDA,
+          collateral: collateralPDA,
+          mint: collateral.mint,
+          authority: provider.wallet.publicKey,
+        })
+        .rpc();
+
+      console.log(`✅ Colateral ${collateral.name} registrado`);
+    } catch (err) {
+      console.log(`Colateral ${collateral.name} ya registrado`);
+    }
+  }
+
   console.log("\n🎉 Protocolo Oráculo REAL listo!");
   console.log("Governance Mint:", govMintPDA.toString());
   console.log("Config PDA:", configPDA.toString());
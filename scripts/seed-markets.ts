@@ -7,11 +7,38 @@ import {
   mintTo,
 } from "@solana/spl-token";
 
+const TOKEN_METADATA_PROGRAM_ID = new PublicKey(
+  "metaqbxxUerdq28cj1RbAWkYQm3ybzjb6a8bt518x1s"
+);
+
+const metadataPDA = (mint: PublicKey) =>
+  PublicKey.findProgramAddressSync(
+    [Buffer.from("metadata"), TOKEN_METADATA_PROGRAM_ID.toBuffer(), mint.toBuffer()],
+    TOKEN_METADATA_PROGRAM_ID
+  )[0];
+
+// The original six buckets keep their ids in the category registry
+const SEED_CATEGORIES = [
+  "Crypto",
+  "Sports",
+  "Politics",
+  "Entertainment",
+  "Technology",
+  "Other",
+];
+
+const categoryPDA = (id: number, programId: PublicKey) =>
+  PublicKey.findProgramAddressSync(
+    [Buffer.from("category"), new anchor.BN(id).toArrayLike(Buffer, "le", 2)],
+    programId
+  )[0];
+
 const SEED_MARKETS = [
   {
     question: "Will BTC hit $100K by end of 2025?",
     description: "Bitcoin price prediction based on major exchange data",
     category: 0, // Crypto
+    tags: ["bitcoin"],
     daysUntilEnd: 60,
     source: "https://api.coingecko.com/btc",
     liquidity: 500_000_000,
@@ -20,6 +47,7 @@ const SEED_MARKETS = [
     question: "Will ETH surpass $5K in Q1 2026?",
     description: "Ethereum price milestone prediction",
     category: 0,
+    tags: ["ethereum"],
     daysUntilEnd: 90,
     source: "https://api.coingecko.com/eth",
     liquidity: 300_000_000,
@@ -28,6 +56,7 @@ const SEED_MARKETS = [
     question: "Will Solana process 100K TPS this year?",
     description: "Network performance milestone for Solana blockchain",
     category: 4, // Technology
+    tags: ["solana"],
     daysUntilEnd: 120,
     source: "https://explorer.solana.com/metrics",
     liquidity: 200_000_000,
@@ -77,15 +106,42 @@ async function main() {
     program.programId
   );
 
+  // Allow the mock USDC mint as collateral
+  const [collateralPDA] = PublicKey.findProgramAddressSync(
+    [Buffer.from("collateral"), usdcMint.toBuffer()],
+    program.programId
+  );
+
+  await program.methods
+    .registerCollateral(
+      new anchor.BN(1_000_000), // 1 USDC min bet
+      new anchor.BN(100_000_000) // 100 USDC min liquidity
+    )
+    .accounts({
+      config: configPDA,
+      collateral: collateralPDA,
+      mint: usdcMint,
+      authority: provider.wallet.publicKey,
+    })
+    .rpc();
+
+  for (const [id, name] of SEED_CATEGORIES.entries()) {
+    await program.methods
+      .createCategory(id, name, null, new anchor.BN(0), null)
+      .accounts({
+        config: configPDA,
+        category: categoryPDA(id, program.programId),
+        parentCategory: null,
+        authority: provider.wallet.publicKey,
+      })
+      .rpc();
+  }
+
   for (const marketData of SEED_MARKETS) {
     try {
-      const timestamp = Math.floor(Date.now() / 1000);
+      const { totalMarkets } = await program.account.config.fetch(configPDA);
       const [marketPDA] = PublicKey.findProgramAddressSync(
-        [
-          Buffer.from("market"),
-          provider.wallet.publicKey.toBuffer(),
-          new anchor.BN(timestamp).toArrayLike(Buffer, "le", 8),
-        ],
+        [Buffer.from("market"), totalMarkets.toArrayLike(Buffer, "le", 8)],
         program.programId
       );
 
@@ -109,23 +165,36 @@ async function main() {
       );
 
       await program.methods
-        .createMarket(
-          marketData.question,
-          marketData.description,
-          marketData.category,
+        .createMarket({
+          question: marketData.question,
+          description: marketData.description,
+          category: marketData.category,
           endTime,
-          marketData.source,
-          new anchor.BN(marketData.liquidity)
-        )
+          resolutionSource: marketData.source,
+          initialLiquidity: new anchor.BN(marketData.liquidity),
+          priceCondition: null,
+          resolutionMode: { daoVote: {} },
+          resolver: null,
+          votingPeriod: null, // governance default
+          resolutionGrace: null, // governance default
+          metadata: null,
+          tags: marketData.tags,
+        })
         .accounts({
           config: configPDA,
           market: marketPDA,
           creator: provider.wallet.publicKey,
-          creatorUsdc: seederUSDC,
+          creatorCollateral: seederUSDC,
           liquidityVault: vaultPDA,
           yesTokenMint: yesMintPDA,
           noTokenMint: noMintPDA,
-          usdcMint: usdcMint,
+          yesMetadata: metadataPDA(yesMintPDA),
+          noMetadata: metadataPDA(noMintPDA),
+          collateral: collateralPDA,
+          categoryAccount: categoryPDA(marketData.category, program.programId),
+          parentCategoryAccount: null,
+          collateralMint: usdcMint,
+          tokenMetadataProgram: TOKEN_METADATA_PROGRAM_ID,
         })
         .rpc();
 
@@ -2,26 +2,66 @@ import * as anchor from "@coral-xyz/anchor";
 import { Program } from "@coral-xyz/anchor";
 import { Oraculo } from "../target/types/oraculo";
 import { GovernanceToken } from "../target/types/governance_token";
+import { ExampleIntegrator } from "../target/types/example_integrator";
 import { expect } from "chai";
 import {
   Keypair,
   SystemProgram,
+  Transaction,
   LAMPORTS_PER_SOL,
+  sendAndConfirmTransaction,
 } from "@solana/web3.js";
 import {
   TOKEN_PROGRAM_ID,
+  TOKEN_2022_PROGRAM_ID,
+  ASSOCIATED_TOKEN_PROGRAM_ID,
+  ExtensionType,
+  getMintLen,
+  createInitializeTransferFeeConfigInstruction,
+  createInitializeMintInstruction,
+  getMint,
   createMint,
   createAccount,
   mintTo,
   getAccount,
+  getAssociatedTokenAddressSync,
 } from "@solana/spl-token";
 
+const TOKEN_METADATA_PROGRAM_ID = new anchor.web3.PublicKey(
+  "metaqbxxUerdq28cj1RbAWkYQm3ybzjb6a8bt518x1s"
+);
+
+const metadataPDA = (mint: anchor.web3.PublicKey) =>
+  anchor.web3.PublicKey.findProgramAddressSync(
+    [
+      Buffer.from("metadata"),
+      TOKEN_METADATA_PROGRAM_ID.toBuffer(),
+      mint.toBuffer(),
+    ],
+    TOKEN_METADATA_PROGRAM_ID
+  )[0];
+
 describe("oraculo", () => {
   const provider = anchor.AnchorProvider.env();
   anchor.setProvider(provider);
 
   const program = anchor.workspace.Oraculo as Program<Oraculo>;
   const governanceProgram = anchor.workspace.GovernanceToken as Program<GovernanceToken>;
+  const integratorProgram = anchor.workspace.ExampleIntegrator as Program<ExampleIntegrator>;
+
+  // Markets are addressed by their sequential id from `Config.total_markets`
+  const marketAddress = (marketId: anchor.BN) =>
+    anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("market"), marketId.toArrayLike(Buffer, "le", 8)],
+      program.programId
+    )[0];
+
+  // Categories are registered by governance under their u16 id
+  const categoryAddress = (id: number) =>
+    anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("category"), new anchor.BN(id).toArrayLike(Buffer, "le", 2)],
+      program.programId
+    )[0];
 
   let usdcMint: anchor.web3.PublicKey;
   let governanceMint: anchor.web3.PublicKey;
@@ -31,6 +71,8 @@ describe("oraculo", () => {
   
   let configPDA: anchor.web3.PublicKey;
   let treasuryPDA: anchor.web3.PublicKey;
+  let treasuryVaultPDA: anchor.web3.PublicKey;
+  let collateralPDA: anchor.web3.PublicKey;
 
   before(async () => {
     // Create keypairs
@@ -74,6 +116,16 @@ describe("oraculo", () => {
       [Buffer.from("treasury")],
       program.programId
     );
+
+    [treasuryVaultPDA] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("treasury_vault"), usdcMint.toBuffer()],
+      program.programId
+    );
+
+    [collateralPDA] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("collateral"), usdcMint.toBuffer()],
+      program.programId
+    );
   });
 
   it("Initializes governance token", async () => {
@@ -88,7 +140,7 @@ describe("oraculo", () => {
     );
 
     await governanceProgram.methods
-      .initialize(9)
+      .initialize(9, new anchor.BN("1000000000000000000")) // 1B tokens max supply
       .accounts({
         config: govConfigPDA,
         mint: govMintPDA,
@@ -102,6 +154,61 @@ describe("oraculo", () => {
     const config = await governanceProgram.account.tokenConfig.fetch(govConfigPDA);
     expect(config.authority.toString()).to.equal(creator.publicKey.toString());
   });
+  it("Names the governance mint via Token Metadata", async () => {
+    const [govConfigPDA] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("config")],
+      governanceProgram.programId
+    );
+
+    await governanceProgram.methods
+      .createTokenMetadata("Oraculo", "ORCL", "https://oraculo.app/token.json")
+      .accounts({
+        config: govConfigPDA,
+        mint: governanceMint,
+        metadata: metadataPDA(governanceMint),
+        authority: creator.publicKey,
+        tokenMetadataProgram: TOKEN_METADATA_PROGRAM_ID,
+      })
+      .signers([creator])
+      .rpc();
+
+    const info = await provider.connection.getAccountInfo(metadataPDA(governanceMint));
+    expect(info.owner.toString()).to.equal(TOKEN_METADATA_PROGRAM_ID.toString());
+  });
+
+  it("Creates the governance emission schedule", async () => {
+    const [govConfigPDA] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("config")],
+      governanceProgram.programId
+    );
+    const [schedulePDA] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("emission_schedule")],
+      governanceProgram.programId
+    );
+
+    await governanceProgram.methods
+      .createEmissionSchedule(
+        new anchor.BN(Math.floor(Date.now() / 1000) - 60),
+        new anchor.BN(86400 * 30), // 30 day epochs
+        [
+          {
+            name: "treasury",
+            minter: creator.publicKey,
+            epochAllowances: [new anchor.BN("100000000000000000")], // 100M
+          },
+        ]
+      )
+      .accounts({
+        config: govConfigPDA,
+        emissionSchedule: schedulePDA,
+        authority: creator.publicKey,
+      })
+      .signers([creator])
+      .rpc();
+
+    const schedule = await governanceProgram.account.emissionSchedule.fetch(schedulePDA);
+    expect(schedule.buckets.length).to.equal(1);
+  });
 
   it("Initializes protocol config", async () => {
     const minLiquidity = new anchor.BN(100_000_000); // 100 USDC
@@ -114,7 +221,9 @@ describe("oraculo", () => {
         minLiquidity,
         proposalStake,
         quorum,
-        supermajority
+        supermajority,
+        creator.publicKey, // guardian
+        100 // 1% protocol fee
       )
       .accounts({
         config: configPDA,
@@ -127,6 +236,78 @@ describe("oraculo", () => {
 
     const config = await program.account.config.fetch(configPDA);
     expect(config.minLiquidity.toString()).to.equal(minLiquidity.toString());
+    expect(config.treasury.toString()).to.equal(treasuryPDA.toString());
+  });
+
+  it("Registers USDC as collateral", async () => {
+    await program.methods
+      .registerCollateral(
+        new anchor.BN(1_000_000), // 1 USDC min bet
+        new anchor.BN(100_000_000) // 100 USDC min liquidity
+      )
+      .accounts({
+        config: configPDA,
+        collateral: collateralPDA,
+        mint: usdcMint,
+        authority: creator.publicKey,
+      })
+      .signers([creator])
+      .rpc();
+
+    const collateral = await program.account.collateralConfig.fetch(collateralPDA);
+    expect(collateral.decimals).to.equal(6);
+    expect(collateral.enabled).to.equal(true);
+  });
+
+  it("Registers the Crypto category", async () => {
+    await program.methods
+      .createCategory(0, "Crypto", null, new anchor.BN(0), null)
+      .accounts({
+        config: configPDA,
+        category: categoryAddress(0),
+        parentCategory: null,
+        authority: creator.publicKey,
+      })
+      .signers([creator])
+      .rpc();
+
+    const category = await program.account.category.fetch(categoryAddress(0));
+    expect(category.name).to.equal("Crypto");
+    expect(category.enabled).to.equal(true);
+  });
+
+  it("Reserves ids 0-5 for the original categories", async () => {
+    try {
+      await program.methods
+        .createCategory(1, "Esports", null, new anchor.BN(0), null)
+        .accounts({
+          config: configPDA,
+          category: categoryAddress(1),
+          parentCategory: null,
+          authority: creator.publicKey,
+        })
+        .signers([creator])
+        .rpc();
+      expect.fail("registered a reserved id under another name");
+    } catch (err) {
+      expect(err.error.errorCode.code).to.equal("InvalidCategory");
+    }
+  });
+
+  it("Opens the treasury vault for USDC", async () => {
+    await program.methods
+      .openTreasuryVault()
+      .accounts({
+        treasury: treasuryPDA,
+        treasuryVault: treasuryVaultPDA,
+        mint: usdcMint,
+        payer: creator.publicKey,
+      })
+      .signers([creator])
+      .rpc();
+
+    const vault = await getAccount(provider.connection, treasuryVaultPDA);
+    expect(vault.owner.toString()).to.equal(treasuryPDA.toString());
   });
 
   it("Creates a prediction market", async () => {
@@ -149,14 +330,8 @@ describe("oraculo", () => {
     );
 
     const timestamp = Math.floor(Date.now() / 1000);
-    const [marketPDA] = anchor.web3.PublicKey.findProgramAddressSync(
-      [
-        Buffer.from("market"),
-        creator.publicKey.toBuffer(),
-        new anchor.BN(timestamp).toArrayLike(Buffer, "le", 8),
-      ],
-      program.programId
-    );
+    const { totalMarkets } = await program.account.config.fetch(configPDA);
+    const marketPDA = marketAddress(totalMarkets);
 
     const [vaultPDA] = anchor.web3.PublicKey.findProgramAddressSync(
       [Buffer.from("vault"), marketPDA.toBuffer()],
@@ -176,23 +351,36 @@ describe("oraculo", () => {
     const endTime = new anchor.BN(timestamp + 86400 * 30); // 30 days
 
     await program.methods
-      .createMarket(
-        "Will BTC hit $100K by end of 2025?",
-        "Bitcoin price prediction market",
-        0, // Crypto category
+      .createMarket({
+        question: "Will BTC hit $100K by end of 2025?",
+        description: "Bitcoin price prediction market",
+        category: 0, // Crypto
         endTime,
-        "https://api.coingecko.com/btc",
-        new anchor.BN(200_000_000) // 200 USDC initial liquidity
-      )
+        resolutionSource: "https://api.coingecko.com/btc",
+        initialLiquidity: new anchor.BN(200_000_000), // 200 USDC
+        priceCondition: null,
+        resolutionMode: { daoVote: {} },
+        resolver: null,
+        votingPeriod: null, // governance default
+        resolutionGrace: null, // governance default
+        metadata: null,
+        tags: ["bitcoin"],
+      })
       .accounts({
         config: configPDA,
         market: marketPDA,
         creator: creator.publicKey,
-        creatorUsdc: creatorUSDC,
+        creatorCollateral: creatorUSDC,
         liquidityVault: vaultPDA,
         yesTokenMint: yesMintPDA,
         noTokenMint: noMintPDA,
-        usdcMint: usdcMint,
+        yesMetadata: metadataPDA(yesMintPDA),
+        noMetadata: metadataPDA(noMintPDA),
+        collateral: collateralPDA,
+        categoryAccount: categoryAddress(0),
+        parentCategoryAccount: null,
+        collateralMint: usdcMint,
+        tokenMetadataProgram: TOKEN_METADATA_PROGRAM_ID,
       })
       .signers([creator])
       .rpc();
@@ -200,18 +388,80 @@ describe("oraculo", () => {
     const market = await program.account.market.fetch(marketPDA);
     expect(market.question).to.equal("Will BTC hit $100K by end of 2025?");
     expect(market.totalLiquidity.toString()).to.equal("200000000");
-  });
+    expect(market.collateralMint.toString()).to.equal(usdcMint.toString());
+    expect(market.marketId.toString()).to.equal(totalMarkets.toString());
+    expect(market.category).to.equal(0);
+    expect(market.tags).to.deep.equal(["bitcoin"]);
 
-  it("Places a bet on the market", async () => {
-    const timestamp = Math.floor(Date.now() / 1000);
-    const [marketPDA] = anchor.web3.PublicKey.findProgramAddressSync(
+    const config = await program.account.config.fetch(configPDA);
+    expect(config.totalMarkets.toString()).to.equal(totalMarkets.addn(1).toString());
+
+    // This is the creator's first market, so it is entry 0 of their index
+    const [creatorIndexPDA] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("creator_index"), creator.publicKey.toBuffer()],
+      program.programId
+    );
+    const [creatorMarketPDA] = anchor.web3.PublicKey.findProgramAddressSync(
       [
-        Buffer.from("market"),
+        Buffer.from("creator_market"),
         creator.publicKey.toBuffer(),
-        new anchor.BN(timestamp).toArrayLike(Buffer, "le", 8),
+        new anchor.BN(0).toArrayLike(Buffer, "le", 8),
       ],
       program.programId
     );
+    const creatorIndex = await program.account.creatorIndex.fetch(creatorIndexPDA);
+    expect(creatorIndex.marketCount.toNumber()).to.equal(1);
+    const creatorMarket = await program.account.creatorMarket.fetch(creatorMarketPDA);
+    expect(creatorMarket.market.toString()).to.equal(marketPDA.toString());
+    expect(creatorMarket.marketId.toString()).to.equal(market.marketId.toString());
+  });
+
+  // Edits the market created above; text can only change before any trade
+  const editMarket = async (description: string) => {
+    const { totalMarkets } = await program.account.config.fetch(configPDA);
+    const marketPDA = marketAddress(totalMarkets.subn(1));
+    const [yesMintPDA] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("yes_mint"), marketPDA.toBuffer()],
+      program.programId
+    );
+    const [noMintPDA] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("no_mint"), marketPDA.toBuffer()],
+      program.programId
+    );
+    const [orderBookPDA] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("order_book"), marketPDA.toBuffer()],
+      program.programId
+    );
+
+    await program.methods
+      .editMarket(null, description, null, null)
+      .accounts({
+        config: configPDA,
+        market: marketPDA,
+        creator: creator.publicKey,
+        yesTokenMint: yesMintPDA,
+        noTokenMint: noMintPDA,
+        orderBook: orderBookPDA,
+        yesMetadata: metadataPDA(yesMintPDA),
+        noMetadata: metadataPDA(noMintPDA),
+        tokenMetadataProgram: TOKEN_METADATA_PROGRAM_ID,
+      })
+      .signers([creator])
+      .rpc();
+    return program.account.market.fetch(marketPDA);
+  };
+
+  it("Creator edits the market before the first bet", async () => {
+    const market = await editMarket("Bitcoin price prediction market, per CoinGecko");
+    expect(market.description).to.equal("Bitcoin price prediction market, per CoinGecko");
+    expect(market.edits).to.equal(1);
+    expect(market.hasTrades).to.equal(false);
+  });
+
+  it("Places a bet on the market", async () => {
+    // The market created by the previous test
+    const { totalMarkets } = await program.account.config.fetch(configPDA);
+    const marketPDA = marketAddress(totalMarkets.subn(1));
 
     // Create USDC account for user1
     const user1USDC = await createAccount(
@@ -244,20 +494,1318 @@ describe("oraculo", () => {
     await program.methods
       .placeBet(
         new anchor.BN(10_000_000), // 10 tokens
-        true // bet on YES
+        true, // bet on YES
+        new anchor.BN(20_000_000) // max cost
       )
       .accounts({
+        config: configPDA,
         market: marketPDA,
         user: user1.publicKey,
-        userUsdc: user1USDC,
+        userCollateral: user1USDC,
         liquidityVault: vaultPDA,
-        yesTokenMint: yesMintPDA,
+        treasuryVault: treasuryVaultPDA,
+        collateral: collateralPDA,
+        collateralMint: usdcMint,
+        outcomeTokenMint: yesMintPDA,
       })
       .signers([user1])
       .rpc();
 
     const market = await program.account.market.fetch(marketPDA);
     expect(market.volume.toNumber()).to.be.greaterThan(0);
+    const config = await program.account.config.fetch(configPDA);
+    expect(config.totalVolume.toNumber()).to.be.greaterThan(0);
+
+    // 10 USDC of shares mints 10 whole YES tokens (9 decimals)
+    const yesAccount = await getAccount(
+      provider.connection,
+      getAssociatedTokenAddressSync(yesMintPDA, user1.publicKey)
+    );
+    expect(yesAccount.amount.toString()).to.equal("10000000000");
+  });
+
+  it("Rejects edits once the market has traded", async () => {
+    try {
+      await editMarket("Changed after the first bet");
+      expect.fail("edited a market with bets");
+    } catch (err) {
+      expect(err.error.errorCode.code).to.equal("MarketHasBets");
+    }
+  });
+
+  it("Returns the market price through return data", async () => {
+    const { totalMarkets } = await program.account.config.fetch(configPDA);
+    const marketPDA = marketAddress(totalMarkets.subn(1));
+    const [priceHistoryPDA] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("price_history"), marketPDA.toBuffer()],
+      program.programId
+    );
+
+    const price = await program.methods
+      .getMarketPrice(new anchor.BN(1))
+      .accounts({
+        market: marketPDA,
+        priceHistory: priceHistoryPDA,
+      })
+      .view();
+
+    const market = await program.account.market.fetch(marketPDA);
+    expect(price.market.toString()).to.equal(marketPDA.toString());
+    expect(price.status).to.deep.equal({ active: {} });
+    // The YES bet moved the price above even odds
+    expect(price.yesPrice).to.be.greaterThan(5_000);
+    expect(price.yesPrice + price.noPrice).to.equal(10_000);
+    expect(price.twap).to.not.be.null;
+    expect(price.yesPool.toString()).to.equal(market.yesPool.toString());
+    expect(price.volume.toString()).to.equal(market.volume.toString());
+  });
+
+  it("Integrator bets via CPI only below its price limit", async () => {
+    const { totalMarkets } = await program.account.config.fetch(configPDA);
+    const marketPDA = marketAddress(totalMarkets.subn(1));
+    const [priceHistoryPDA] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("price_history"), marketPDA.toBuffer()],
+      program.programId
+    );
+    const [vaultPDA] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("vault"), marketPDA.toBuffer()],
+      program.programId
+    );
+    const [yesMintPDA] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("yes_mint"), marketPDA.toBuffer()],
+      program.programId
+    );
+
+    const user2USDC = await createAccount(
+      provider.connection,
+      user2,
+      usdcMint,
+      user2.publicKey
+    );
+    await mintTo(
+      provider.connection,
+      creator,
+      usdcMint,
+      user2USDC,
+      creator,
+      100_000_000
+    );
+    const userOutcomeToken = getAssociatedTokenAddressSync(yesMintPDA, user2.publicKey);
+
+    const betBelowPrice = (maxPrice: number) =>
+      integratorProgram.methods
+        .betBelowPrice(new anchor.BN(1_000_000), true, maxPrice, new anchor.BN(1))
+        .accounts({
+          config: configPDA,
+          market: marketPDA,
+          priceHistory: priceHistoryPDA,
+          user: user2.publicKey,
+          userCollateral: user2USDC,
+          liquidityVault: vaultPDA,
+          treasuryVault: treasuryVaultPDA,
+          collateral: collateralPDA,
+          collateralMint: usdcMint,
+          outcomeTokenMint: yesMintPDA,
+          userOutcomeToken,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+        })
+        .signers([user2])
+        .rpc();
+
+    // YES trades above 1%, so the TWAP check refuses the bet
+    try {
+      await betBelowPrice(100);
+      expect.fail("bet above the price limit");
+    } catch (err) {
+      expect(err.error.errorCode.code).to.equal("PriceAboveLimit");
+    }
+
+    await betBelowPrice(9_900);
+    const yesAccount = await getAccount(provider.connection, userOutcomeToken);
+    // 1 USDC of shares mints 1 whole YES token (9 decimals)
+    expect(yesAccount.amount.toString()).to.equal("1000000000");
+  });
+
+  it("Keeps a transfer-fee collateral vault solvent for every claim", async () => {
+    // Token-2022 collateral that withholds 1% of every transfer
+    const feeMint = Keypair.generate();
+    const mintLen = getMintLen([ExtensionType.TransferFeeConfig]);
+    const lamports = await provider.connection.getMinimumBalanceForRentExemption(mintLen);
+    await sendAndConfirmTransaction(
+      provider.connection,
+      new Transaction().add(
+        SystemProgram.createAccount({
+          fromPubkey: creator.publicKey,
+          newAccountPubkey: feeMint.publicKey,
+          space: mintLen,
+          lamports,
+          programId: TOKEN_2022_PROGRAM_ID,
+        }),
+        createInitializeTransferFeeConfigInstruction(
+          feeMint.publicKey,
+          creator.publicKey,
+          creator.publicKey,
+          100, // 1%
+          BigInt(1_000_000_000),
+          TOKEN_2022_PROGRAM_ID
+        ),
+        createInitializeMintInstruction(
+          feeMint.publicKey,
+          6,
+          creator.publicKey,
+          null,
+          TOKEN_2022_PROGRAM_ID
+        )
+      ),
+      [creator, feeMint]
+    );
+
+    const [feeCollateralPDA] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("collateral"), feeMint.publicKey.toBuffer()],
+      program.programId
+    );
+    const [feeTreasuryVaultPDA] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("treasury_vault"), feeMint.publicKey.toBuffer()],
+      program.programId
+    );
+    await program.methods
+      .registerCollateral(new anchor.BN(1_000_000), new anchor.BN(100_000_000))
+      .accounts({
+        config: configPDA,
+        collateral: feeCollateralPDA,
+        mint: feeMint.publicKey,
+        authority: creator.publicKey,
+      })
+      .signers([creator])
+      .rpc();
+    await program.methods
+      .openTreasuryVault()
+      .accounts({
+        treasury: treasuryPDA,
+        treasuryVault: feeTreasuryVaultPDA,
+        mint: feeMint.publicKey,
+        payer: creator.publicKey,
+        tokenProgram: TOKEN_2022_PROGRAM_ID,
+      })
+      .signers([creator])
+      .rpc();
+
+    const fundAccount = async (owner: Keypair, amount: number) => {
+      const account = await createAccount(
+        provider.connection,
+        owner,
+        feeMint.publicKey,
+        owner.publicKey,
+        Keypair.generate(),
+        undefined,
+        TOKEN_2022_PROGRAM_ID
+      );
+      await mintTo(
+        provider.connection,
+        creator,
+        feeMint.publicKey,
+        account,
+        creator,
+        amount,
+        [],
+        undefined,
+        TOKEN_2022_PROGRAM_ID
+      );
+      return account;
+    };
+    const creatorCollateral = await fundAccount(creator, 200_000_000);
+    const bettorCollateral = await fundAccount(user2, 100_000_000);
+
+    const timestamp = Math.floor(Date.now() / 1000);
+    const { totalMarkets } = await program.account.config.fetch(configPDA);
+    const marketPDA = marketAddress(totalMarkets);
+    const [vaultPDA] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("vault"), marketPDA.toBuffer()],
+      program.programId
+    );
+    const [yesMintPDA] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("yes_mint"), marketPDA.toBuffer()],
+      program.programId
+    );
+    const [noMintPDA] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("no_mint"), marketPDA.toBuffer()],
+      program.programId
+    );
+
+    await program.methods
+      .createMarket({
+        question: "Will SOL close above $500?",
+        description: "Market collateralised by a transfer-fee token",
+        category: 0,
+        endTime: new anchor.BN(timestamp + 86400),
+        resolutionSource: "https://api.coingecko.com/sol",
+        initialLiquidity: new anchor.BN(110_000_000),
+        priceCondition: null,
+        resolutionMode: { daoVote: {} },
+        resolver: null,
+        votingPeriod: null,
+        resolutionGrace: null,
+        metadata: null,
+        tags: [],
+      })
+      .accounts({
+        config: configPDA,
+        market: marketPDA,
+        creator: creator.publicKey,
+        creatorCollateral,
+        liquidityVault: vaultPDA,
+        yesTokenMint: yesMintPDA,
+        noTokenMint: noMintPDA,
+        yesMetadata: metadataPDA(yesMintPDA),
+        noMetadata: metadataPDA(noMintPDA),
+        collateral: feeCollateralPDA,
+        categoryAccount: categoryAddress(0),
+        parentCategoryAccount: null,
+        collateralMint: feeMint.publicKey,
+        tokenProgram: TOKEN_2022_PROGRAM_ID,
+        tokenMetadataProgram: TOKEN_METADATA_PROGRAM_ID,
+      })
+      .signers([creator])
+      .rpc();
+
+    // Bet on both outcomes; NO bets mint into the bettor's NO token account
+    const bet = (betOnYes: boolean) =>
+      program.methods
+        .placeBet(new anchor.BN(10_000_000), betOnYes, new anchor.BN(20_000_000))
+        .accounts({
+          config: configPDA,
+          market: marketPDA,
+          user: user2.publicKey,
+          userCollateral: bettorCollateral,
+          liquidityVault: vaultPDA,
+          treasuryVault: feeTreasuryVaultPDA,
+          collateral: feeCollateralPDA,
+          collateralMint: feeMint.publicKey,
+          outcomeTokenMint: betOnYes ? yesMintPDA : noMintPDA,
+          tokenProgram: TOKEN_2022_PROGRAM_ID,
+        })
+        .signers([user2])
+        .rpc();
+    await bet(true);
+    await bet(false);
+
+    const noAccount = await getAccount(
+      provider.connection,
+      getAssociatedTokenAddressSync(noMintPDA, user2.publicKey, false, TOKEN_2022_PROGRAM_ID),
+      undefined,
+      TOKEN_2022_PROGRAM_ID
+    );
+    expect(noAccount.amount.toString()).to.equal("10000000000");
+
+    // The vault holds every unit the market accounts for, and enough to pay
+    // out either side in full (one whole collateral unit per whole token)
+    const market = await program.account.market.fetch(marketPDA);
+    const vault = await getAccount(provider.connection, vaultPDA, undefined, TOKEN_2022_PROGRAM_ID);
+    expect(vault.amount.toString()).to.equal(market.totalLiquidity.toString());
+    for (const mint of [yesMintPDA, noMintPDA]) {
+      const { supply } = await getMint(provider.connection, mint, undefined, TOKEN_2022_PROGRAM_ID);
+      expect(vault.amount >= supply / BigInt(1000)).to.equal(true);
+    }
+  });
+
+  it("Guardian pause blocks trading until lifted", async () => {
+    const PAUSE_TRADING = 1;
+
+    await program.methods
+      .setPauseFlags(PAUSE_TRADING)
+      .accounts({
+        config: configPDA,
+        guardian: creator.publicKey,
+      })
+      .signers([creator])
+      .rpc();
+
+    let config = await program.account.config.fetch(configPDA);
+    expect(config.paused).to.equal(PAUSE_TRADING);
+
+    await program.methods
+      .setPauseFlags(0)
+      .accounts({
+        config: configPDA,
+        guardian: creator.publicKey,
+      })
+      .signers([creator])
+      .rpc();
+
+    config = await program.account.config.fetch(configPDA);
+    expect(config.paused).to.equal(0);
+  });
+
+  it("Rejects markets in unknown or disabled categories", async () => {
+    const creatorUSDC = await createAccount(
+      provider.connection,
+      creator,
+      usdcMint,
+      creator.publicKey,
+      Keypair.generate()
+    );
+    await mintTo(
+      provider.connection,
+      creator,
+      usdcMint,
+      creatorUSDC,
+      creator,
+      200_000_000
+    );
+
+    // A top-level category with a subcategory, then disabled
+    await program.methods
+      .createCategory(6, "Weather", null, new anchor.BN(0), null)
+      .accounts({
+        config: configPDA,
+        category: categoryAddress(6),
+        parentCategory: null,
+        authority: creator.publicKey,
+      })
+      .signers([creator])
+      .rpc();
+    await program.methods
+      .createCategory(7, "Hurricanes", 6, new anchor.BN(0), null)
+      .accounts({
+        config: configPDA,
+        category: categoryAddress(7),
+        parentCategory: categoryAddress(6),
+        authority: creator.publicKey,
+      })
+      .signers([creator])
+      .rpc();
+    await program.methods
+      .updateCategory("Weather", new anchor.BN(0), null, false)
+      .accounts({
+        config: configPDA,
+        category: categoryAddress(6),
+        authority: creator.publicKey,
+      })
+      .signers([creator])
+      .rpc();
+
+    const createMarketIn = async (
+      category: number,
+      parentCategoryAccount: anchor.web3.PublicKey | null
+    ) => {
+      const timestamp = Math.floor(Date.now() / 1000);
+      const { totalMarkets } = await program.account.config.fetch(configPDA);
+      const marketPDA = marketAddress(totalMarkets);
+      const [vaultPDA] = anchor.web3.PublicKey.findProgramAddressSync(
+        [Buffer.from("vault"), marketPDA.toBuffer()],
+        program.programId
+      );
+      const [yesMintPDA] = anchor.web3.PublicKey.findProgramAddressSync(
+        [Buffer.from("yes_mint"), marketPDA.toBuffer()],
+        program.programId
+      );
+      const [noMintPDA] = anchor.web3.PublicKey.findProgramAddressSync(
+        [Buffer.from("no_mint"), marketPDA.toBuffer()],
+        program.programId
+      );
+
+      try {
+        await program.methods
+          .createMarket({
+            question: "Will a hurricane make landfall in Florida this year?",
+            description: "Weather market",
+            category,
+            endTime: new anchor.BN(timestamp + 86400),
+            resolutionSource: "https://www.nhc.noaa.gov",
+            initialLiquidity: new anchor.BN(100_000_000),
+            priceCondition: null,
+            resolutionMode: { daoVote: {} },
+            resolver: null,
+            votingPeriod: null,
+            resolutionGrace: null,
+            metadata: null,
+            tags: [],
+          })
+          .accounts({
+            config: configPDA,
+            market: marketPDA,
+            creator: creator.publicKey,
+            creatorCollateral: creatorUSDC,
+            liquidityVault: vaultPDA,
+            yesTokenMint: yesMintPDA,
+            noTokenMint: noMintPDA,
+            yesMetadata: metadataPDA(yesMintPDA),
+            noMetadata: metadataPDA(noMintPDA),
+            collateral: collateralPDA,
+            categoryAccount: categoryAddress(category),
+            parentCategoryAccount,
+            collateralMint: usdcMint,
+            tokenMetadataProgram: TOKEN_METADATA_PROGRAM_ID,
+          })
+          .signers([creator])
+          .rpc();
+        expect.fail(`created a market in category ${category}`);
+      } catch (err) {
+        return err.error.errorCode.code;
+      }
+    };
+
+    expect(await createMarketIn(99, null)).to.equal("UnknownCategory");
+    expect(await createMarketIn(6, null)).to.equal("CategoryDisabled");
+    // The subcategory is still enabled itself, but follows its parent
+    expect(await createMarketIn(7, categoryAddress(6))).to.equal("CategoryDisabled");
+  });
+
+  it("Resolves a price threshold market from its oracle feed", async () => {
+    // Fixture price: BTC/USD $101,234.56, expo -8 (tests/fixtures/btc-usd-price.json)
+    const priceFeed = new anchor.web3.PublicKey(
+      "2AWKJYorB95TZzHRzFAz4U5MVtQGs5JSmxnsBQDUAgj6"
+    );
+
+    const creatorUSDC = await createAccount(
+      provider.connection,
+      creator,
+      usdcMint,
+      creator.publicKey,
+      Keypair.generate()
+    );
+    await mintTo(
+      provider.connection,
+      creator,
+      usdcMint,
+      creatorUSDC,
+      creator,
+      200_000_000
+    );
+
+    const timestamp = Math.floor(Date.now() / 1000);
+    const { totalMarkets } = await program.account.config.fetch(configPDA);
+    const marketPDA = marketAddress(totalMarkets);
+    const [vaultPDA] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("vault"), marketPDA.toBuffer()],
+      program.programId
+    );
+    const [yesMintPDA] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("yes_mint"), marketPDA.toBuffer()],
+      program.programId
+    );
+    const [noMintPDA] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("no_mint"), marketPDA.toBuffer()],
+      program.programId
+    );
+
+    await program.methods
+      .createMarket({
+        question: "Will BTC close above $100K?",
+        description: "Resolved from the BTC/USD price feed",
+        category: 0,
+        endTime: new anchor.BN(timestamp + 2),
+        resolutionSource: "pyth:BTC/USD",
+        initialLiquidity: new anchor.BN(100_000_000),
+        priceCondition: {
+          feed: priceFeed,
+          threshold: new anchor.BN(100_000),
+          expo: 0,
+          comparison: { greaterThan: {} },
+          maxStaleness: 60,
+          maxConfBps: 100,
+        },
+        resolutionMode: { priceOracle: {} },
+        resolver: null,
+        votingPeriod: null,
+        resolutionGrace: null,
+        metadata: null,
+        tags: [],
+      })
+      .accounts({
+        config: configPDA,
+        market: marketPDA,
+        creator: creator.publicKey,
+        creatorCollateral: creatorUSDC,
+        liquidityVault: vaultPDA,
+        yesTokenMint: yesMintPDA,
+        noTokenMint: noMintPDA,
+        yesMetadata: metadataPDA(yesMintPDA),
+        noMetadata: metadataPDA(noMintPDA),
+        collateral: collateralPDA,
+        categoryAccount: categoryAddress(0),
+        parentCategoryAccount: null,
+        collateralMint: usdcMint,
+        tokenMetadataProgram: TOKEN_METADATA_PROGRAM_ID,
+      })
+      .signers([creator])
+      .rpc();
+
+    // Wait for the market to close
+    await new Promise(resolve => setTimeout(resolve, 4000));
+
+    await program.methods
+      .resolveFromOracle()
+      .accounts({
+        config: configPDA,
+        market: marketPDA,
+        priceFeed,
+      })
+      .rpc();
+
+    const market = await program.account.market.fetch(marketPDA);
+    expect(market.status).to.deep.equal({ resolved: {} });
+    expect(market.outcome).to.equal(true);
+  });
+
+  it("Spawns a market from a template and closes the paused template", async () => {
+    const creatorUSDC = await createAccount(
+      provider.connection,
+      creator,
+      usdcMint,
+      creator.publicKey,
+      Keypair.generate()
+    );
+
+    const templateId = new anchor.BN(1);
+    const [templatePDA] = anchor.web3.PublicKey.findProgramAddressSync(
+      [
+        Buffer.from("template"),
+        creator.publicKey.toBuffer(),
+        templateId.toArrayLike(Buffer, "le", 8),
+      ],
+      program.programId
+    );
+    const [templateVaultPDA] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("template_vault"), templatePDA.toBuffer()],
+      program.programId
+    );
+
+    const timestamp = Math.floor(Date.now() / 1000);
+    await program.methods
+      .createMarketTemplate({
+        templateId,
+        questionPattern: "Will BTC close above $100K on {date}? (#{index})",
+        description: "Daily BTC series",
+        category: 0,
+        resolutionSource: "https://api.coingecko.com/btc",
+        priceCondition: null,
+        resolutionMode: { daoVote: {} },
+        resolver: null,
+        votingPeriod: null,
+        resolutionGrace: null,
+        duration: new anchor.BN(86400),
+        interval: new anchor.BN(86400),
+        liquidity: new anchor.BN(100_000_000),
+        firstSpawnAt: new anchor.BN(timestamp + 2),
+      })
+      .accounts({
+        config: configPDA,
+        template: templatePDA,
+        templateVault: templateVaultPDA,
+        creator: creator.publicKey,
+        collateral: collateralPDA,
+        categoryAccount: categoryAddress(0),
+        parentCategoryAccount: null,
+        collateralMint: usdcMint,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([creator])
+      .rpc();
+
+    // Fund one market and a remainder to withdraw later
+    await mintTo(
+      provider.connection,
+      creator,
+      usdcMint,
+      templateVaultPDA,
+      creator,
+      150_000_000
+    );
+
+    // Wait for the first market to come due
+    await new Promise(resolve => setTimeout(resolve, 4000));
+
+    const { totalMarkets } = await program.account.config.fetch(configPDA);
+    const marketPDA = marketAddress(totalMarkets);
+    const [vaultPDA] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("vault"), marketPDA.toBuffer()],
+      program.programId
+    );
+    const [yesMintPDA] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("yes_mint"), marketPDA.toBuffer()],
+      program.programId
+    );
+    const [noMintPDA] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("no_mint"), marketPDA.toBuffer()],
+      program.programId
+    );
+
+    await program.methods
+      .spawnFromTemplate()
+      .accounts({
+        config: configPDA,
+        template: templatePDA,
+        templateVault: templateVaultPDA,
+        market: marketPDA,
+        payer: user1.publicKey,
+        liquidityVault: vaultPDA,
+        yesTokenMint: yesMintPDA,
+        noTokenMint: noMintPDA,
+        yesMetadata: metadataPDA(yesMintPDA),
+        noMetadata: metadataPDA(noMintPDA),
+        collateral: collateralPDA,
+        categoryAccount: categoryAddress(0),
+        parentCategoryAccount: null,
+        collateralMint: usdcMint,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        tokenMetadataProgram: TOKEN_METADATA_PROGRAM_ID,
+      })
+      .signers([user1])
+      .rpc();
+
+    const market = await program.account.market.fetch(marketPDA);
+    expect(market.creator.toString()).to.equal(creator.publicKey.toString());
+    expect(market.question).to.match(/^Will BTC close above \$100K on \d{4}-\d{2}-\d{2}\? \(#0\)$/);
+    expect(market.totalLiquidity.toString()).to.equal("100000000");
+
+    const template = await program.account.marketTemplate.fetch(templatePDA);
+    expect(template.seriesIndex.toNumber()).to.equal(1);
+    expect(template.lastMarket.toString()).to.equal(marketPDA.toString());
+
+    // The series must be paused before its vault can be emptied
+    const closeTemplate = () =>
+      program.methods
+        .closeTemplate()
+        .accounts({
+          config: configPDA,
+          template: templatePDA,
+          templateVault: templateVaultPDA,
+          creator: creator.publicKey,
+          creatorCollateral: creatorUSDC,
+          collateralMint: usdcMint,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([creator])
+        .rpc();
+    try {
+      await closeTemplate();
+      expect.fail("closed an active template");
+    } catch (err) {
+      expect(err.error.errorCode.code).to.equal("TemplateActive");
+    }
+
+    await program.methods
+      .setTemplateActive(false)
+      .accounts({
+        template: templatePDA,
+        creator: creator.publicKey,
+      })
+      .signers([creator])
+      .rpc();
+    await closeTemplate();
+
+    const refunded = await getAccount(provider.connection, creatorUSDC);
+    expect(refunded.amount.toString()).to.equal("50000000");
+    expect(await program.account.marketTemplate.fetchNullable(templatePDA)).to.be.null;
+  });
+
+  it("Returns open proposal stakes when a resolution round expires", async () => {
+    // Short periods so the round can lapse within the test
+    await program.methods
+      .setResolutionPeriods({
+        minVotingPeriod: new anchor.BN(1),
+        maxVotingPeriod: new anchor.BN(86400 * 30),
+        defaultVotingPeriod: new anchor.BN(86400 * 2),
+        minResolutionGrace: new anchor.BN(1),
+        maxResolutionGrace: new anchor.BN(86400 * 90),
+        defaultResolutionGrace: new anchor.BN(86400 * 7),
+        unresolvedFallback: { extendRound: {} },
+      })
+      .accounts({
+        config: configPDA,
+        authority: creator.publicKey,
+      })
+      .signers([creator])
+      .rpc();
+
+    // The proposer holds exactly one proposal stake of governance tokens
+    const [govConfigPDA] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("config")],
+      governanceProgram.programId
+    );
+    const [schedulePDA] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("emission_schedule")],
+      governanceProgram.programId
+    );
+    const proposerGov = await createAccount(
+      provider.connection,
+      user2,
+      governanceMint,
+      user2.publicKey
+    );
+    const { proposalStake } = await program.account.config.fetch(configPDA);
+    await governanceProgram.methods
+      .mintTokens(0, proposalStake)
+      .accounts({
+        config: govConfigPDA,
+        emissionSchedule: schedulePDA,
+        minter: creator.publicKey,
+        mint: governanceMint,
+        destination: proposerGov,
+      })
+      .signers([creator])
+      .rpc();
+
+    const creatorUSDC = await createAccount(
+      provider.connection,
+      creator,
+      usdcMint,
+      creator.publicKey,
+      Keypair.generate()
+    );
+    await mintTo(
+      provider.connection,
+      creator,
+      usdcMint,
+      creatorUSDC,
+      creator,
+      100_000_000
+    );
+
+    const timestamp = Math.floor(Date.now() / 1000);
+    const { totalMarkets } = await program.account.config.fetch(configPDA);
+    const marketPDA = marketAddress(totalMarkets);
+    const [vaultPDA] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("vault"), marketPDA.toBuffer()],
+      program.programId
+    );
+    const [yesMintPDA] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("yes_mint"), marketPDA.toBuffer()],
+      program.programId
+    );
+    const [noMintPDA] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("no_mint"), marketPDA.toBuffer()],
+      program.programId
+    );
+    const [priceHistoryPDA] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("price_history"), marketPDA.toBuffer()],
+      program.programId
+    );
+
+    await program.methods
+      .createMarket({
+        question: "Will ETH close above $5K?",
+        description: "Short-lived market for the expiry test",
+        category: 0,
+        endTime: new anchor.BN(timestamp + 2),
+        resolutionSource: "https://api.coingecko.com/eth",
+        initialLiquidity: new anchor.BN(100_000_000),
+        priceCondition: null,
+        resolutionMode: { daoVote: {} },
+        resolver: null,
+        votingPeriod: new anchor.BN(2),
+        resolutionGrace: new anchor.BN(6),
+        metadata: null,
+        tags: [],
+      })
+      .accounts({
+        config: configPDA,
+        market: marketPDA,
+        creator: creator.publicKey,
+        creatorCollateral: creatorUSDC,
+        liquidityVault: vaultPDA,
+        yesTokenMint: yesMintPDA,
+        noTokenMint: noMintPDA,
+        yesMetadata: metadataPDA(yesMintPDA),
+        noMetadata: metadataPDA(noMintPDA),
+        collateral: collateralPDA,
+        categoryAccount: categoryAddress(0),
+        parentCategoryAccount: null,
+        collateralMint: usdcMint,
+        tokenMetadataProgram: TOKEN_METADATA_PROGRAM_ID,
+      })
+      .signers([creator])
+      .rpc();
+
+    // Propose once the market has ended; nobody votes
+    await new Promise(resolve => setTimeout(resolve, 4000));
+    const [proposalPDA] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("proposal"), marketPDA.toBuffer(), user2.publicKey.toBuffer()],
+      program.programId
+    );
+    const [stakeVaultPDA] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("proposal_stake"), proposalPDA.toBuffer()],
+      program.programId
+    );
+    await program.methods
+      .proposeResolution(false, "ETH stayed below $5K")
+      .accounts({
+        config: configPDA,
+        market: marketPDA,
+        proposal: proposalPDA,
+        proposer: user2.publicKey,
+        proposerGovToken: proposerGov,
+        proposalStakeVault: stakeVaultPDA,
+        governanceTokenMint: governanceMint,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([user2])
+      .rpc();
+    expect((await getAccount(provider.connection, proposerGov)).amount.toString()).to.equal("0");
+
+    // Wait out the resolution time
+    await new Promise(resolve => setTimeout(resolve, 7000));
+
+    await program.methods
+      .expireResolution()
+      .accounts({
+        config: configPDA,
+        market: marketPDA,
+        priceHistory: priceHistoryPDA,
+        governanceTokenMint: governanceMint,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .remainingAccounts([
+        { pubkey: proposalPDA, isWritable: true, isSigner: false },
+        { pubkey: stakeVaultPDA, isWritable: true, isSigner: false },
+        { pubkey: proposerGov, isWritable: true, isSigner: false },
+      ])
+      .rpc();
+
+    const proposerBalance = await getAccount(provider.connection, proposerGov);
+    expect(proposerBalance.amount.toString()).to.equal(proposalStake.toString());
+    const proposal = await program.account.proposal.fetch(proposalPDA);
+    expect(proposal.status).to.deep.equal({ rejected: {} });
+    const market = await program.account.market.fetch(marketPDA);
+    expect(market.status).to.deep.equal({ active: {} });
+    expect(market.openProposals).to.equal(0);
+  });
+
+  it("Creator withdrawal leaves every refund claimable", async () => {
+    // Cancel markets whose resolution round lapses
+    await program.methods
+      .setResolutionPeriods({
+        minVotingPeriod: new anchor.BN(1),
+        maxVotingPeriod: new anchor.BN(86400 * 30),
+        defaultVotingPeriod: new anchor.BN(86400 * 2),
+        minResolutionGrace: new anchor.BN(1),
+        maxResolutionGrace: new anchor.BN(86400 * 90),
+        defaultResolutionGrace: new anchor.BN(86400 * 7),
+        unresolvedFallback: { cancel: {} },
+      })
+      .accounts({
+        config: configPDA,
+        authority: creator.publicKey,
+      })
+      .signers([creator])
+      .rpc();
+
+    const creatorUSDC = await createAccount(
+      provider.connection,
+      creator,
+      usdcMint,
+      creator.publicKey,
+      Keypair.generate()
+    );
+    await mintTo(
+      provider.connection,
+      creator,
+      usdcMint,
+      creatorUSDC,
+      creator,
+      100_000_000
+    );
+
+    const timestamp = Math.floor(Date.now() / 1000);
+    const { totalMarkets } = await program.account.config.fetch(configPDA);
+    const marketPDA = marketAddress(totalMarkets);
+    const [vaultPDA] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("vault"), marketPDA.toBuffer()],
+      program.programId
+    );
+    const [yesMintPDA] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("yes_mint"), marketPDA.toBuffer()],
+      program.programId
+    );
+    const [noMintPDA] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("no_mint"), marketPDA.toBuffer()],
+      program.programId
+    );
+    const [priceHistoryPDA] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("price_history"), marketPDA.toBuffer()],
+      program.programId
+    );
+
+    await program.methods
+      .createMarket({
+        question: "Will SOL close above $500?",
+        description: "Short-lived market for the refund test",
+        category: 0,
+        endTime: new anchor.BN(timestamp + 4),
+        resolutionSource: "https://api.coingecko.com/sol",
+        initialLiquidity: new anchor.BN(100_000_000),
+        priceCondition: null,
+        resolutionMode: { daoVote: {} },
+        resolver: null,
+        votingPeriod: new anchor.BN(1),
+        resolutionGrace: new anchor.BN(4),
+        metadata: null,
+        tags: [],
+      })
+      .accounts({
+        config: configPDA,
+        market: marketPDA,
+        creator: creator.publicKey,
+        creatorCollateral: creatorUSDC,
+        liquidityVault: vaultPDA,
+        yesTokenMint: yesMintPDA,
+        noTokenMint: noMintPDA,
+        yesMetadata: metadataPDA(yesMintPDA),
+        noMetadata: metadataPDA(noMintPDA),
+        collateral: collateralPDA,
+        categoryAccount: categoryAddress(0),
+        parentCategoryAccount: null,
+        collateralMint: usdcMint,
+        tokenMetadataProgram: TOKEN_METADATA_PROGRAM_ID,
+      })
+      .signers([creator])
+      .rpc();
+
+    // One bettor on each side
+    const bettors = [
+      { user: user1, outcome: true, mint: yesMintPDA },
+      { user: user2, outcome: false, mint: noMintPDA },
+    ];
+    const collateralAccounts = [];
+    for (const { user, outcome, mint } of bettors) {
+      const userUSDC = await createAccount(
+        provider.connection,
+        user,
+        usdcMint,
+        user.publicKey,
+        Keypair.generate()
+      );
+      await mintTo(
+        provider.connection,
+        creator,
+        usdcMint,
+        userUSDC,
+        creator,
+        50_000_000
+      );
+      await program.methods
+        .placeBet(new anchor.BN(20_000_000), outcome, new anchor.BN(50_000_000))
+        .accounts({
+          config: configPDA,
+          market: marketPDA,
+          user: user.publicKey,
+          userCollateral: userUSDC,
+          liquidityVault: vaultPDA,
+          treasuryVault: treasuryVaultPDA,
+          collateral: collateralPDA,
+          collateralMint: usdcMint,
+          outcomeTokenMint: mint,
+        })
+        .signers([user])
+        .rpc();
+      collateralAccounts.push(userUSDC);
+    }
+
+    // Nobody proposes, so the round lapses and the market is cancelled
+    await new Promise(resolve => setTimeout(resolve, 10000));
+    await program.methods
+      .expireResolution()
+      .accounts({
+        config: configPDA,
+        market: marketPDA,
+        priceHistory: priceHistoryPDA,
+        governanceTokenMint: governanceMint,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .rpc();
+    expect((await program.account.market.fetch(marketPDA)).status).to.deep.equal({ cancelled: {} });
+
+    // The creator withdraws before anyone has claimed a refund
+    await program.methods
+      .withdrawCreatorLiquidity()
+      .accounts({
+        config: configPDA,
+        market: marketPDA,
+        creator: creator.publicKey,
+        creatorCollateral: creatorUSDC,
+        liquidityVault: vaultPDA,
+        collateralMint: usdcMint,
+        yesTokenMint: yesMintPDA,
+        noTokenMint: noMintPDA,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        gauge: null,
+        rewardPosition: null,
+      })
+      .signers([creator])
+      .rpc();
+
+    // Every holder can still redeem all their tokens
+    for (const [i, { user, outcome, mint }] of bettors.entries()) {
+      const outcomeToken = getAssociatedTokenAddressSync(mint, user.publicKey);
+      const { amount } = await getAccount(provider.connection, outcomeToken);
+      await program.methods
+        .claimRefund(new anchor.BN(amount.toString()), outcome)
+        .accounts({
+          config: configPDA,
+          market: marketPDA,
+          user: user.publicKey,
+          userCollateral: collateralAccounts[i],
+          liquidityVault: vaultPDA,
+          collateralMint: usdcMint,
+          yesTokenMint: yesMintPDA,
+          noTokenMint: noMintPDA,
+          userOutcomeToken: outcomeToken,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([user])
+        .rpc();
+      expect((await getAccount(provider.connection, outcomeToken)).amount.toString()).to.equal("0");
+    }
+    expect((await getMint(provider.connection, yesMintPDA)).supply.toString()).to.equal("0");
+    expect((await getMint(provider.connection, noMintPDA)).supply.toString()).to.equal("0");
+
+    await program.methods
+      .setResolutionPeriods({
+        minVotingPeriod: new anchor.BN(1),
+        maxVotingPeriod: new anchor.BN(86400 * 30),
+        defaultVotingPeriod: new anchor.BN(86400 * 2),
+        minResolutionGrace: new anchor.BN(1),
+        maxResolutionGrace: new anchor.BN(86400 * 90),
+        defaultResolutionGrace: new anchor.BN(86400 * 7),
+        unresolvedFallback: { extendRound: {} },
+      })
+      .accounts({
+        config: configPDA,
+        authority: creator.publicKey,
+      })
+      .signers([creator])
+      .rpc();
+  });
+
+  it("Each pause flag blocks the instructions it covers", async () => {
+    const PAUSE_TRADING = 1;
+    const PAUSE_MARKET_CREATION = 2;
+    const PAUSE_PROPOSALS = 4;
+    const PAUSE_CLAIMS = 8;
+
+    const markets = await program.account.market.all();
+    const findMarket = (question: string) =>
+      markets.find((market) => market.account.question === question).publicKey;
+    const openMarket = findMarket("Will BTC hit $100K by end of 2025?");
+    const resolvedMarket = findMarket("Will BTC close above $100K?");
+    const pda = (seed: string, market: anchor.web3.PublicKey) =>
+      anchor.web3.PublicKey.findProgramAddressSync(
+        [Buffer.from(seed), market.toBuffer()],
+        program.programId
+      )[0];
+
+    const user1USDC = await createAccount(
+      provider.connection,
+      user1,
+      usdcMint,
+      user1.publicKey,
+      Keypair.generate()
+    );
+    const creatorUSDC = await createAccount(
+      provider.connection,
+      creator,
+      usdcMint,
+      creator.publicKey,
+      Keypair.generate()
+    );
+    await mintTo(provider.connection, creator, usdcMint, user1USDC, creator, 10_000_000);
+    await mintTo(provider.connection, creator, usdcMint, creatorUSDC, creator, 100_000_000);
+    const user1Gov = await createAccount(
+      provider.connection,
+      user1,
+      governanceMint,
+      user1.publicKey,
+      Keypair.generate()
+    );
+    const user1Yes = getAssociatedTokenAddressSync(pda("yes_mint", openMarket), user1.publicKey);
+
+    const newMarket = async () => {
+      const { totalMarkets } = await program.account.config.fetch(configPDA);
+      const market = marketAddress(totalMarkets);
+      return program.methods
+        .createMarket({
+          question: "Created while paused?",
+          description: "Must be refused",
+          category: 0,
+          endTime: new anchor.BN(Math.floor(Date.now() / 1000) + 86400),
+          resolutionSource: "https://example.com",
+          initialLiquidity: new anchor.BN(100_000_000),
+          priceCondition: null,
+          resolutionMode: { daoVote: {} },
+          resolver: null,
+          votingPeriod: null,
+          resolutionGrace: null,
+          metadata: null,
+          tags: [],
+        })
+        .accounts({
+          config: configPDA,
+          market,
+          creator: creator.publicKey,
+          creatorCollateral: creatorUSDC,
+          liquidityVault: pda("vault", market),
+          yesTokenMint: pda("yes_mint", market),
+          noTokenMint: pda("no_mint", market),
+          yesMetadata: metadataPDA(pda("yes_mint", market)),
+          noMetadata: metadataPDA(pda("no_mint", market)),
+          collateral: collateralPDA,
+          categoryAccount: categoryAddress(0),
+          parentCategoryAccount: null,
+          collateralMint: usdcMint,
+          tokenMetadataProgram: TOKEN_METADATA_PROGRAM_ID,
+        })
+        .signers([creator])
+        .rpc();
+    };
+
+    const covered: Record<number, Record<string, () => Promise<unknown>>> = {
+      [PAUSE_TRADING]: {
+        placeBet: () =>
+          program.methods
+            .placeBet(new anchor.BN(1_000_000), true, new anchor.BN(2_000_000))
+            .accounts({
+              config: configPDA,
+              market: openMarket,
+              user: user1.publicKey,
+              userCollateral: user1USDC,
+              liquidityVault: pda("vault", openMarket),
+              treasuryVault: treasuryVaultPDA,
+              collateral: collateralPDA,
+              collateralMint: usdcMint,
+              outcomeTokenMint: pda("yes_mint", openMarket),
+            })
+            .signers([user1])
+            .rpc(),
+        createOrderBook: () =>
+          program.methods
+            .createOrderBook()
+            .accounts({
+              config: configPDA,
+              market: openMarket,
+              orderBook: pda("order_book", openMarket),
+              bookCollateralVault: pda("book_collateral", openMarket),
+              bookYesVault: pda("book_yes", openMarket),
+              bookNoVault: pda("book_no", openMarket),
+              collateralMint: usdcMint,
+              yesTokenMint: pda("yes_mint", openMarket),
+              noTokenMint: pda("no_mint", openMarket),
+              payer: creator.publicKey,
+              tokenProgram: TOKEN_PROGRAM_ID,
+            })
+            .signers([creator])
+            .rpc(),
+      },
+      [PAUSE_MARKET_CREATION]: {
+        createMarket: newMarket,
+        editMarket: () => editMarket("Edited while paused"),
+        registerOutcomeCollateral: () =>
+          program.methods
+            .registerOutcomeCollateral()
+            .accounts({
+              config: configPDA,
+              market: openMarket,
+              outcomeMint: pda("yes_mint", openMarket),
+              parentCollateral: collateralPDA,
+              collateral: pda("collateral", pda("yes_mint", openMarket)),
+              payer: creator.publicKey,
+            })
+            .signers([creator])
+            .rpc(),
+      },
+      [PAUSE_PROPOSALS]: {
+        proposeResolution: () => {
+          const proposal = anchor.web3.PublicKey.findProgramAddressSync(
+            [Buffer.from("proposal"), openMarket.toBuffer(), user1.publicKey.toBuffer()],
+            program.programId
+          )[0];
+          return program.methods
+            .proposeResolution(true, "Proposed while paused")
+            .accounts({
+              config: configPDA,
+              market: openMarket,
+              proposal,
+              proposer: user1.publicKey,
+              proposerGovToken: user1Gov,
+              proposalStakeVault: pda("proposal_stake", proposal),
+              governanceTokenMint: governanceMint,
+              tokenProgram: TOKEN_PROGRAM_ID,
+            })
+            .signers([user1])
+            .rpc();
+        },
+        expireResolution: () =>
+          program.methods
+            .expireResolution()
+            .accounts({
+              config: configPDA,
+              market: openMarket,
+              priceHistory: pda("price_history", openMarket),
+              governanceTokenMint: governanceMint,
+              tokenProgram: TOKEN_PROGRAM_ID,
+            })
+            .rpc(),
+        resolveFromOracle: () =>
+          program.methods
+            .resolveFromOracle()
+            .accounts({
+              config: configPDA,
+              market: openMarket,
+              priceFeed: new anchor.web3.PublicKey("2AWKJYorB95TZzHRzFAz4U5MVtQGs5JSmxnsBQDUAgj6"),
+            })
+            .rpc(),
+      },
+      [PAUSE_CLAIMS]: {
+        claimWinnings: () =>
+          program.methods
+            .claimWinnings(new anchor.BN(1))
+            .accounts({
+              config: configPDA,
+              market: resolvedMarket,
+              user: user1.publicKey,
+              userCollateral: user1USDC,
+              liquidityVault: pda("vault", resolvedMarket),
+              collateralMint: usdcMint,
+              yesTokenMint: pda("yes_mint", resolvedMarket),
+              noTokenMint: pda("no_mint", resolvedMarket),
+              userOutcomeToken: user1Yes,
+              tokenProgram: TOKEN_PROGRAM_ID,
+            })
+            .signers([user1])
+            .rpc(),
+      },
+    };
+
+    const setPauseFlags = (flags: number) =>
+      program.methods
+        .setPauseFlags(flags)
+        .accounts({
+          config: configPDA,
+          guardian: creator.publicKey,
+        })
+        .signers([creator])
+        .rpc();
+
+    // Each instruction is refused under its own flag and under every flag at once
+    const PAUSE_ALL = PAUSE_TRADING | PAUSE_MARKET_CREATION | PAUSE_PROPOSALS | PAUSE_CLAIMS;
+    for (const flags of [PAUSE_TRADING, PAUSE_MARKET_CREATION, PAUSE_PROPOSALS, PAUSE_CLAIMS, PAUSE_ALL]) {
+      await setPauseFlags(flags);
+      for (const [flag, calls] of Object.entries(covered)) {
+        if ((flags & Number(flag)) === 0) {
+          continue;
+        }
+        for (const [name, call] of Object.entries(calls)) {
+          try {
+            await call();
+            expect.fail(`${name} ran with pause flags ${flags}`);
+          } catch (err) {
+            expect(err.error?.errorCode?.code, name).to.equal("ProtocolPaused");
+          }
+        }
+      }
+    }
+    await setPauseFlags(0);
   });
 
   // Additional tests for proposal, voting, execution, and claiming would go here
@@ -0,0 +1,197 @@
+use std::collections::{BTreeMap, HashSet};
+use std::str::FromStr;
+
+use anchor_lang::prelude::Pubkey;
+use governance_token::merkle;
+use serde::Serialize;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Recipient {
+    pub wallet: Pubkey,
+    pub amount: u64,
+}
+
+#[derive(Debug, Serialize)]
+pub struct Claim {
+    pub index: u64,
+    pub amount: String,
+    pub proof: Vec<[u8; 32]>,
+}
+
+/// Everything `create_distributor` and `claim_airdrop` need
+#[derive(Debug, Serialize)]
+pub struct Distribution {
+    pub root: [u8; 32],
+    pub max_total_claim: String,
+    pub max_num_nodes: u64,
+    pub claims: BTreeMap<String, Claim>,
+}
+
+/// Parse `wallet,amount` rows. A header row and blank lines are skipped.
+pub fn parse_csv(input: &str) -> Result<Vec<Recipient>, String> {
+    let mut recipients = Vec::new();
+    let mut seen = HashSet::new();
+
+    for (line_no, line) in input.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() {
+            continue;
+        }
+
+        let mut fields = line.split(',').map(str::trim);
+        let (wallet, amount) = match (fields.next(), fields.next(), fields.next()) {
+            (Some(wallet), Some(amount), None) => (wallet, amount),
+            _ => return Err(format!("line {}: expected `wallet,amount`", line_no + 1)),
+        };
+
+        let wallet = match Pubkey::from_str(wallet) {
+            Ok(wallet) => wallet,
+            Err(_) if line_no == 0 => continue, // header
+            Err(_) => return Err(format!("line {}: invalid wallet `{}`", line_no + 1, wallet)),
+        };
+        let amount = amount
+            .parse::<u64>()
+            .map_err(|_| format!("line {}: invalid amount `{}`", line_no + 1, amount))?;
+
+        if amount == 0 {
+            return Err(format!("line {}: amount must be greater than zero", line_no + 1));
+        }
+        if !seen.insert(wallet) {
+            return Err(format!("line {}: duplicate wallet {}", line_no + 1, wallet));
+        }
+
+        recipients.push(Recipient { wallet, amount });
+    }
+
+    if recipients.is_empty() {
+        return Err("no recipients".to_string());
+    }
+
+    Ok(recipients)
+}
+
+/// Build the tree bottom-up. An unpaired node is promoted unchanged to the
+/// next level, so its proof simply has no sibling at that level.
+pub fn build(recipients: &[Recipient]) -> Result<Distribution, String> {
+    let leaves: Vec<[u8; 32]> = recipients
+        .iter()
+        .enumerate()
+        .map(|(i, r)| merkle::leaf(i as u64, &r.wallet, r.amount))
+        .collect();
+
+    let mut levels = vec![leaves];
+    while levels.last().map_or(0, Vec::len) > 1 {
+        let next = levels
+            .last()
+            .unwrap()
+            .chunks(2)
+            .map(|pair| match pair {
+                [a, b] => merkle::hash_pair(a, b),
+                [a] => *a,
+                _ => unreachable!(),
+            })
+            .collect();
+        levels.push(next);
+    }
+    let root = levels.last().unwrap()[0];
+
+    let mut total: u64 = 0;
+    let mut claims = BTreeMap::new();
+    for (i, recipient) in recipients.iter().enumerate() {
+        total = total
+            .checked_add(recipient.amount)
+            .ok_or("total amount overflows u64")?;
+
+        let mut proof = Vec::new();
+        let mut position = i;
+        for level in &levels[..levels.len() - 1] {
+            let sibling = position ^ 1;
+            if sibling < level.len() {
+                proof.push(level[sibling]);
+            }
+            position /= 2;
+        }
+
+        claims.insert(
+            recipient.wallet.to_string(),
+            Claim {
+                index: i as u64,
+                amount: recipient.amount.to_string(),
+                proof,
+            },
+        );
+    }
+
+    Ok(Distribution {
+        root,
+        max_total_claim: total.to_string(),
+        max_num_nodes: recipients.len() as u64,
+        claims,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn recipients(n: usize) -> Vec<Recipient> {
+        (0..n)
+            .map(|i| Recipient {
+                wallet: Pubkey::new_unique(),
+                amount: 1_000 * (i as u64 + 1),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_every_proof_verifies() {
+        for n in 1..=9 {
+            let recipients = recipients(n);
+            let distribution = build(&recipients).unwrap();
+
+            for recipient in &recipients {
+                let claim = &distribution.claims[&recipient.wallet.to_string()];
+                let leaf = merkle::leaf(claim.index, &recipient.wallet, recipient.amount);
+                assert!(
+                    merkle::verify(&claim.proof, distribution.root, leaf),
+                    "proof failed for {} of {}",
+                    claim.index,
+                    n
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_totals() {
+        let distribution = build(&recipients(4)).unwrap();
+        assert_eq!(distribution.max_total_claim, "10000");
+        assert_eq!(distribution.max_num_nodes, 4);
+    }
+
+    #[test]
+    fn test_parse_csv() {
+        let a = Pubkey::new_unique();
+        let b = Pubkey::new_unique();
+        let csv = format!("wallet,amount\n{},100\n\n{}, 250\n", a, b);
+
+        let parsed = parse_csv(&csv).unwrap();
+        assert_eq!(
+            parsed,
+            vec![
+                Recipient { wallet: a, amount: 100 },
+                Recipient { wallet: b, amount: 250 },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_csv_rejects_bad_rows() {
+        let a = Pubkey::new_unique();
+        assert!(parse_csv(&format!("{},100\n{},5\n", a, a)).is_err()); // duplicate
+        assert!(parse_csv(&format!("{},0\n", a)).is_err());
+        assert!(parse_csv(&format!("{},-1\n", a)).is_err());
+        assert!(parse_csv(&format!("{},1\nnot-a-key,1\n", a)).is_err());
+        assert!(parse_csv("wallet,amount\n").is_err());
+    }
+}
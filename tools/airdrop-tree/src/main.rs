@@ -0,0 +1,38 @@
+//! Usage: airdrop-tree <recipients.csv> [distribution.json]
+//!
+//! Reads `wallet,amount` rows (amounts in base units) and writes the merkle
+//! root, totals and per-wallet proofs as JSON.
+
+use std::{env, fs, process};
+
+fn main() {
+    let args: Vec<String> = env::args().collect();
+    if args.len() < 2 || args.len() > 3 {
+        eprintln!("usage: {} <recipients.csv> [distribution.json]", args[0]);
+        process::exit(2);
+    }
+
+    if let Err(err) = run(&args[1], args.get(2)) {
+        eprintln!("error: {}", err);
+        process::exit(1);
+    }
+}
+
+fn run(input: &str, output: Option<&String>) -> Result<(), String> {
+    let csv = fs::read_to_string(input).map_err(|e| format!("{}: {}", input, e))?;
+    let recipients = airdrop_tree::parse_csv(&csv)?;
+    let distribution = airdrop_tree::build(&recipients)?;
+    let json = serde_json::to_string_pretty(&distribution).map_err(|e| e.to_string())?;
+
+    match output {
+        Some(path) => fs::write(path, json).map_err(|e| format!("{}: {}", path, e))?,
+        None => println!("{}", json),
+    }
+
+    let root: String = distribution.root.iter().map(|b| format!("{:02x}", b)).collect();
+    eprintln!(
+        "{} recipients, {} total, root {}",
+        distribution.max_num_nodes, distribution.max_total_claim, root
+    );
+    Ok(())
+}